@@ -0,0 +1,45 @@
+use leptos::prelude::*;
+use models::entities::AuditLogPage;
+use models::errors::SystemError;
+use models::payloads::AuditLogQuery;
+use tracing::instrument;
+
+#[cfg(feature = "ssr")]
+use crate::helper::require_session;
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+
+/// The workspace's audit log, newest first, narrowed by `query`.
+#[instrument(err, skip_all)]
+#[server(GetAuditLog)]
+pub async fn get_audit_log(query: AuditLogQuery) -> Result<AuditLogPage, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let query = query.normalized();
+        if let (Some(since), Some(until)) = (query.since, query.until)
+            && since >= until
+        {
+            return Err(SystemError::validation(
+                "The start of the range must be before its end.",
+            ));
+        }
+
+        let session = require_session().await?;
+        session.require(Permission::ViewAuditLog)?;
+        let mut conn = session.acquire().await?;
+
+        let (items, total) =
+            crate::db_ops::list_audit_events(&mut conn, &session.user.system_id, &query).await?;
+        Ok(AuditLogPage {
+            items,
+            total,
+            page: query.page,
+            page_size: query.page_size,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = query;
+        unreachable!()
+    }
+}
@@ -1,19 +1,21 @@
 #[cfg(feature = "ssr")]
-use crate::db_ops::auth::{
-    create_session, delete_session, get_session_user, hash_password, refresh_session_expiry,
-    verify_password,
-};
+use crate::db_ops::auth::{create_session, delete_session, hash_password, verify_password};
 #[cfg(feature = "ssr")]
 use crate::db_ops::{create_handler, create_system, get_handler_auth_data};
 #[cfg(feature = "ssr")]
+use crate::helper::Audit;
+#[cfg(feature = "ssr")]
 use models::HandlerRole;
 #[cfg(feature = "ssr")]
-use sqlx::PgPool;
+use models::entities::{AuditAction, AuditEntity};
 
 use leptos::prelude::*;
-use models::entities::User;
+use models::entities::{LoginOutcome, User};
 use models::errors::SystemError;
-use models::payloads::{AuthenticateUserPayload, RegisterWorkspacePayload};
+use models::payloads::{
+    AuthenticateUserPayload, RegisterWorkspacePayload, RequestPasswordResetPayload,
+    ResetPasswordPayload,
+};
 use tracing::instrument;
 
 #[instrument(ret, err, skip_all, fields(email = %payload.email))]
@@ -21,18 +23,19 @@ use tracing::instrument;
 pub async fn register_workspace(payload: RegisterWorkspacePayload) -> Result<User, SystemError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::helper::set_session_token;
         use validator::Validate;
 
         // Validate payload
         payload
             .validate()
             .map_err(|e| SystemError::validation(e.to_string()))?;
+        // Sign-in matches emails case-insensitively, so store the one form it compares against
+        let payload = RegisterWorkspacePayload {
+            email: payload.email.trim().to_lowercase(),
+            ..payload
+        };
 
-        // get Postgres pool from context
-        let pool = use_context::<PgPool>().ok_or_else(|| {
-            SystemError::database("Database connection pool not found in context.")
-        })?;
+        let pool = crate::helper::db_pool()?;
 
         let password_hash = hash_password(payload.password.clone()).await?;
         let owner_id = uuid::Uuid::new_v4();
@@ -53,6 +56,24 @@ pub async fn register_workspace(payload: RegisterWorkspacePayload) -> Result<Use
         )
         .await?;
 
+        Audit::new(AuditEntity::Workspace, AuditAction::Created)
+            .entity_id(system_id)
+            .after(&serde_json::json!({
+                "system_name": payload.system_name,
+                "workspace_handle": payload.workspace_handle,
+            }))
+            .record(&mut tx, &system_id, Some(&handler_id))
+            .await?;
+        Audit::new(AuditEntity::Handler, AuditAction::Created)
+            .entity_id(handler_id)
+            .after(&serde_json::json!({
+                "email": payload.email,
+                "user_name": payload.user_name,
+                "handler_role": HandlerRole::SystemAdmin,
+            }))
+            .record(&mut tx, &system_id, Some(&handler_id))
+            .await?;
+
         let avatar_url = format!(
             "https://api.dicebear.com/7.x/initials/svg?seed={}&backgroundColor=8b5cf6,06b6d4&backgroundType=gradientLinear&fontFamily=monospace&fontSize=40",
             payload.system_name
@@ -72,16 +93,7 @@ pub async fn register_workspace(payload: RegisterWorkspacePayload) -> Result<Use
             system_name: payload.system_name.clone(),
         };
 
-        let token = uuid::Uuid::new_v4().to_string();
-        let bundle = create_session(&mut tx, &auth_data, &token).await?;
-
-        tx.commit()
-            .await
-            .map_err(|e| SystemError::database(e.to_string()))?;
-
-        set_session_token(&bundle.token)?;
-
-        Ok(bundle.user)
+        start_session(tx, &auth_data).await
     }
     #[cfg(not(feature = "ssr"))]
     {
@@ -92,41 +104,89 @@ pub async fn register_workspace(payload: RegisterWorkspacePayload) -> Result<Use
 
 #[instrument(err, skip_all, fields(email = %payload.email))]
 #[server(AuthenticateUser)]
-pub async fn authenticate_user(payload: AuthenticateUserPayload) -> Result<User, SystemError> {
+pub async fn authenticate_user(
+    payload: AuthenticateUserPayload,
+) -> Result<LoginOutcome, SystemError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::helper::set_session_token;
+        use crate::db_ops::auth::{record_login_attempt, verify_dummy_password};
+        use crate::db_ops::{LoginChallengeKind, get_two_factor_state, insert_login_challenge};
+        use crate::helper::{digest_token, generate_token};
+        use models::auth::AuthError;
         use validator::Validate;
 
         payload
             .validate()
             .map_err(|e| SystemError::validation(e.to_string()))?;
 
-        let pool = use_context::<PgPool>().ok_or_else(|| {
-            SystemError::database("Database connection pool not found in context.")
-        })?;
+        let pool = crate::helper::db_pool()?;
+        let email = payload.email.trim().to_lowercase();
+        let ip_address = crate::helper::client_ip().await;
 
         let mut tx = pool
             .begin()
             .await
             .map_err(|e| SystemError::database(e.to_string()))?;
 
-        let data = get_handler_auth_data(&mut tx, &payload.email).await?;
+        // 1. Refuse outright while this account or client is backing off
+        enforce_login_throttle(&mut tx, &email, ip_address.as_deref()).await?;
 
-        if !verify_password(payload.password.clone(), data.password_hash.clone()).await? {
-            return Err(SystemError::unauthorized("Invalid credentials"));
-        }
+        // 2. Unknown emails still pay for a full hash verification
+        let data = get_handler_auth_data(&mut tx, &email).await?;
+        let verified = match &data {
+            Some(data) => {
+                verify_password(payload.password.clone(), data.password_hash.clone()).await?
+            }
+            None => {
+                verify_dummy_password(payload.password.clone()).await?;
+                false
+            }
+        };
 
-        let token = uuid::Uuid::new_v4().to_string();
-        let bundle = crate::db_ops::auth::create_session(&mut tx, &data, &token).await?;
+        // On a workspace subdomain, only that workspace's handlers may sign in
+        let workspace = crate::workspace::current_workspace();
+        let in_workspace = |data: &crate::db_ops::handler::HandlerAuthData| {
+            workspace.as_ref().is_none_or(|w| w.id == data.system_id)
+        };
 
-        tx.commit()
-            .await
-            .map_err(|e| SystemError::database(e.to_string()))?;
+        let Some(data) = data.filter(|d| verified && in_workspace(d)) else {
+            record_login_attempt(&mut tx, &email, ip_address.as_deref(), false).await?;
+            tx.commit()
+                .await
+                .map_err(|e| SystemError::database(e.to_string()))?;
+            tracing::warn!(ip = ?ip_address, "Failed sign-in attempt");
+            return Err(AuthError::InvalidCredentials.into());
+        };
+
+        // 3. A second factor, or enrolling one, stands between the password and a session.
+        //    The attempt only counts as a success once that step is passed too.
+        let two_factor = get_two_factor_state(&mut tx, &data.handler_id).await?;
+        let challenge_kind = if two_factor.totp_secret.is_some() {
+            Some(LoginChallengeKind::Verify)
+        } else if two_factor.workspace_requires && data.handler_role.is_privileged() {
+            Some(LoginChallengeKind::Enrol)
+        } else {
+            None
+        };
+
+        if let Some(kind) = challenge_kind {
+            let challenge = generate_token();
+            insert_login_challenge(&mut tx, &data.handler_id, kind, &digest_token(&challenge))
+                .await?;
+            tx.commit()
+                .await
+                .map_err(|e| SystemError::database(e.to_string()))?;
 
-        set_session_token(&bundle.token)?;
+            return Ok(match kind {
+                LoginChallengeKind::Verify => LoginOutcome::TwoFactorRequired { challenge },
+                LoginChallengeKind::Enrol => LoginOutcome::TwoFactorSetupRequired { challenge },
+            });
+        }
+
+        record_login_attempt(&mut tx, &email, ip_address.as_deref(), true).await?;
+        let user = start_session(tx, &data).await?;
 
-        Ok(bundle.user)
+        Ok(LoginOutcome::Authenticated(user))
     }
     #[cfg(not(feature = "ssr"))]
     {
@@ -135,6 +195,61 @@ pub async fn authenticate_user(payload: AuthenticateUserPayload) -> Result<User,
     }
 }
 
+/// Refuses while this account or client is backing off from failed sign-ins. The attempt
+/// locks are taken first and hold until the caller records its own attempt and commits, so
+/// parallel requests are counted one by one.
+#[cfg(feature = "ssr")]
+pub(crate) async fn enforce_login_throttle(
+    tx: &mut sqlx::PgConnection,
+    email: &str,
+    ip_address: Option<&str>,
+) -> Result<(), SystemError> {
+    use crate::db_ops::auth::{email_failure_streak, ip_failure_streak, lock_login_attempts};
+    use models::auth::{AuthError, LoginThrottle};
+
+    lock_login_attempts(tx, email, ip_address).await?;
+    let account = email_failure_streak(tx, email).await?;
+    let mut retry_after =
+        LoginThrottle::ACCOUNT.retry_after_secs(account.failures, account.secs_since_last);
+    if let Some(ip) = ip_address {
+        let client = ip_failure_streak(tx, ip).await?;
+        retry_after = retry_after
+            .max(LoginThrottle::CLIENT.retry_after_secs(client.failures, client.secs_since_last));
+    }
+    if let Some(secs) = retry_after {
+        tracing::warn!(ip = ?ip_address, retry_after = secs, "Sign-in throttled");
+        return Err(AuthError::TooManyAttempts(secs).into());
+    }
+
+    Ok(())
+}
+
+/// Creates a session for `data`, commits `tx` and sets the session cookie.
+#[cfg(feature = "ssr")]
+pub(crate) async fn start_session(
+    mut tx: sqlx::Transaction<'static, sqlx::Postgres>,
+    data: &crate::db_ops::handler::HandlerAuthData,
+) -> Result<User, SystemError> {
+    let config = crate::config::app_config()?;
+    let device = crate::helper::client_device().await;
+    let token = crate::helper::generate_token();
+    let bundle = create_session(&mut tx, data, &token, &device, config.session.ttl).await?;
+
+    Audit::new(AuditEntity::Handler, AuditAction::SignedIn)
+        .entity_id(data.handler_id)
+        .after(&serde_json::json!({ "user_agent": device.user_agent }))
+        .record(&mut tx, &data.system_id, Some(&data.handler_id))
+        .await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    crate::helper::set_session_token(&bundle.token)?;
+
+    Ok(bundle.user)
+}
+
 #[instrument(skip_all)]
 #[server(ValidateSession)]
 pub async fn validate_session() -> Result<Option<User>, SystemError> {
@@ -142,37 +257,14 @@ pub async fn validate_session() -> Result<Option<User>, SystemError> {
     {
         tracing::debug!("Executing ValidateSession server function");
 
-        let pool = use_context::<PgPool>().ok_or_else(|| {
-            tracing::error!(
-                "CRITICAL: use_context::<PgPool>() returned None in validate_session()"
-            );
-            SystemError::database("Database connection pool not found in context.")
-        })?;
-
-        let token = crate::helper::get_session_token().await;
-
-        tracing::debug!(
-            token_resolved = token.is_some(),
-            "Session token resolution check"
-        );
-
-        match token {
-            Some(t) => {
-                let mut conn = pool
-                    .acquire()
-                    .await
-                    .map_err(|e| SystemError::database(e.to_string()))?;
-
-                let user = get_session_user(&mut conn, &t).await?;
-                if let Some(ref u) = user {
-                    tracing::info!(email = %u.email, "Session successfully authenticated for user");
-                    let _ = refresh_session_expiry(&mut conn, &t).await;
-                }
-                tracing::debug!(user_fetched = user.is_some(), "Database user fetch check");
-                Ok(user)
+        let session = crate::helper::current_session().await?;
+        match session {
+            Some(session) => {
+                tracing::info!(email = %session.user.email, "Session successfully authenticated for user");
+                Ok(Some(session.user))
             }
             None => {
-                tracing::debug!("No active session token found; returning no user.");
+                tracing::debug!("No active session found; returning no user.");
                 Ok(None)
             }
         }
@@ -188,19 +280,17 @@ pub async fn validate_session() -> Result<Option<User>, SystemError> {
 pub async fn logout() -> Result<(), SystemError> {
     #[cfg(feature = "ssr")]
     {
-        let pool = use_context::<PgPool>().ok_or_else(|| {
-            SystemError::database("Database connection pool not found in context.")
-        })?;
-
-        let token = crate::helper::get_session_token().await;
-
-        if let Some(t) = token {
-            let mut conn = pool
-                .acquire()
+        if let Some(session) = crate::helper::current_session().await? {
+            let user = &session.user;
+            let mut tx = session.begin().await?;
+            delete_session(&mut tx, &session.token_hash).await?;
+            Audit::new(AuditEntity::Handler, AuditAction::SignedOut)
+                .entity_id(user.id)
+                .record(&mut tx, &user.system_id, Some(&user.id))
+                .await?;
+            tx.commit()
                 .await
                 .map_err(|e| SystemError::database(e.to_string()))?;
-
-            delete_session(&mut conn, &t).await?;
         }
 
         crate::helper::remove_session_token()?;
@@ -212,3 +302,141 @@ pub async fn logout() -> Result<(), SystemError> {
         unreachable!()
     }
 }
+
+/// Reset requests one email address may make per hour.
+#[cfg(feature = "ssr")]
+const MAX_RESET_REQUESTS_PER_EMAIL: i64 = 3;
+
+/// Reset requests one client IP may make per hour, across any number of emails.
+#[cfg(feature = "ssr")]
+const MAX_RESET_REQUESTS_PER_IP: i64 = 10;
+
+/// Emails a single-use reset link. Always succeeds so the response never reveals
+/// whether an account exists for the address; requests over the hourly limits are
+/// dropped just as quietly.
+#[instrument(err, skip_all, fields(email = %payload.email))]
+#[server(RequestPasswordReset)]
+pub async fn request_password_reset(
+    payload: RequestPasswordResetPayload,
+) -> Result<(), SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::auth::{
+            find_active_handler_id, insert_password_reset_token, lock_password_reset_requests,
+            recent_password_reset_requests, record_password_reset_request,
+        };
+        use crate::helper::{db_pool, digest_token, generate_token};
+        use crate::mail::{OutgoingMail, mailer};
+
+        payload.validate()?;
+
+        let mailer = mailer()?;
+        let pool = db_pool()?;
+        let email = payload.email.trim().to_lowercase();
+        let ip_address = crate::helper::client_ip().await;
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        lock_password_reset_requests(&mut tx, &email, ip_address.as_deref()).await?;
+        let recent = recent_password_reset_requests(&mut tx, &email, ip_address.as_deref()).await?;
+        if recent.email >= MAX_RESET_REQUESTS_PER_EMAIL || recent.ip >= MAX_RESET_REQUESTS_PER_IP {
+            tracing::warn!(ip = ?ip_address, "Password reset requests throttled");
+            return Ok(());
+        }
+        record_password_reset_request(&mut tx, &email, ip_address.as_deref()).await?;
+
+        // Issuing a token discards the handler's earlier unused ones, so at most one is live
+        let token = match find_active_handler_id(&mut tx, &email).await? {
+            Some(handler_id) => {
+                let token = generate_token();
+                insert_password_reset_token(&mut tx, &handler_id, &digest_token(&token)).await?;
+                Some(token)
+            }
+            None => None,
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        let Some(token) = token else {
+            tracing::info!("Password reset requested for unknown email");
+            return Ok(());
+        };
+
+        // Sent off the request path so known and unknown emails answer equally fast
+        let link = mailer.link(&format!("/auth/reset-password/{token}"));
+        tokio::spawn(async move {
+            let mail = OutgoingMail {
+                to: email,
+                subject: "Reset your Sharp System password".to_string(),
+                body: format!(
+                    "Someone asked to reset the password for this account.\n\n\
+                     Open this link within the next hour to choose a new one:\n{link}\n\n\
+                     If this wasn't you, you can ignore this email."
+                ),
+            };
+            if let Err(e) = mailer.send(mail).await {
+                tracing::error!(error = %e, "Failed to send password reset mail");
+            }
+        });
+
+        Ok(())
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Sets a new password from a reset link and signs the handler out of every session.
+#[instrument(err, skip_all)]
+#[server(ResetPassword)]
+pub async fn reset_password(payload: ResetPasswordPayload) -> Result<(), SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::auth::{consume_password_reset_token, update_password_hash};
+        use crate::helper::{db_pool, digest_token};
+
+        payload.validate()?;
+
+        let pool = db_pool()?;
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        let handler_id = consume_password_reset_token(&mut tx, &digest_token(&payload.token))
+            .await?
+            .ok_or_else(|| SystemError::not_found("This reset link is invalid or has expired."))?;
+
+        let password_hash = hash_password(payload.password.clone()).await?;
+        update_password_hash(&mut tx, &handler_id, &password_hash).await?;
+        let revoked = crate::db_ops::revoke_handler_sessions(&mut tx, &handler_id).await?;
+
+        let data = crate::db_ops::get_handler_auth_data_by_id(&mut tx, &handler_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Handler not found."))?;
+        Audit::new(AuditEntity::Handler, AuditAction::PasswordReset)
+            .entity_id(handler_id)
+            .after(&serde_json::json!({ "sessions_revoked": revoked }))
+            .record(&mut tx, &data.system_id, Some(&handler_id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(revoked, "Password reset completed");
+        Ok(())
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
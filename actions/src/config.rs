@@ -0,0 +1,321 @@
+//! Server settings read once at startup from the environment (and `.env`).
+//!
+//! `main` builds an [`AppConfig`], refuses to start if it is invalid, and provides it as
+//! context; helpers and actions read it back with [`app_config`].
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub database_url: String,
+    pub bind_address: SocketAddr,
+    /// Public URL that links in emails are built against.
+    pub base_url: String,
+    /// Domain workspaces are served under as `<handle>.<base_domain>`. Subdomain
+    /// resolution is off when unset.
+    pub base_domain: Option<String>,
+    pub mail_outbox_dir: PathBuf,
+    pub cookie: CookieConfig,
+    pub session: SessionConfig,
+    pub pool: PoolConfig,
+    /// Trust the last `X-Forwarded-For` entry as the client IP. Only enable behind a proxy
+    /// that appends to it.
+    pub trust_proxy_headers: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieConfig {
+    /// Send the session cookie over HTTPS only.
+    pub secure: bool,
+    /// Parent domain (e.g. `sharp.example`) so the cookie reaches workspace subdomains.
+    pub domain: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionConfig {
+    /// How long a session lives without being used.
+    pub ttl: Duration,
+    /// Minimum time between expiry extensions for an active session.
+    pub refresh_after: Duration,
+}
+
+impl SessionConfig {
+    /// Sessions whose remaining lifetime drops below this are extended back to `ttl`.
+    pub fn refresh_below(&self) -> Duration {
+        self.ttl.saturating_sub(self.refresh_after)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    pub key: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl AppConfig {
+    /// Reads the process environment. `default_bind` is used when `APP_BIND_ADDRESS` is unset.
+    pub fn from_env(default_bind: SocketAddr) -> Result<Self, ConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok(), default_bind)
+    }
+
+    pub fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+        default_bind: SocketAddr,
+    ) -> Result<Self, ConfigError> {
+        let var = |key: &str| {
+            lookup(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let database_url = var("DATABASE_URL").ok_or_else(|| ConfigError {
+            key: "DATABASE_URL",
+            message: "must be set".to_string(),
+        })?;
+
+        let bind_address = parse_or("APP_BIND_ADDRESS", var("APP_BIND_ADDRESS"), default_bind)?;
+
+        let base_url = var("APP_BASE_URL")
+            .unwrap_or_else(|| format!("http://{bind_address}"))
+            .trim_end_matches('/')
+            .to_string();
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err(ConfigError {
+                key: "APP_BASE_URL",
+                message: "must start with http:// or https://".to_string(),
+            });
+        }
+
+        let base_domain = var("APP_BASE_DOMAIN").map(|d| d.trim_matches('.').to_lowercase());
+        if let Some(domain) = &base_domain
+            && url_host(&base_url) != *domain
+        {
+            return Err(ConfigError {
+                key: "APP_BASE_DOMAIN",
+                message: format!("must be the host of APP_BASE_URL ({base_url})"),
+            });
+        }
+
+        let cookie = CookieConfig {
+            secure: parse_or(
+                "COOKIE_SECURE",
+                var("COOKIE_SECURE"),
+                base_url.starts_with("https://"),
+            )?,
+            domain: var("COOKIE_DOMAIN").map(|d| d.trim_start_matches('.').to_lowercase()),
+        };
+
+        let session = SessionConfig {
+            ttl: hours("SESSION_TTL_HOURS", var("SESSION_TTL_HOURS"), 7 * 24)?,
+            refresh_after: hours(
+                "SESSION_REFRESH_AFTER_HOURS",
+                var("SESSION_REFRESH_AFTER_HOURS"),
+                24,
+            )?,
+        };
+        if session.refresh_after >= session.ttl {
+            return Err(ConfigError {
+                key: "SESSION_REFRESH_AFTER_HOURS",
+                message: "must be shorter than SESSION_TTL_HOURS".to_string(),
+            });
+        }
+
+        let pool = PoolConfig {
+            max_connections: parse_or("DB_MAX_CONNECTIONS", var("DB_MAX_CONNECTIONS"), 20)?,
+            min_connections: parse_or("DB_MIN_CONNECTIONS", var("DB_MIN_CONNECTIONS"), 5)?,
+            acquire_timeout: Duration::from_secs(parse_or(
+                "DB_ACQUIRE_TIMEOUT_SECS",
+                var("DB_ACQUIRE_TIMEOUT_SECS"),
+                15,
+            )?),
+            idle_timeout: Duration::from_secs(parse_or(
+                "DB_IDLE_TIMEOUT_SECS",
+                var("DB_IDLE_TIMEOUT_SECS"),
+                600,
+            )?),
+        };
+        if pool.max_connections == 0 {
+            return Err(ConfigError {
+                key: "DB_MAX_CONNECTIONS",
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if pool.min_connections > pool.max_connections {
+            return Err(ConfigError {
+                key: "DB_MIN_CONNECTIONS",
+                message: "must not exceed DB_MAX_CONNECTIONS".to_string(),
+            });
+        }
+
+        Ok(Self {
+            database_url,
+            bind_address,
+            base_url,
+            base_domain,
+            mail_outbox_dir: var("MAIL_OUTBOX_DIR")
+                .unwrap_or_else(|| "outbox".to_string())
+                .into(),
+            cookie,
+            session,
+            pool,
+            trust_proxy_headers: parse_or(
+                "TRUST_PROXY_HEADERS",
+                var("TRUST_PROXY_HEADERS"),
+                false,
+            )?,
+        })
+    }
+}
+
+/// The host of an `http(s)://host[:port][/path]` URL, without the port.
+fn url_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split('/').next().unwrap_or_default();
+    authority
+        .rsplit_once(':')
+        .map_or(authority, |(host, _)| host)
+        .to_lowercase()
+}
+
+fn parse_or<T: std::str::FromStr>(
+    key: &'static str,
+    value: Option<String>,
+    default: T,
+) -> Result<T, ConfigError> {
+    match value {
+        None => Ok(default),
+        Some(raw) => raw.parse().map_err(|_| ConfigError {
+            key,
+            message: format!("`{raw}` is not a valid value"),
+        }),
+    }
+}
+
+fn hours(key: &'static str, value: Option<String>, default: u64) -> Result<Duration, ConfigError> {
+    let hours: u64 = parse_or(key, value, default)?;
+    if hours == 0 {
+        return Err(ConfigError {
+            key,
+            message: "must be at least 1".to_string(),
+        });
+    }
+    Ok(Duration::from_secs(hours * 60 * 60))
+}
+
+#[cfg(feature = "ssr")]
+pub fn app_config() -> Result<std::sync::Arc<AppConfig>, models::errors::SystemError> {
+    leptos::prelude::use_context::<std::sync::Arc<AppConfig>>()
+        .ok_or_else(|| models::errors::SystemError::general("AppConfig not found in context."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn load(vars: &[(&str, &str)]) -> Result<AppConfig, ConfigError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        AppConfig::from_lookup(
+            |key| vars.get(key).cloned(),
+            "127.0.0.1:3000".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = load(&[("DATABASE_URL", "postgres://localhost/sharp")]).unwrap();
+
+        assert_eq!(config.base_url, "http://127.0.0.1:3000");
+        assert!(!config.cookie.secure);
+        assert_eq!(config.cookie.domain, None);
+        assert_eq!(config.session.ttl, Duration::from_secs(7 * 24 * 3600));
+        assert_eq!(
+            config.session.refresh_below(),
+            Duration::from_secs(6 * 24 * 3600)
+        );
+        assert_eq!(config.pool.max_connections, 20);
+        assert!(!config.trust_proxy_headers);
+    }
+
+    #[test]
+    fn test_https_base_url_implies_secure_cookie() {
+        let config = load(&[
+            ("DATABASE_URL", "postgres://localhost/sharp"),
+            ("APP_BASE_URL", "https://sharp.example/"),
+            ("COOKIE_DOMAIN", ".Sharp.Example"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.base_url, "https://sharp.example");
+        assert!(config.cookie.secure);
+        assert_eq!(config.cookie.domain.as_deref(), Some("sharp.example"));
+    }
+
+    #[test]
+    fn test_base_domain_must_match_base_url() {
+        let config = load(&[
+            ("DATABASE_URL", "x"),
+            ("APP_BASE_URL", "http://localhost:3000"),
+            ("APP_BASE_DOMAIN", "localhost"),
+        ])
+        .unwrap();
+        assert_eq!(config.base_domain.as_deref(), Some("localhost"));
+
+        let err = load(&[
+            ("DATABASE_URL", "x"),
+            ("APP_BASE_URL", "http://localhost:3000"),
+            ("APP_BASE_DOMAIN", "sharp.example"),
+        ])
+        .unwrap_err();
+        assert_eq!(err.key, "APP_BASE_DOMAIN");
+    }
+
+    #[test]
+    fn test_rejects_invalid_settings() {
+        let key = |vars: &[(&str, &str)]| load(vars).unwrap_err().key;
+
+        assert_eq!(key(&[]), "DATABASE_URL");
+        assert_eq!(
+            key(&[("DATABASE_URL", "x"), ("APP_BASE_URL", "sharp.example")]),
+            "APP_BASE_URL"
+        );
+        assert_eq!(
+            key(&[("DATABASE_URL", "x"), ("COOKIE_SECURE", "maybe")]),
+            "COOKIE_SECURE"
+        );
+        assert_eq!(
+            key(&[
+                ("DATABASE_URL", "x"),
+                ("SESSION_TTL_HOURS", "12"),
+                ("SESSION_REFRESH_AFTER_HOURS", "12")
+            ]),
+            "SESSION_REFRESH_AFTER_HOURS"
+        );
+        assert_eq!(
+            key(&[("DATABASE_URL", "x"), ("DB_MIN_CONNECTIONS", "50")]),
+            "DB_MIN_CONNECTIONS"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use leptos::prelude::*;
+use models::entities::{Customer, CustomerHistory};
+use models::errors::SystemError;
+use models::payloads::CustomerPayload;
+use tracing::instrument;
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
+#[cfg(feature = "ssr")]
+use validator::Validate;
+
+/// Maximum number of customers returned by one search.
+#[cfg(feature = "ssr")]
+const CUSTOMER_SEARCH_LIMIT: i64 = 100;
+
+#[instrument(err, skip_all)]
+#[server(AddCustomer)]
+pub async fn add_customer(payload: CustomerPayload) -> Result<Customer, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let payload = payload.normalized();
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let customer =
+            crate::db_ops::insert_customer(&mut tx, &user.system_id, &payload, &user.id).await?;
+
+        Audit::new(AuditEntity::Customer, AuditAction::Created)
+            .entity_id(customer.id)
+            .after(&customer)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(customer)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all, fields(%customer_id))]
+#[server(UpdateCustomer)]
+pub async fn update_customer(
+    customer_id: Uuid,
+    payload: CustomerPayload,
+) -> Result<Customer, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let payload = payload.normalized();
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let current = crate::db_ops::get_customer(&mut tx, &customer_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Customer not found."))?;
+        let customer =
+            crate::db_ops::update_customer(&mut tx, &customer_id, &user.system_id, &payload)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Customer not found."))?;
+
+        Audit::new(AuditEntity::Customer, AuditAction::Updated)
+            .entity_id(customer.id)
+            .before(&current)
+            .after(&customer)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(customer)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (customer_id, payload);
+        unreachable!()
+    }
+}
+
+/// Removes a customer record. Their past sales stay on the books without a customer.
+#[instrument(err, skip_all, fields(%customer_id))]
+#[server(DeleteCustomer)]
+pub async fn delete_customer(customer_id: Uuid) -> Result<(), SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::DeleteCustomers)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let customer = crate::db_ops::delete_customer(&mut tx, &customer_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Customer not found."))?;
+
+        Audit::new(AuditEntity::Customer, AuditAction::Deleted)
+            .entity_id(customer.id)
+            .before(&customer)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = customer_id;
+        unreachable!()
+    }
+}
+
+/// Customers whose name, phone or email contains `search`; all of them when it is blank.
+#[instrument(err, skip_all)]
+#[server(GetCustomers)]
+pub async fn get_customers(search: Option<String>) -> Result<Vec<Customer>, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut conn = session.acquire().await?;
+
+        let search = search.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        crate::db_ops::search_customers(&mut conn, &user.system_id, search, CUSTOMER_SEARCH_LIMIT)
+            .await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = search;
+        unreachable!()
+    }
+}
+
+/// The customer's sales, refunds and the serialized units they still hold, with warranty.
+#[instrument(err, skip_all, fields(%customer_id))]
+#[server(GetCustomerHistory)]
+pub async fn get_customer_history(customer_id: Uuid) -> Result<CustomerHistory, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut conn = session.acquire().await?;
+
+        let customer = crate::db_ops::get_customer(&mut conn, &customer_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Customer not found."))?;
+        let sales = crate::db_ops::list_customer_sales(&mut conn, &customer.id).await?;
+        let returns = crate::db_ops::list_customer_returns(&mut conn, &customer.id).await?;
+        let units = crate::db_ops::list_customer_units(&mut conn, &customer.id).await?;
+
+        Ok(CustomerHistory {
+            customer,
+            sales,
+            returns,
+            units,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = customer_id;
+        unreachable!()
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity, AuditEvent};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use models::payloads::AuditLogQuery;
+#[cfg(feature = "ssr")]
+use serde_json::Value;
+#[cfg(feature = "ssr")]
+use sqlx::PgConnection;
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+/// An audit event ready to be written, as assembled by `helper::Audit`.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    pub entity_type: AuditEntity,
+    pub entity_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub before_state: Option<Value>,
+    pub after_state: Option<Value>,
+    pub ip_address: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_audit_event(
+    conn: &mut PgConnection,
+    system_id: &Uuid,
+    actor_id: Option<&Uuid>,
+    record: &AuditRecord,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_events
+            (system_id, actor_id, entity_type, entity_id, action, before_state, after_state, ip_address)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        system_id,
+        actor_id,
+        record.entity_type as AuditEntity,
+        record.entity_id,
+        record.action as AuditAction,
+        record.before_state,
+        record.after_state,
+        record.ip_address
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to write audit event: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(())
+}
+
+/// One page of the System's audit log, newest first, with the total matching count.
+#[cfg(feature = "ssr")]
+pub async fn list_audit_events(
+    conn: &mut PgConnection,
+    system_id: &Uuid,
+    query: &AuditLogQuery,
+) -> Result<(Vec<AuditEvent>, i64), SystemError> {
+    let total = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM audit_events
+        WHERE system_id = $1
+          AND ($2::audit_entity IS NULL OR entity_type = $2)
+          AND ($3::uuid IS NULL OR entity_id = $3)
+          AND ($4::uuid IS NULL OR actor_id = $4)
+          AND ($5::audit_action IS NULL OR action = $5)
+          AND ($6::timestamptz IS NULL OR occurred_at >= $6)
+          AND ($7::timestamptz IS NULL OR occurred_at < $7)
+        "#,
+        system_id,
+        query.entity_type as Option<AuditEntity>,
+        query.entity_id,
+        query.actor_id,
+        query.action as Option<AuditAction>,
+        query.since,
+        query.until
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count audit events: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    let events = sqlx::query_as!(
+        AuditEvent,
+        r#"
+        SELECT e.id, e.actor_id, h.user_name as "actor_name?",
+               e.entity_type as "entity_type: AuditEntity", e.entity_id,
+               e.action as "action: AuditAction",
+               e.before_state, e.after_state, e.ip_address, e.occurred_at
+        FROM audit_events e
+        LEFT JOIN handlers h ON h.id = e.actor_id
+        WHERE e.system_id = $1
+          AND ($2::audit_entity IS NULL OR e.entity_type = $2)
+          AND ($3::uuid IS NULL OR e.entity_id = $3)
+          AND ($4::uuid IS NULL OR e.actor_id = $4)
+          AND ($5::audit_action IS NULL OR e.action = $5)
+          AND ($6::timestamptz IS NULL OR e.occurred_at >= $6)
+          AND ($7::timestamptz IS NULL OR e.occurred_at < $7)
+        ORDER BY e.occurred_at DESC, e.id
+        LIMIT $8 OFFSET $9
+        "#,
+        system_id,
+        query.entity_type as Option<AuditEntity>,
+        query.entity_id,
+        query.actor_id,
+        query.action as Option<AuditAction>,
+        query.since,
+        query.until,
+        i64::from(query.page_size),
+        query.offset()
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch audit events: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok((events, total))
+}
@@ -37,6 +37,21 @@ pub async fn create_system(
     Ok(id)
 }
 
+/// Maps a duplicate email, whether it differs only by case or not, to a validation error.
+#[cfg(feature = "ssr")]
+pub(crate) fn map_handler_insert_error(e: sqlx::Error) -> SystemError {
+    if let Some(db_err) = e.as_database_error()
+        && matches!(
+            db_err.constraint(),
+            Some("handlers_email_key" | "handlers_email_lower_key")
+        )
+    {
+        return SystemError::validation("This email address is already registered.");
+    }
+    SystemError::database(e.to_string())
+}
+
+/// Expects `payload.email` to be trimmed and lowercased already.
 #[cfg(feature = "ssr")]
 pub async fn create_handler(
     tx: &mut PgConnection,
@@ -58,7 +73,7 @@ pub async fn create_handler(
     )
     .fetch_one(&mut *tx)
     .await
-    .map_err(|e| SystemError::database(e.to_string()))?;
+    .map_err(map_handler_insert_error)?;
 
     Ok(id)
 }
@@ -72,14 +87,24 @@ pub struct SessionBundle {
     pub user: User,
 }
 
+/// Where a new session is being opened from.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Default)]
+pub struct SessionDevice {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// Opens a session for `data`. Only the digest of `token` is stored.
 #[cfg(feature = "ssr")]
 pub async fn create_session(
     tx: &mut PgConnection,
     data: &crate::db_ops::handler::HandlerAuthData,
     token: &str,
+    device: &SessionDevice,
+    ttl: std::time::Duration,
 ) -> Result<SessionBundle, SystemError> {
     struct SessionRecord {
-        token: String,
         handler_id: Uuid,
         system_id: Uuid,
         email: String,
@@ -95,12 +120,12 @@ pub async fn create_session(
     let record = sqlx::query_as!(
         SessionRecord,
         r#"INSERT INTO sessions 
-        (handler_id, system_id, token, handler_role, user_name, email, avatar_url, bio, preferred_theme, system_handle, system_name) 
-        VALUES ($1, $2, $3, $4::public.handler_role, $5, $6, $7, $8, $9, $10, $11) 
-        RETURNING token as "token!", handler_id as "handler_id!", system_id as "system_id!", email as "email!", user_name as "user_name!", handler_role as "handler_role!: HandlerRole", avatar_url, bio, preferred_theme, system_handle as "system_handle!", system_name as "system_name!""#,
+        (handler_id, system_id, token_hash, handler_role, user_name, email, avatar_url, bio, preferred_theme, system_handle, system_name, user_agent, ip_address, expires_at) 
+        VALUES ($1, $2, $3, $4::public.handler_role, $5, $6, $7, $8, $9, $10, $11, $12, $13, NOW() + make_interval(secs => $14)) 
+        RETURNING handler_id as "handler_id!", system_id as "system_id!", email as "email!", user_name as "user_name!", handler_role as "handler_role!: HandlerRole", avatar_url, bio, preferred_theme, system_handle as "system_handle!", system_name as "system_name!""#,
         data.handler_id,
         data.system_id,
-        token,
+        crate::helper::digest_token(token),
         data.handler_role as _,
         data.user_name,
         data.email,
@@ -108,14 +133,17 @@ pub async fn create_session(
         data.bio,
         data.preferred_theme,
         data.workspace_handle,
-        data.system_name
+        data.system_name,
+        device.user_agent,
+        device.ip_address,
+        ttl.as_secs_f64()
     )
     .fetch_one(&mut *tx)
     .await
     .map_err(|e| SystemError::database(e.to_string()))?;
 
     Ok(SessionBundle {
-        token: record.token,
+        token: token.to_string(),
         user: User {
             id: record.handler_id,
             system_id: record.system_id,
@@ -135,7 +163,7 @@ pub async fn create_session(
 #[instrument(skip_all)]
 pub async fn get_session_user(
     tx: &mut PgConnection,
-    token: &str,
+    token_hash: &str,
 ) -> Result<Option<User>, SystemError> {
     struct UserRecord {
         handler_id: Uuid,
@@ -154,8 +182,8 @@ pub async fn get_session_user(
         UserRecord,
         "SELECT handler_id as \"handler_id!\", system_id as \"system_id!\", email as \"email!\", user_name as \"user_name!\", handler_role as \"handler_role!: HandlerRole\", avatar_url, bio, preferred_theme, system_handle as \"system_handle!\", system_name as \"system_name!\" 
          FROM sessions 
-         WHERE token = $1 AND expires_at > NOW()",
-        token
+         WHERE token_hash = $1 AND expires_at > NOW()",
+        token_hash
     )
     .fetch_optional(&mut *tx)
     .await
@@ -179,8 +207,8 @@ pub async fn get_session_user(
 }
 
 #[cfg(feature = "ssr")]
-pub async fn delete_session(tx: &mut PgConnection, token: &str) -> Result<(), SystemError> {
-    sqlx::query!("DELETE FROM sessions WHERE token = $1", token)
+pub async fn delete_session(tx: &mut PgConnection, token_hash: &str) -> Result<(), SystemError> {
+    sqlx::query!("DELETE FROM sessions WHERE token_hash = $1", token_hash)
         .execute(&mut *tx)
         .await
         .map_err(|e| SystemError::database(e.to_string()))?;
@@ -189,24 +217,312 @@ pub async fn delete_session(tx: &mut PgConnection, token: &str) -> Result<(), Sy
 }
 
 #[cfg(feature = "ssr")]
-pub async fn refresh_session_expiry(tx: &mut PgConnection, token: &str) -> Result<(), SystemError> {
+pub async fn refresh_session_expiry(
+    tx: &mut PgConnection,
+    token_hash: &str,
+    session: &crate::config::SessionConfig,
+) -> Result<(), SystemError> {
     // ◈ Optimization: Conditional Refresh (The "Lazier Refresh" pattern)
-    // We only update the expires_at timestamp if it has been at least 24 hours since the last refresh.
+    // We only extend expires_at once `refresh_after` has passed since the last extension,
+    // and only touch last_seen_at once a minute.
     // This prevents row-locking contention during rapid-fire requests while keeping sessions alive.
     sqlx::query!(
         "UPDATE sessions 
-         SET expires_at = NOW() + INTERVAL '7 days' 
-         WHERE token = $1 
-         AND expires_at < NOW() + INTERVAL '6 days'",
-        token
+         SET expires_at = CASE
+                 WHEN expires_at < NOW() + make_interval(secs => $2) THEN NOW() + make_interval(secs => $3)
+                 ELSE expires_at
+             END,
+             last_seen_at = NOW()
+         WHERE token_hash = $1 
+         AND (expires_at < NOW() + make_interval(secs => $2) OR last_seen_at < NOW() - INTERVAL '1 minute')",
+        token_hash,
+        session.refresh_below().as_secs_f64(),
+        session.ttl.as_secs_f64()
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn find_active_handler_id(
+    tx: &mut PgConnection,
+    email: &str,
+) -> Result<Option<Uuid>, SystemError> {
+    sqlx::query_scalar!(
+        "SELECT id FROM handlers WHERE lower(email) = lower($1) AND is_active",
+        email
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Stores a new reset token digest, discarding any earlier unused ones for the handler.
+#[cfg(feature = "ssr")]
+pub async fn insert_password_reset_token(
+    tx: &mut PgConnection,
+    handler_id: &Uuid,
+    token_hash: &str,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "DELETE FROM password_reset_tokens WHERE handler_id = $1 AND used_at IS NULL",
+        handler_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    sqlx::query!(
+        "INSERT INTO password_reset_tokens (handler_id, token_hash) VALUES ($1, $2)",
+        handler_id,
+        token_hash
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Consumes a reset token, returning the handler it belongs to if it was still valid.
+#[cfg(feature = "ssr")]
+pub async fn consume_password_reset_token(
+    tx: &mut PgConnection,
+    token_hash: &str,
+) -> Result<Option<Uuid>, SystemError> {
+    sqlx::query_scalar!(
+        "UPDATE password_reset_tokens
+         SET used_at = NOW()
+         WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+         RETURNING handler_id",
+        token_hash
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn update_password_hash(
+    tx: &mut PgConnection,
+    handler_id: &Uuid,
+    password_hash: &str,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE handlers SET password_hash = $2, updated_at = NOW() WHERE id = $1",
+        handler_id,
+        password_hash
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Consecutive failed sign-ins and how long ago the latest one happened.
+#[cfg(feature = "ssr")]
+pub struct FailureStreak {
+    pub failures: i64,
+    pub secs_since_last: i64,
+}
+
+/// Serializes sign-in attempts for an email and a client IP until the transaction ends, so
+/// concurrent requests cannot all read the same failure streak and each get a guess.
+/// The email is locked first, always in that order, so two requests never wait on each other.
+#[cfg(feature = "ssr")]
+pub async fn lock_login_attempts(
+    tx: &mut PgConnection,
+    email: &str,
+    ip_address: Option<&str>,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext('login_attempts:email:' || lower($1)))",
+        email
     )
     .execute(&mut *tx)
     .await
     .map_err(|e| SystemError::database(e.to_string()))?;
 
+    if let Some(ip_address) = ip_address {
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock(hashtext('login_attempts:ip:' || $1))",
+            ip_address
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+    }
+
     Ok(())
 }
 
+#[cfg(feature = "ssr")]
+pub async fn record_login_attempt(
+    tx: &mut PgConnection,
+    email: &str,
+    ip_address: Option<&str>,
+    succeeded: bool,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "INSERT INTO login_attempts (email, ip_address, succeeded) VALUES (lower($1), $2, $3)",
+        email,
+        ip_address,
+        succeeded
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Failures against an email since its last successful sign-in (looking back one day).
+#[cfg(feature = "ssr")]
+pub async fn email_failure_streak(
+    tx: &mut PgConnection,
+    email: &str,
+) -> Result<FailureStreak, SystemError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) as "failures!",
+            COALESCE(EXTRACT(EPOCH FROM NOW() - MAX(attempted_at))::BIGINT, 0) as "secs_since_last!"
+        FROM login_attempts
+        WHERE email = lower($1)
+          AND NOT succeeded
+          AND attempted_at > NOW() - INTERVAL '1 day'
+          AND attempted_at > COALESCE(
+              (SELECT MAX(attempted_at) FROM login_attempts WHERE email = lower($1) AND succeeded),
+              '-infinity'
+          )
+        "#,
+        email
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(FailureStreak {
+        failures: record.failures,
+        secs_since_last: record.secs_since_last,
+    })
+}
+
+/// Failures from a client IP in the last hour. Successes do not reset this, so an attacker
+/// cannot clear it by signing in to an account of their own.
+#[cfg(feature = "ssr")]
+pub async fn ip_failure_streak(
+    tx: &mut PgConnection,
+    ip_address: &str,
+) -> Result<FailureStreak, SystemError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) as "failures!",
+            COALESCE(EXTRACT(EPOCH FROM NOW() - MAX(attempted_at))::BIGINT, 0) as "secs_since_last!"
+        FROM login_attempts
+        WHERE ip_address = $1
+          AND NOT succeeded
+          AND attempted_at > NOW() - INTERVAL '1 hour'
+        "#,
+        ip_address
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(FailureStreak {
+        failures: record.failures,
+        secs_since_last: record.secs_since_last,
+    })
+}
+
+/// Serializes reset requests for an email and a client IP, in the same order as sign-ins,
+/// so concurrent requests cannot all slip under the same count.
+#[cfg(feature = "ssr")]
+pub async fn lock_password_reset_requests(
+    tx: &mut PgConnection,
+    email: &str,
+    ip_address: Option<&str>,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext('password_reset_requests:email:' || lower($1)))",
+        email
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    if let Some(ip_address) = ip_address {
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock(hashtext('password_reset_requests:ip:' || $1))",
+            ip_address
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn record_password_reset_request(
+    tx: &mut PgConnection,
+    email: &str,
+    ip_address: Option<&str>,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "INSERT INTO password_reset_requests (email, ip_address) VALUES (lower($1), $2)",
+        email,
+        ip_address
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Reset requests in the last hour for an email and for a client IP.
+#[cfg(feature = "ssr")]
+pub struct ResetRequestCounts {
+    pub email: i64,
+    pub ip: i64,
+}
+
+#[cfg(feature = "ssr")]
+pub async fn recent_password_reset_requests(
+    tx: &mut PgConnection,
+    email: &str,
+    ip_address: Option<&str>,
+) -> Result<ResetRequestCounts, SystemError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE email = lower($1)) as "email!",
+            COUNT(*) FILTER (WHERE ip_address = $2) as "ip!"
+        FROM password_reset_requests
+        WHERE (email = lower($1) OR ip_address = $2)
+          AND requested_at > NOW() - INTERVAL '1 hour'
+        "#,
+        email,
+        ip_address
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(ResetRequestCounts {
+        email: record.email,
+        ip: record.ip,
+    })
+}
+
 // #[cfg(feature = "ssr")]
 // pub async fn verify_password(
 //     tx: &mut Transaction<'_, Postgres>,
@@ -242,6 +558,29 @@ pub async fn verify_password(
     .map_err(|e| SystemError::general(format!("Task join error: {}", e)))?
 }
 
+/// Runs a full Argon2 verification against a throwaway hash so that unknown emails take
+/// as long to reject as wrong passwords.
+#[cfg(feature = "ssr")]
+pub async fn verify_dummy_password(password_candidate: String) -> Result<(), SystemError> {
+    use argon2::{PasswordHash, PasswordVerifier};
+    use std::sync::LazyLock;
+
+    static DUMMY_HASH: LazyLock<String> = LazyLock::new(|| {
+        Argon2::default()
+            .hash_password(b"sharp-system-dummy", &SaltString::generate(&mut OsRng))
+            .map(|h| h.to_string())
+            .unwrap_or_default()
+    });
+
+    tokio::task::spawn_blocking(move || {
+        if let Ok(parsed_hash) = PasswordHash::new(&DUMMY_HASH) {
+            let _ = Argon2::default().verify_password(password_candidate.as_bytes(), &parsed_hash);
+        }
+    })
+    .await
+    .map_err(|e| SystemError::general(format!("Task join error: {}", e)))
+}
+
 #[cfg(feature = "ssr")]
 pub async fn hash_password(raw_pass: String) -> Result<String, SystemError> {
     tokio::task::spawn_blocking(move || {
@@ -0,0 +1,254 @@
+#[cfg(feature = "ssr")]
+use models::entities::{Customer, CustomerReturn, RefundMethod, Sale, WarrantyUnit};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use models::payloads::CustomerPayload;
+#[cfg(feature = "ssr")]
+use sqlx::{Postgres, Transaction};
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+fn map_customer_write_error(e: sqlx::Error) -> SystemError {
+    if let Some(db_err) = e.as_database_error()
+        && db_err.constraint() == Some("customers_system_phone_key")
+    {
+        return SystemError::validation("Another customer already has this phone number.");
+    }
+    SystemError::database(e.to_string())
+}
+
+/// Expects a payload that has been through `CustomerPayload::normalized`.
+#[cfg(feature = "ssr")]
+pub async fn insert_customer(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+    payload: &CustomerPayload,
+    created_by: &Uuid,
+) -> Result<Customer, SystemError> {
+    sqlx::query_as!(
+        Customer,
+        r#"
+        INSERT INTO customers (system_id, name, phone, email, notes, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, system_id, name, phone, email, notes, created_at, updated_at
+        "#,
+        system_id,
+        payload.name,
+        payload.phone,
+        payload.email,
+        payload.notes,
+        created_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert customer: {}", e);
+        map_customer_write_error(e)
+    })
+}
+
+/// Expects a payload that has been through `CustomerPayload::normalized`.
+#[cfg(feature = "ssr")]
+pub async fn update_customer(
+    conn: &mut Transaction<'_, Postgres>,
+    customer_id: &Uuid,
+    system_id: &Uuid,
+    payload: &CustomerPayload,
+) -> Result<Option<Customer>, SystemError> {
+    sqlx::query_as!(
+        Customer,
+        r#"
+        UPDATE customers
+        SET name = $3, phone = $4, email = $5, notes = $6
+        WHERE id = $1 AND system_id = $2
+        RETURNING id, system_id, name, phone, email, notes, created_at, updated_at
+        "#,
+        customer_id,
+        system_id,
+        payload.name,
+        payload.phone,
+        payload.email,
+        payload.notes
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update customer: {}", e);
+        map_customer_write_error(e)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_customer(
+    conn: &mut sqlx::PgConnection,
+    customer_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Customer>, SystemError> {
+    sqlx::query_as!(
+        Customer,
+        r#"
+        SELECT id, system_id, name, phone, email, notes, created_at, updated_at
+        FROM customers
+        WHERE id = $1 AND system_id = $2
+        "#,
+        customer_id,
+        system_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Deletes the record; past sales keep their totals and become anonymous.
+#[cfg(feature = "ssr")]
+pub async fn delete_customer(
+    conn: &mut Transaction<'_, Postgres>,
+    customer_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Customer>, SystemError> {
+    sqlx::query_as!(
+        Customer,
+        r#"
+        DELETE FROM customers
+        WHERE id = $1 AND system_id = $2
+        RETURNING id, system_id, name, phone, email, notes, created_at, updated_at
+        "#,
+        customer_id,
+        system_id
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to delete customer: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+/// Case-insensitive substring search over name, phone and email, alphabetical.
+#[cfg(feature = "ssr")]
+pub async fn search_customers(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    search: Option<&str>,
+    limit: i64,
+) -> Result<Vec<Customer>, SystemError> {
+    let pattern = search.map(super::inventory::escape_like);
+
+    sqlx::query_as!(
+        Customer,
+        r#"
+        SELECT id, system_id, name, phone, email, notes, created_at, updated_at
+        FROM customers
+        WHERE system_id = $1
+          AND ($2::text IS NULL
+               OR name ILIKE '%' || $2 || '%'
+               OR phone ILIKE '%' || $2 || '%'
+               OR email ILIKE '%' || $2 || '%')
+        ORDER BY name ASC
+        LIMIT $3
+        "#,
+        system_id,
+        pattern,
+        limit
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Exact match on a phone number already run through `normalize_phone`.
+#[cfg(feature = "ssr")]
+pub async fn find_customer_by_phone(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    phone: &str,
+) -> Result<Option<Customer>, SystemError> {
+    sqlx::query_as!(
+        Customer,
+        r#"
+        SELECT id, system_id, name, phone, email, notes, created_at, updated_at
+        FROM customers
+        WHERE system_id = $1 AND phone = $2
+        "#,
+        system_id,
+        phone
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn list_customer_sales(
+    conn: &mut sqlx::PgConnection,
+    customer_id: &Uuid,
+) -> Result<Vec<Sale>, SystemError> {
+    sqlx::query_as!(
+        Sale,
+        r#"
+        SELECT id, system_id, invoice_number, total_amount, deposit_applied, sold_by, customer_id,
+               created_at
+        FROM sales
+        WHERE customer_id = $1
+        ORDER BY created_at DESC
+        "#,
+        customer_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn list_customer_returns(
+    conn: &mut sqlx::PgConnection,
+    customer_id: &Uuid,
+) -> Result<Vec<CustomerReturn>, SystemError> {
+    sqlx::query_as!(
+        CustomerReturn,
+        r#"
+        SELECT r.id, r.sale_id, s.invoice_number, r.refund_amount,
+               r.refund_method as "refund_method: RefundMethod", r.reason, r.created_at
+        FROM sale_returns r
+        JOIN sales s ON s.id = r.sale_id
+        WHERE s.customer_id = $1
+        ORDER BY r.created_at DESC
+        "#,
+        customer_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Serialized units sold to the customer that have not come back, with the end of their
+/// warranty counted from the day of sale.
+#[cfg(feature = "ssr")]
+pub async fn list_customer_units(
+    conn: &mut sqlx::PgConnection,
+    customer_id: &Uuid,
+) -> Result<Vec<WarrantyUnit>, SystemError> {
+    sqlx::query_as!(
+        WarrantyUnit,
+        r#"
+        SELECT tu.id as tracked_unit_id, p.name as product_name, tu.serial_numbers,
+               s.invoice_number, s.created_at as sold_at,
+               (CASE WHEN p.warranty_months > 0
+                     THEN s.created_at + make_interval(months => p.warranty_months)
+                END) as "warranty_until?"
+        FROM sale_lines sl
+        JOIN sales s ON s.id = sl.sale_id
+        JOIN tracked_units tu ON tu.id = sl.tracked_unit_id
+        JOIN products p ON p.id = sl.product_id
+        WHERE s.customer_id = $1
+          AND NOT EXISTS (SELECT 1 FROM sale_return_lines rl WHERE rl.sale_line_id = sl.id)
+        ORDER BY s.created_at DESC
+        "#,
+        customer_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
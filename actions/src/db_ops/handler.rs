@@ -27,21 +27,7 @@ pub struct HandlerAuthData {
 pub async fn get_handler_auth_data(
     tx: &mut PgConnection,
     email: &str,
-) -> Result<HandlerAuthData, SystemError> {
-    struct HandlerRecord {
-        handler_id: Uuid,
-        password_hash: String,
-        user_name: String,
-        email: String,
-        handler_role: HandlerRole,
-        avatar_url: Option<String>,
-        bio: Option<String>,
-        preferred_theme: String,
-        system_id: Uuid,
-        workspace_handle: String,
-        system_name: String,
-    }
-
+) -> Result<Option<HandlerAuthData>, SystemError> {
     let record = sqlx::query_as!(
         HandlerRecord,
         r#"
@@ -59,26 +45,80 @@ pub async fn get_handler_auth_data(
             s.system_name as "system_name!"
         FROM handlers h
         JOIN systems s ON h.system_id = s.id
-        WHERE h.email = $1
+        WHERE lower(h.email) = lower($1) AND h.is_active
         "#,
         email
     )
     .fetch_optional(&mut *tx)
     .await
-    .map_err(|e| SystemError::database(e.to_string()))?
-    .ok_or_else(|| SystemError::not_found("User not found"))?;
+    .map_err(|e| SystemError::database(e.to_string()))?;
 
-    Ok(HandlerAuthData {
-        handler_id: record.handler_id,
-        password_hash: record.password_hash,
-        user_name: record.user_name,
-        email: record.email,
-        handler_role: record.handler_role,
-        avatar_url: record.avatar_url,
-        bio: record.bio,
-        preferred_theme: Some(record.preferred_theme),
-        system_id: record.system_id,
-        workspace_handle: record.workspace_handle,
-        system_name: record.system_name,
-    })
+    Ok(record.map(HandlerAuthData::from))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_handler_auth_data_by_id(
+    tx: &mut PgConnection,
+    handler_id: &Uuid,
+) -> Result<Option<HandlerAuthData>, SystemError> {
+    let record = sqlx::query_as!(
+        HandlerRecord,
+        r#"
+        SELECT 
+            h.id as handler_id,
+            h.password_hash,
+            h.user_name as "user_name!",
+            h.email,
+            h.handler_role as "handler_role!: HandlerRole",
+            h.avatar_url,
+            h.bio,
+            h.preferred_theme as "preferred_theme!",
+            s.id as system_id,
+            s.system_handle as "workspace_handle!",
+            s.system_name as "system_name!"
+        FROM handlers h
+        JOIN systems s ON h.system_id = s.id
+        WHERE h.id = $1 AND h.is_active
+        "#,
+        handler_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(record.map(HandlerAuthData::from))
+}
+
+#[cfg(feature = "ssr")]
+struct HandlerRecord {
+    handler_id: Uuid,
+    password_hash: String,
+    user_name: String,
+    email: String,
+    handler_role: HandlerRole,
+    avatar_url: Option<String>,
+    bio: Option<String>,
+    preferred_theme: String,
+    system_id: Uuid,
+    workspace_handle: String,
+    system_name: String,
+}
+
+#[cfg(feature = "ssr")]
+impl From<HandlerRecord> for HandlerAuthData {
+    fn from(record: HandlerRecord) -> Self {
+        Self {
+            handler_id: record.handler_id,
+            password_hash: record.password_hash,
+            user_name: record.user_name,
+            email: record.email,
+            handler_role: record.handler_role,
+            avatar_url: record.avatar_url,
+            bio: record.bio,
+            preferred_theme: Some(record.preferred_theme),
+            system_id: record.system_id,
+            workspace_handle: record.workspace_handle,
+            system_name: record.system_name,
+        }
+    }
 }
@@ -1,15 +1,32 @@
 #[cfg(feature = "ssr")]
-use models::entities::Product;
+use models::entities::{
+    Product, StockAdjustmentReason, StockedProduct, TrackedUnit, TrackedUnitStatus,
+    UntrackedInventory,
+};
 #[cfg(feature = "ssr")]
 use models::errors::SystemError;
 #[cfg(feature = "ssr")]
-use models::payloads::AddProductPayload;
+use models::payloads::{AddProductPayload, ProductListQuery, UpdateProductPayload};
+#[cfg(feature = "ssr")]
+use rust_decimal::Decimal;
 #[cfg(feature = "ssr")]
 use uuid::Uuid;
 
 #[cfg(feature = "ssr")]
 use sqlx::{Postgres, Transaction};
 
+#[cfg(feature = "ssr")]
+fn map_product_write_error(e: sqlx::Error) -> SystemError {
+    if let Some(db_err) = e.as_database_error()
+        && db_err.constraint() == Some("products_system_sku_key")
+    {
+        return SystemError::validation(
+            "A product with this SKU already exists in this workspace.".to_string(),
+        );
+    }
+    SystemError::database(e.to_string())
+}
+
 #[cfg(feature = "ssr")]
 pub async fn insert_product(
     conn: &mut Transaction<'_, Postgres>,
@@ -26,10 +43,10 @@ pub async fn insert_product(
         Product,
         r#"
         INSERT INTO products (
-            id, system_id, name, sku, category, is_tracked, added_by
+            id, system_id, name, sku, category, is_tracked, added_by, warranty_months
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
-        RETURNING id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?"
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?", is_archived, warranty_months
         "#
         ,
         product_id,
@@ -38,18 +55,14 @@ pub async fn insert_product(
         sku,
         category,
         payload.is_tracked,
-        added_by
+        added_by,
+        payload.warranty_months
     )
     .fetch_one(&mut **conn)
     .await
     .map_err(|e| {
         tracing::error!("Failed to insert product: {}", e);
-        if let Some(db_err) = e.as_database_error() {
-            if db_err.constraint() == Some("products_sku_key") {
-                return SystemError::validation("A product with this SKU already exists.".to_string());
-            }
-        }
-        SystemError::database(e.to_string())
+        map_product_write_error(e)
     })?;
 
     Ok(product)
@@ -77,27 +90,669 @@ pub async fn initialize_untracked_inventory(
 
     Ok(())
 }
+/// Returns one page of the workspace catalog and the total number of matches.
 #[cfg(feature = "ssr")]
-pub async fn get_products_for_system(
+pub async fn list_products(
     conn: &mut sqlx::PgConnection,
     system_id: &Uuid,
-) -> Result<Vec<Product>, SystemError> {
+    query: &ProductListQuery,
+) -> Result<(Vec<Product>, i64), SystemError> {
+    let search = query.search.as_deref().map(escape_like);
+
+    let total = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM products
+        WHERE system_id = $1
+          AND is_archived = $2
+          AND ($3::text IS NULL OR name ILIKE '%' || $3 || '%' OR sku ILIKE '%' || $3 || '%')
+          AND ($4::text IS NULL OR category = $4)
+          AND ($5::bool IS NULL OR is_tracked = $5)
+        "#,
+        system_id,
+        query.archived,
+        search,
+        query.category,
+        query.is_tracked
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count products: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
     let products = sqlx::query_as!(
         Product,
         r#"
-        SELECT id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?"
+        SELECT id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?", is_archived, warranty_months
         FROM products
         WHERE system_id = $1
-        ORDER BY name ASC
+          AND is_archived = $2
+          AND ($3::text IS NULL OR name ILIKE '%' || $3 || '%' OR sku ILIKE '%' || $3 || '%')
+          AND ($4::text IS NULL OR category = $4)
+          AND ($5::bool IS NULL OR is_tracked = $5)
+        ORDER BY
+            CASE WHEN $6 = 'name_desc' THEN name END DESC,
+            CASE WHEN $6 = 'newest' THEN created_at END DESC,
+            CASE WHEN $6 = 'oldest' THEN created_at END ASC,
+            CASE WHEN $6 = 'category' THEN category END ASC NULLS LAST,
+            name ASC,
+            id ASC
+        LIMIT $7 OFFSET $8
+        "#,
+        system_id,
+        query.archived,
+        search,
+        query.category,
+        query.is_tracked,
+        query.sort.key(),
+        i64::from(query.page_size),
+        query.offset()
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch products: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok((products, total))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_product_for_system(
+    conn: &mut sqlx::PgConnection,
+    product_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Product>, SystemError> {
+    let product = sqlx::query_as!(
+        Product,
+        r#"
+        SELECT id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?", is_archived, warranty_months
+        FROM products
+        WHERE id = $1 AND system_id = $2
         "#,
+        product_id,
+        system_id
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch product: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(product)
+}
+
+/// Like [`get_product_for_system`], but holds the row lock until the transaction ends so a
+/// change of tracking mode and a delivery of stock cannot interleave.
+#[cfg(feature = "ssr")]
+pub async fn lock_product_for_system(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Product>, SystemError> {
+    let product = sqlx::query_as!(
+        Product,
+        r#"
+        SELECT id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?", is_archived, warranty_months
+        FROM products
+        WHERE id = $1 AND system_id = $2
+        FOR UPDATE
+        "#,
+        product_id,
         system_id
     )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to lock product: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(product)
+}
+
+/// Serializes serial intake per workspace so two concurrent batches cannot both
+/// claim the same IMEI between the duplicate check and the insert.
+#[cfg(feature = "ssr")]
+pub async fn lock_serial_registry(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext('tracked_units:' || $1::uuid::text))",
+        system_id
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Returns the subset of `serials` already registered on any tracked unit in the workspace.
+/// The `&&` overlap test is served by the GIN index on `serial_numbers`.
+#[cfg(feature = "ssr")]
+pub async fn find_existing_serials(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    serials: &[String],
+) -> Result<Vec<String>, SystemError> {
+    let existing = sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT serial as "serial!"
+        FROM tracked_units tu
+        JOIN products p ON p.id = tu.product_id
+        CROSS JOIN LATERAL unnest(tu.serial_numbers) AS serial
+        WHERE p.system_id = $1
+          AND tu.serial_numbers && $2::text[]
+          AND serial = ANY($2::text[])
+        "#,
+        system_id,
+        serials
+    )
     .fetch_all(conn)
     .await
     .map_err(|e| {
-        tracing::error!("Failed to fetch products: {}", e);
+        tracing::error!("Failed to check existing serials: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(existing)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_tracked_unit(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    serial_numbers: &[String],
+    acquisition_cost: Decimal,
+    target_msrp: Decimal,
+    supplier_id: Option<&Uuid>,
+    added_by: &Uuid,
+) -> Result<TrackedUnit, SystemError> {
+    let unit = sqlx::query_as!(
+        TrackedUnit,
+        r#"
+        INSERT INTO tracked_units (
+            product_id, serial_numbers, acquisition_cost, target_msrp, supplier_id, added_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, product_id, serial_numbers, supplier_id, status as "status: TrackedUnitStatus",
+                  acquisition_cost, target_msrp, added_by, last_updated_by
+        "#,
+        product_id,
+        serial_numbers,
+        acquisition_cost,
+        target_msrp,
+        supplier_id,
+        added_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert tracked unit: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(unit)
+}
+
+/// Adds received units to the bulk bucket and folds their cost into the weighted average:
+/// `new_avg = (qty * avg + received * unit_cost) / (qty + received)`.
+#[cfg(feature = "ssr")]
+pub async fn receive_bulk_stock(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    quantity: i32,
+    unit_cost: Decimal,
+    updated_by: &Uuid,
+) -> Result<UntrackedInventory, SystemError> {
+    let level = sqlx::query_as!(
+        UntrackedInventory,
+        r#"
+        INSERT INTO untracked_inventory (product_id, quantity, average_acquisition_cost, last_updated_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (product_id) DO UPDATE SET
+            average_acquisition_cost = ROUND(
+                (untracked_inventory.quantity * untracked_inventory.average_acquisition_cost
+                    + EXCLUDED.quantity * EXCLUDED.average_acquisition_cost)
+                / (untracked_inventory.quantity + EXCLUDED.quantity),
+                2
+            ),
+            quantity = untracked_inventory.quantity + EXCLUDED.quantity,
+            last_updated_by = EXCLUDED.last_updated_by
+        RETURNING product_id, quantity, average_acquisition_cost, base_retail_price, last_updated_by
+        "#,
+        product_id,
+        quantity,
+        unit_cost,
+        updated_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to receive bulk stock: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(level)
+}
+
+/// Applies a signed quantity change without touching the average cost.
+/// Returns `None` when the product has no bulk bucket.
+#[cfg(feature = "ssr")]
+pub async fn adjust_bulk_stock(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    quantity_delta: i32,
+    updated_by: &Uuid,
+) -> Result<Option<UntrackedInventory>, SystemError> {
+    let level = sqlx::query_as!(
+        UntrackedInventory,
+        r#"
+        UPDATE untracked_inventory
+        SET quantity = quantity + $2, last_updated_by = $3
+        WHERE product_id = $1
+        RETURNING product_id, quantity, average_acquisition_cost, base_retail_price, last_updated_by
+        "#,
+        product_id,
+        quantity_delta,
+        updated_by
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        if let Some(db_err) = e.as_database_error()
+            && db_err.constraint() == Some("untracked_inventory_quantity_check")
+        {
+            return SystemError::inventory(
+                "Not enough stock on hand: this adjustment would make the quantity negative.",
+            );
+        }
+        if let Some(db_err) = e.as_database_error()
+            && db_err.constraint() == Some("untracked_inventory_reserved_quantity_check")
+        {
+            return SystemError::inventory(
+                "Part of this stock is on hold for reservations: release them before removing it.",
+            );
+        }
+        tracing::error!("Failed to adjust bulk stock: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(level)
+}
+
+#[cfg(feature = "ssr")]
+#[allow(clippy::too_many_arguments)]
+pub async fn record_stock_adjustment(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+    product_id: &Uuid,
+    quantity_delta: i32,
+    reason: StockAdjustmentReason,
+    unit_cost: Option<Decimal>,
+    note: Option<&str>,
+    supplier_id: Option<&Uuid>,
+    adjusted_by: &Uuid,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO stock_adjustments (
+            system_id, product_id, quantity_delta, reason, unit_cost, note, supplier_id,
+            adjusted_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        system_id,
+        product_id,
+        quantity_delta,
+        reason as _,
+        unit_cost,
+        note,
+        supplier_id,
+        adjusted_by
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to record stock adjustment: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(())
+}
+
+/// Flips an in-stock unit to `Sold` and returns its acquisition cost.
+/// Returns `None` if the unit is not in stock for this product and workspace.
+#[cfg(feature = "ssr")]
+pub async fn mark_tracked_unit_sold(
+    conn: &mut Transaction<'_, Postgres>,
+    unit_id: &Uuid,
+    product_id: &Uuid,
+    system_id: &Uuid,
+    updated_by: &Uuid,
+) -> Result<Option<Decimal>, SystemError> {
+    let cost = sqlx::query_scalar!(
+        r#"
+        UPDATE tracked_units tu
+        SET status = 'Sold', last_updated_by = $4
+        FROM products p
+        WHERE tu.id = $1
+          AND tu.product_id = $2
+          AND p.id = tu.product_id
+          AND p.system_id = $3
+          AND tu.status = 'InStock'
+        RETURNING tu.acquisition_cost
+        "#,
+        unit_id,
+        product_id,
+        system_id,
+        updated_by
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to mark tracked unit sold: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(cost)
+}
+
+/// Removes sold units from the bulk bucket and returns the average cost they left at.
+/// Returns `None` when fewer than `quantity` units are on hand and not reserved.
+#[cfg(feature = "ssr")]
+pub async fn sell_bulk_stock(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    quantity: i32,
+    updated_by: &Uuid,
+) -> Result<Option<Decimal>, SystemError> {
+    let cost = sqlx::query_scalar!(
+        r#"
+        UPDATE untracked_inventory
+        SET quantity = quantity - $2, last_updated_by = $3
+        WHERE product_id = $1 AND quantity - reserved_quantity >= $2
+        RETURNING average_acquisition_cost
+        "#,
+        product_id,
+        quantity,
+        updated_by
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to decrement bulk stock: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(cost)
+}
+
+/// Exact serial/IMEI lookup, served by the GIN index on `serial_numbers`.
+#[cfg(feature = "ssr")]
+pub async fn find_tracked_unit_by_serial(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    serial: &str,
+) -> Result<Option<TrackedUnit>, SystemError> {
+    let unit = sqlx::query_as!(
+        TrackedUnit,
+        r#"
+        SELECT tu.id, tu.product_id, tu.serial_numbers, tu.supplier_id,
+               tu.status as "status: TrackedUnitStatus", tu.acquisition_cost, tu.target_msrp,
+               tu.added_by, tu.last_updated_by
+        FROM tracked_units tu
+        JOIN products p ON p.id = tu.product_id
+        WHERE p.system_id = $1 AND tu.serial_numbers @> ARRAY[$2::text]
+        LIMIT 1
+        "#,
+        system_id,
+        serial
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to look up serial: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(unit)
+}
+
+#[cfg(feature = "ssr")]
+struct StockedProductRecord {
+    id: Uuid,
+    system_id: Uuid,
+    name: String,
+    sku: Option<String>,
+    category: Option<String>,
+    is_tracked: bool,
+    added_by: Uuid,
+    last_edited_by: Option<Uuid>,
+    is_archived: bool,
+    warranty_months: i32,
+    on_hand: i32,
+    retail_price: Decimal,
+}
+
+#[cfg(feature = "ssr")]
+impl From<StockedProductRecord> for StockedProduct {
+    fn from(r: StockedProductRecord) -> Self {
+        Self {
+            product: Product {
+                id: r.id,
+                system_id: r.system_id,
+                name: r.name,
+                sku: r.sku,
+                category: r.category,
+                is_tracked: r.is_tracked,
+                added_by: r.added_by,
+                last_edited_by: r.last_edited_by,
+                is_archived: r.is_archived,
+                warranty_months: r.warranty_months,
+            },
+            on_hand: r.on_hand,
+            retail_price: r.retail_price,
+        }
+    }
+}
+
+/// Escapes `LIKE` wildcards so user input is matched literally.
+#[cfg(feature = "ssr")]
+pub(crate) fn escape_like(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Searches the catalog with availability attached. `exact` matches the SKU only;
+/// otherwise a case-insensitive substring search runs over name and category.
+#[cfg(feature = "ssr")]
+pub async fn search_stocked_products(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    query: &str,
+    exact: bool,
+    limit: i64,
+) -> Result<Vec<StockedProduct>, SystemError> {
+    let records = sqlx::query_as!(
+        StockedProductRecord,
+        r#"
+        SELECT p.id, p.system_id, p.name, p.sku, p.category, p.is_tracked, p.added_by,
+               p.last_edited_by as "last_edited_by?", p.is_archived, p.warranty_months,
+               (CASE WHEN p.is_tracked THEN (
+                    SELECT COUNT(*) FROM tracked_units tu
+                    WHERE tu.product_id = p.id AND tu.status = 'InStock'
+                )::int
+                ELSE COALESCE(ui.quantity - ui.reserved_quantity, 0) END) as "on_hand!",
+               COALESCE(ui.base_retail_price, 0) as "retail_price!"
+        FROM products p
+        LEFT JOIN untracked_inventory ui ON ui.product_id = p.id
+        WHERE p.system_id = $1
+          AND NOT p.is_archived
+          AND (
+            ($3 AND p.sku = $2)
+            OR (NOT $3 AND (p.name ILIKE '%' || $5 || '%' OR p.category ILIKE '%' || $5 || '%'))
+          )
+        ORDER BY (p.name ILIKE $5 || '%') DESC, p.name ASC
+        LIMIT $4
+        "#,
+        system_id,
+        query,
+        exact,
+        limit,
+        escape_like(query)
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to search products: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(records.into_iter().map(StockedProduct::from).collect())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn update_product(
+    conn: &mut Transaction<'_, Postgres>,
+    payload: &UpdateProductPayload,
+    system_id: &Uuid,
+    edited_by: &Uuid,
+) -> Result<Option<Product>, SystemError> {
+    let sku = payload.sku.as_ref().filter(|s| !s.trim().is_empty());
+    let category = payload.category.as_ref().filter(|s| !s.trim().is_empty());
+
+    let product = sqlx::query_as!(
+        Product,
+        r#"
+        UPDATE products
+        SET name = $3, sku = $4, category = $5, is_tracked = $6, last_edited_by = $7,
+            warranty_months = $8
+        WHERE id = $1 AND system_id = $2
+        RETURNING id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?", is_archived, warranty_months
+        "#,
+        payload.product_id,
+        system_id,
+        payload.name,
+        sku,
+        category,
+        payload.is_tracked,
+        edited_by,
+        payload.warranty_months
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update product: {}", e);
+        map_product_write_error(e)
+    })?;
+
+    Ok(product)
+}
+
+/// What physical and historical records hang off a product.
+#[cfg(feature = "ssr")]
+pub struct ProductFootprint {
+    /// Units or bulk quantity currently held.
+    pub has_stock: bool,
+    /// Sales, tracked units, reservations, purchase orders or ledger rows that must survive
+    /// the product.
+    pub has_history: bool,
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_product_footprint(
+    conn: &mut sqlx::PgConnection,
+    product_id: &Uuid,
+) -> Result<ProductFootprint, SystemError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            (EXISTS(SELECT 1 FROM tracked_units WHERE product_id = $1)
+                OR EXISTS(SELECT 1 FROM untracked_inventory WHERE product_id = $1 AND quantity > 0)
+            ) as "has_stock!",
+            (EXISTS(SELECT 1 FROM sale_lines WHERE product_id = $1)
+                OR EXISTS(SELECT 1 FROM tracked_units WHERE product_id = $1)
+                OR EXISTS(SELECT 1 FROM stock_adjustments WHERE product_id = $1)
+                OR EXISTS(SELECT 1 FROM reservation_lines WHERE product_id = $1)
+                OR EXISTS(SELECT 1 FROM purchase_order_lines WHERE product_id = $1)
+            ) as "has_history!"
+        "#,
+        product_id
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(ProductFootprint {
+        has_stock: record.has_stock,
+        has_history: record.has_history,
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn remove_untracked_inventory(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "DELETE FROM untracked_inventory WHERE product_id = $1",
+        product_id
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn archive_product(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    edited_by: &Uuid,
+) -> Result<Product, SystemError> {
+    let product = sqlx::query_as!(
+        Product,
+        r#"
+        UPDATE products
+        SET is_archived = TRUE, last_edited_by = $2
+        WHERE id = $1
+        RETURNING id, system_id, name, sku, category, is_tracked, added_by, last_edited_by as "last_edited_by?", is_archived, warranty_months
+        "#,
+        product_id,
+        edited_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to archive product: {}", e);
         SystemError::database(e.to_string())
     })?;
 
-    Ok(products)
+    Ok(product)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn delete_product(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+) -> Result<(), SystemError> {
+    sqlx::query!("DELETE FROM products WHERE id = $1", product_id)
+        .execute(&mut **conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete product: {}", e);
+            SystemError::database(e.to_string())
+        })?;
+
+    Ok(())
 }
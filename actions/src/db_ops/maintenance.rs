@@ -0,0 +1,81 @@
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use sqlx::PgConnection;
+
+// Each purge returns how many rows it deleted.
+
+#[cfg(feature = "ssr")]
+pub async fn purge_expired_sessions(conn: &mut PgConnection) -> Result<u64, SystemError> {
+    let result = sqlx::query!("DELETE FROM sessions WHERE expires_at <= NOW()")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+/// Used reset tokens are dead too; keeping them only lengthens the unique index.
+#[cfg(feature = "ssr")]
+pub async fn purge_spent_password_reset_tokens(
+    conn: &mut PgConnection,
+) -> Result<u64, SystemError> {
+    let result = sqlx::query!(
+        "DELETE FROM password_reset_tokens WHERE expires_at <= NOW() OR used_at IS NOT NULL"
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+/// Accepted invites are kept as a record of who invited whom.
+#[cfg(feature = "ssr")]
+pub async fn purge_expired_invites(conn: &mut PgConnection) -> Result<u64, SystemError> {
+    let result = sqlx::query!(
+        "DELETE FROM handler_invites WHERE accepted_at IS NULL AND expires_at <= NOW()"
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn purge_expired_login_challenges(conn: &mut PgConnection) -> Result<u64, SystemError> {
+    let result = sqlx::query!("DELETE FROM login_challenges WHERE expires_at <= NOW()")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+/// Sign-in throttling only looks back a day; a month is kept for investigating abuse.
+#[cfg(feature = "ssr")]
+pub async fn purge_old_login_attempts(conn: &mut PgConnection) -> Result<u64, SystemError> {
+    let result =
+        sqlx::query!("DELETE FROM login_attempts WHERE attempted_at < NOW() - INTERVAL '30 days'")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+/// Reset throttling only looks back an hour; a day is kept for investigating abuse.
+#[cfg(feature = "ssr")]
+pub async fn purge_old_password_reset_requests(
+    conn: &mut PgConnection,
+) -> Result<u64, SystemError> {
+    let result = sqlx::query!(
+        "DELETE FROM password_reset_requests WHERE requested_at < NOW() - INTERVAL '1 day'"
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
@@ -1,13 +1,57 @@
 #[cfg(feature = "ssr")]
+pub mod audit;
+#[cfg(feature = "ssr")]
 pub mod auth;
 #[cfg(feature = "ssr")]
+pub mod customers;
+#[cfg(feature = "ssr")]
 pub mod handler;
 #[cfg(feature = "ssr")]
 pub mod inventory;
+#[cfg(feature = "ssr")]
+pub mod maintenance;
+#[cfg(feature = "ssr")]
+pub mod purchase_orders;
+#[cfg(feature = "ssr")]
+pub mod reservations;
+#[cfg(feature = "ssr")]
+pub mod sales;
+#[cfg(feature = "ssr")]
+pub mod sessions;
+#[cfg(feature = "ssr")]
+pub mod staff;
+#[cfg(feature = "ssr")]
+pub mod suppliers;
+#[cfg(feature = "ssr")]
+pub mod two_factor;
+#[cfg(feature = "ssr")]
+pub mod workspace;
 
+#[cfg(feature = "ssr")]
+pub use audit::*;
 #[cfg(feature = "ssr")]
 pub use auth::*;
 #[cfg(feature = "ssr")]
+pub use customers::*;
+#[cfg(feature = "ssr")]
 pub use handler::*;
 #[cfg(feature = "ssr")]
 pub use inventory::*;
+#[cfg(feature = "ssr")]
+pub use maintenance::*;
+#[cfg(feature = "ssr")]
+pub use purchase_orders::*;
+#[cfg(feature = "ssr")]
+pub use reservations::*;
+#[cfg(feature = "ssr")]
+pub use sales::*;
+#[cfg(feature = "ssr")]
+pub use sessions::*;
+#[cfg(feature = "ssr")]
+pub use staff::*;
+#[cfg(feature = "ssr")]
+pub use suppliers::*;
+#[cfg(feature = "ssr")]
+pub use two_factor::*;
+#[cfg(feature = "ssr")]
+pub use workspace::*;
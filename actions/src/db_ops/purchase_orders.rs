@@ -0,0 +1,288 @@
+#[cfg(feature = "ssr")]
+use models::entities::{PurchaseOrder, PurchaseOrderLine, PurchaseOrderStatus};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use models::payloads::PurchaseOrderLineInput;
+#[cfg(feature = "ssr")]
+use sqlx::{Postgres, Transaction};
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+/// Bumps the workspace's purchase order counter under the same row lock as invoice numbers.
+#[cfg(feature = "ssr")]
+pub async fn next_purchase_order_number(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+) -> Result<i32, SystemError> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE systems
+        SET last_purchase_order_number = last_purchase_order_number + 1
+        WHERE id = $1
+        RETURNING last_purchase_order_number
+        "#,
+        system_id
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to allocate purchase order number: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_purchase_order(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+    po_number: i32,
+    supplier_id: &Uuid,
+    notes: Option<&str>,
+    created_by: &Uuid,
+) -> Result<PurchaseOrder, SystemError> {
+    sqlx::query_as!(
+        PurchaseOrder,
+        r#"
+        INSERT INTO purchase_orders (system_id, po_number, supplier_id, notes, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, system_id, po_number, supplier_id,
+                  status as "status: PurchaseOrderStatus", notes, created_by, created_at,
+                  ordered_at, closed_at
+        "#,
+        system_id,
+        po_number,
+        supplier_id,
+        notes,
+        created_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert purchase order: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_purchase_order_line(
+    conn: &mut Transaction<'_, Postgres>,
+    purchase_order_id: &Uuid,
+    input: &PurchaseOrderLineInput,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO purchase_order_lines (purchase_order_id, product_id, expected_quantity, unit_cost)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        purchase_order_id,
+        input.product_id,
+        input.expected_quantity,
+        input.unit_cost
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert purchase order line: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(())
+}
+
+/// Newest first. `open_only` keeps drafts and orders still awaiting stock.
+#[cfg(feature = "ssr")]
+pub async fn list_purchase_orders(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    open_only: bool,
+) -> Result<Vec<PurchaseOrder>, SystemError> {
+    sqlx::query_as!(
+        PurchaseOrder,
+        r#"
+        SELECT id, system_id, po_number, supplier_id,
+               status as "status: PurchaseOrderStatus", notes, created_by, created_at,
+               ordered_at, closed_at
+        FROM purchase_orders
+        WHERE system_id = $1
+          AND (NOT $2 OR status IN ('draft', 'ordered', 'partially_received'))
+        ORDER BY po_number DESC
+        "#,
+        system_id,
+        open_only
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Lines of every given order, in the order they were added.
+#[cfg(feature = "ssr")]
+pub async fn list_purchase_order_lines(
+    conn: &mut sqlx::PgConnection,
+    purchase_order_ids: &[Uuid],
+) -> Result<Vec<PurchaseOrderLine>, SystemError> {
+    sqlx::query_as!(
+        PurchaseOrderLine,
+        r#"
+        SELECT l.id, l.purchase_order_id, l.product_id, p.name as product_name, p.is_tracked,
+               l.expected_quantity, l.received_quantity, l.unit_cost
+        FROM purchase_order_lines l
+        JOIN products p ON p.id = l.product_id
+        WHERE l.purchase_order_id = ANY($1)
+        ORDER BY l.purchase_order_id, l.id
+        "#,
+        purchase_order_ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn lock_purchase_order(
+    conn: &mut Transaction<'_, Postgres>,
+    purchase_order_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<PurchaseOrder>, SystemError> {
+    sqlx::query_as!(
+        PurchaseOrder,
+        r#"
+        SELECT id, system_id, po_number, supplier_id,
+               status as "status: PurchaseOrderStatus", notes, created_by, created_at,
+               ordered_at, closed_at
+        FROM purchase_orders
+        WHERE id = $1 AND system_id = $2
+        FOR UPDATE
+        "#,
+        purchase_order_id,
+        system_id
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn lock_purchase_order_line(
+    conn: &mut Transaction<'_, Postgres>,
+    line_id: &Uuid,
+) -> Result<Option<PurchaseOrderLine>, SystemError> {
+    sqlx::query_as!(
+        PurchaseOrderLine,
+        r#"
+        SELECT l.id, l.purchase_order_id, l.product_id, p.name as product_name, p.is_tracked,
+               l.expected_quantity, l.received_quantity, l.unit_cost
+        FROM purchase_order_lines l
+        JOIN products p ON p.id = l.product_id
+        WHERE l.id = $1
+        FOR UPDATE OF l
+        "#,
+        line_id
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Moves a draft to `Ordered` or closes an order as `Cancelled`, stamping the matching time.
+#[cfg(feature = "ssr")]
+pub async fn set_purchase_order_status(
+    conn: &mut Transaction<'_, Postgres>,
+    purchase_order_id: &Uuid,
+    status: PurchaseOrderStatus,
+) -> Result<PurchaseOrder, SystemError> {
+    sqlx::query_as!(
+        PurchaseOrder,
+        r#"
+        UPDATE purchase_orders
+        SET status = $2::purchase_order_status,
+            ordered_at = CASE WHEN $2::purchase_order_status = 'ordered'
+                              THEN NOW() ELSE ordered_at END,
+            closed_at = CASE WHEN $2::purchase_order_status IN ('received', 'cancelled')
+                             THEN NOW() ELSE closed_at END
+        WHERE id = $1
+        RETURNING id, system_id, po_number, supplier_id,
+                  status as "status: PurchaseOrderStatus", notes, created_by, created_at,
+                  ordered_at, closed_at
+        "#,
+        purchase_order_id,
+        status as _
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update purchase order status: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn add_received_quantity(
+    conn: &mut Transaction<'_, Postgres>,
+    line_id: &Uuid,
+    quantity: i32,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        r#"
+        UPDATE purchase_order_lines
+        SET received_quantity = received_quantity + $2
+        WHERE id = $1
+        "#,
+        line_id,
+        quantity
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| {
+        if let Some(db_err) = e.as_database_error()
+            && db_err.constraint() == Some("purchase_order_lines_received_quantity_check")
+        {
+            return SystemError::inventory(
+                "This delivery is more than the order line has outstanding.",
+            );
+        }
+        tracing::error!("Failed to record purchase order receipt: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(())
+}
+
+/// Marks the order `Received` and closes it once no line is outstanding, otherwise
+/// `PartiallyReceived`.
+#[cfg(feature = "ssr")]
+pub async fn refresh_purchase_order_progress(
+    conn: &mut Transaction<'_, Postgres>,
+    purchase_order_id: &Uuid,
+) -> Result<PurchaseOrder, SystemError> {
+    sqlx::query_as!(
+        PurchaseOrder,
+        r#"
+        WITH progress AS (
+            SELECT NOT EXISTS (
+                SELECT 1 FROM purchase_order_lines
+                WHERE purchase_order_id = $1 AND received_quantity < expected_quantity
+            ) as complete
+        )
+        UPDATE purchase_orders
+        SET status = CASE WHEN progress.complete
+                          THEN 'received'::purchase_order_status
+                          ELSE 'partially_received'::purchase_order_status
+                     END,
+            closed_at = CASE WHEN progress.complete THEN NOW() ELSE NULL END
+        FROM progress
+        WHERE id = $1
+        RETURNING id, system_id, po_number, supplier_id,
+                  status as "status: PurchaseOrderStatus", notes, created_by, created_at,
+                  ordered_at, closed_at
+        "#,
+        purchase_order_id
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update purchase order progress: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
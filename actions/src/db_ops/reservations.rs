@@ -0,0 +1,398 @@
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "ssr")]
+use models::entities::{Reservation, ReservationLine, ReservationStatus, ReservedItem};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use models::payloads::SaleLineInput;
+#[cfg(feature = "ssr")]
+use rust_decimal::Decimal;
+#[cfg(feature = "ssr")]
+use sqlx::{Postgres, Transaction};
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+/// Puts an in-stock unit on hold. Returns `false` if the unit is not in stock for this
+/// product and workspace.
+#[cfg(feature = "ssr")]
+pub async fn reserve_tracked_unit(
+    conn: &mut Transaction<'_, Postgres>,
+    unit_id: &Uuid,
+    product_id: &Uuid,
+    system_id: &Uuid,
+    updated_by: &Uuid,
+) -> Result<bool, SystemError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE tracked_units tu
+        SET status = 'Reserved', last_updated_by = $4
+        FROM products p
+        WHERE tu.id = $1
+          AND tu.product_id = $2
+          AND p.id = tu.product_id
+          AND p.system_id = $3
+          AND tu.status = 'InStock'
+        "#,
+        unit_id,
+        product_id,
+        system_id,
+        updated_by
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to reserve tracked unit: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Holds part of a bulk bucket. Returns `false` when fewer than `quantity` units are
+/// on hand and not already held.
+#[cfg(feature = "ssr")]
+pub async fn reserve_bulk_stock(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    quantity: i32,
+    updated_by: &Uuid,
+) -> Result<bool, SystemError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE untracked_inventory
+        SET reserved_quantity = reserved_quantity + $2, last_updated_by = $3
+        WHERE product_id = $1 AND quantity - reserved_quantity >= $2
+        "#,
+        product_id,
+        quantity,
+        updated_by
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to reserve bulk stock: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Puts a held unit back on sale. Returns `false` if the unit is no longer reserved.
+#[cfg(feature = "ssr")]
+pub async fn release_tracked_unit(
+    conn: &mut sqlx::PgConnection,
+    unit_id: &Uuid,
+    updated_by: Option<&Uuid>,
+) -> Result<bool, SystemError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE tracked_units
+        SET status = 'InStock', last_updated_by = COALESCE($2, last_updated_by)
+        WHERE id = $1 AND status = 'Reserved'
+        "#,
+        unit_id,
+        updated_by
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to release tracked unit: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn release_bulk_stock(
+    conn: &mut sqlx::PgConnection,
+    product_id: &Uuid,
+    quantity: i32,
+    updated_by: Option<&Uuid>,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        r#"
+        UPDATE untracked_inventory
+        SET reserved_quantity = reserved_quantity - $2,
+            last_updated_by = COALESCE($3, last_updated_by)
+        WHERE product_id = $1
+        "#,
+        product_id,
+        quantity,
+        updated_by
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to release bulk stock: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(())
+}
+
+/// Flips a held unit to `Sold` and returns its acquisition cost, or `None` if it is no
+/// longer reserved.
+#[cfg(feature = "ssr")]
+pub async fn sell_reserved_unit(
+    conn: &mut Transaction<'_, Postgres>,
+    unit_id: &Uuid,
+    updated_by: &Uuid,
+) -> Result<Option<Decimal>, SystemError> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE tracked_units
+        SET status = 'Sold', last_updated_by = $2
+        WHERE id = $1 AND status = 'Reserved'
+        RETURNING acquisition_cost
+        "#,
+        unit_id,
+        updated_by
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to sell reserved unit: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+/// Takes held bulk stock out of the bucket along with its hold, returning the average
+/// cost it left at.
+#[cfg(feature = "ssr")]
+pub async fn sell_reserved_bulk_stock(
+    conn: &mut Transaction<'_, Postgres>,
+    product_id: &Uuid,
+    quantity: i32,
+    updated_by: &Uuid,
+) -> Result<Option<Decimal>, SystemError> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE untracked_inventory
+        SET quantity = quantity - $2,
+            reserved_quantity = reserved_quantity - $2,
+            last_updated_by = $3
+        WHERE product_id = $1 AND reserved_quantity >= $2
+        RETURNING average_acquisition_cost
+        "#,
+        product_id,
+        quantity,
+        updated_by
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to sell reserved bulk stock: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_reservation(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+    customer_name: &str,
+    customer_phone: Option<&str>,
+    deposit: Decimal,
+    expires_at: DateTime<Utc>,
+    created_by: &Uuid,
+) -> Result<Reservation, SystemError> {
+    sqlx::query_as!(
+        Reservation,
+        r#"
+        INSERT INTO reservations (system_id, customer_name, customer_phone, deposit, expires_at, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, system_id, customer_name, customer_phone, deposit, expires_at,
+                  status as "status: ReservationStatus", sale_id, created_by, created_at, closed_at
+        "#,
+        system_id,
+        customer_name,
+        customer_phone,
+        deposit,
+        expires_at,
+        created_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert reservation: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_reservation_line(
+    conn: &mut Transaction<'_, Postgres>,
+    reservation_id: &Uuid,
+    line: &SaleLineInput,
+) -> Result<ReservationLine, SystemError> {
+    sqlx::query_as!(
+        ReservationLine,
+        r#"
+        INSERT INTO reservation_lines (reservation_id, product_id, tracked_unit_id, quantity, unit_price)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, reservation_id, product_id, tracked_unit_id, quantity, unit_price
+        "#,
+        reservation_id,
+        line.product_id,
+        line.tracked_unit_id,
+        line.quantity,
+        line.unit_price
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert reservation line: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+/// Reservations in the workspace, soonest to expire first. `None` lists every status.
+#[cfg(feature = "ssr")]
+pub async fn list_reservations(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    status: Option<ReservationStatus>,
+) -> Result<Vec<Reservation>, SystemError> {
+    sqlx::query_as!(
+        Reservation,
+        r#"
+        SELECT id, system_id, customer_name, customer_phone, deposit, expires_at,
+               status as "status: ReservationStatus", sale_id, created_by, created_at, closed_at
+        FROM reservations
+        WHERE system_id = $1 AND ($2::reservation_status IS NULL OR status = $2)
+        ORDER BY (status = 'active') DESC, expires_at ASC
+        LIMIT 200
+        "#,
+        system_id,
+        status as Option<ReservationStatus>
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Every line of the given reservations, with product names and serials.
+#[cfg(feature = "ssr")]
+pub async fn list_reserved_items(
+    conn: &mut sqlx::PgConnection,
+    reservation_ids: &[Uuid],
+) -> Result<Vec<ReservedItem>, SystemError> {
+    sqlx::query_as!(
+        ReservedItem,
+        r#"
+        SELECT rl.id, rl.reservation_id, rl.product_id, p.name as product_name,
+               rl.tracked_unit_id, tu.serial_numbers as "serial_numbers?",
+               rl.quantity, rl.unit_price
+        FROM reservation_lines rl
+        JOIN products p ON p.id = rl.product_id
+        LEFT JOIN tracked_units tu ON tu.id = rl.tracked_unit_id
+        WHERE rl.reservation_id = ANY($1)
+        ORDER BY p.name, rl.id
+        "#,
+        reservation_ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Loads a reservation and locks it until the transaction ends, so it cannot be converted
+/// and cancelled (or expired) at the same time.
+#[cfg(feature = "ssr")]
+pub async fn lock_reservation(
+    conn: &mut Transaction<'_, Postgres>,
+    reservation_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Reservation>, SystemError> {
+    sqlx::query_as!(
+        Reservation,
+        r#"
+        SELECT id, system_id, customer_name, customer_phone, deposit, expires_at,
+               status as "status: ReservationStatus", sale_id, created_by, created_at, closed_at
+        FROM reservations
+        WHERE id = $1 AND system_id = $2
+        FOR UPDATE
+        "#,
+        reservation_id,
+        system_id
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn list_reservation_lines(
+    conn: &mut sqlx::PgConnection,
+    reservation_id: &Uuid,
+) -> Result<Vec<ReservationLine>, SystemError> {
+    sqlx::query_as!(
+        ReservationLine,
+        r#"
+        SELECT id, reservation_id, product_id, tracked_unit_id, quantity, unit_price
+        FROM reservation_lines
+        WHERE reservation_id = $1
+        ORDER BY id
+        "#,
+        reservation_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Ends an active reservation, recording the sale it became if it was converted.
+#[cfg(feature = "ssr")]
+pub async fn close_reservation(
+    conn: &mut sqlx::PgConnection,
+    reservation_id: &Uuid,
+    status: ReservationStatus,
+    sale_id: Option<&Uuid>,
+) -> Result<Reservation, SystemError> {
+    sqlx::query_as!(
+        Reservation,
+        r#"
+        UPDATE reservations
+        SET status = $2, sale_id = $3, closed_at = NOW()
+        WHERE id = $1 AND status = 'active'
+        RETURNING id, system_id, customer_name, customer_phone, deposit, expires_at,
+                  status as "status: ReservationStatus", sale_id, created_by, created_at, closed_at
+        "#,
+        reservation_id,
+        status as ReservationStatus,
+        sale_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to close reservation: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+/// Active reservations whose hold has run out, across every workspace, oldest first.
+/// Locked and skipped by concurrent callers so two job runs never release the same hold.
+#[cfg(feature = "ssr")]
+pub async fn lock_expired_reservations(
+    conn: &mut sqlx::PgConnection,
+    limit: i64,
+) -> Result<Vec<Reservation>, SystemError> {
+    sqlx::query_as!(
+        Reservation,
+        r#"
+        SELECT id, system_id, customer_name, customer_phone, deposit, expires_at,
+               status as "status: ReservationStatus", sale_id, created_by, created_at, closed_at
+        FROM reservations
+        WHERE status = 'active' AND expires_at <= NOW()
+        ORDER BY expires_at
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+        "#,
+        limit
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
@@ -0,0 +1,289 @@
+#[cfg(feature = "ssr")]
+use models::entities::{
+    RefundMethod, ReturnCondition, ReturnableLine, Sale, SaleLine, SaleReturn, SaleReturnLine,
+    TrackedUnitStatus,
+};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use models::payloads::SaleLineInput;
+#[cfg(feature = "ssr")]
+use rust_decimal::Decimal;
+#[cfg(feature = "ssr")]
+use sqlx::{Postgres, Transaction};
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+/// Bumps the workspace's invoice counter. The row lock on `systems` is held until the
+/// surrounding transaction ends, so concurrent checkouts receive consecutive numbers.
+#[cfg(feature = "ssr")]
+pub async fn next_invoice_number(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+) -> Result<i32, SystemError> {
+    let number = sqlx::query_scalar!(
+        r#"
+        UPDATE systems
+        SET last_invoice_number = last_invoice_number + 1
+        WHERE id = $1
+        RETURNING last_invoice_number
+        "#,
+        system_id
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to allocate invoice number: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(number)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_sale(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+    invoice_number: i32,
+    total_amount: Decimal,
+    deposit_applied: Decimal,
+    sold_by: &Uuid,
+    customer_id: Option<&Uuid>,
+) -> Result<Sale, SystemError> {
+    let sale = sqlx::query_as!(
+        Sale,
+        r#"
+        INSERT INTO sales (
+            system_id, invoice_number, total_amount, deposit_applied, sold_by, customer_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, system_id, invoice_number, total_amount, deposit_applied, sold_by,
+                  customer_id, created_at
+        "#,
+        system_id,
+        invoice_number,
+        total_amount,
+        deposit_applied,
+        sold_by,
+        customer_id
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert sale: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(sale)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_sale_line(
+    conn: &mut Transaction<'_, Postgres>,
+    sale_id: &Uuid,
+    line: &SaleLineInput,
+    unit_cost: Decimal,
+) -> Result<SaleLine, SystemError> {
+    let sale_line = sqlx::query_as!(
+        SaleLine,
+        r#"
+        INSERT INTO sale_lines (sale_id, product_id, tracked_unit_id, quantity, unit_price, unit_cost)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, sale_id, product_id, tracked_unit_id, quantity, unit_price, unit_cost
+        "#,
+        sale_id,
+        line.product_id,
+        line.tracked_unit_id,
+        line.quantity,
+        line.unit_price,
+        unit_cost
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert sale line: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(sale_line)
+}
+
+/// Finds a sale by its invoice number within the workspace.
+#[cfg(feature = "ssr")]
+pub async fn find_sale_by_invoice(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+    invoice_number: i32,
+) -> Result<Option<Sale>, SystemError> {
+    sqlx::query_as!(
+        Sale,
+        r#"
+        SELECT id, system_id, invoice_number, total_amount, deposit_applied, sold_by, customer_id,
+               created_at
+        FROM sales
+        WHERE system_id = $1 AND invoice_number = $2
+        "#,
+        system_id,
+        invoice_number
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Loads a sale and locks it until the transaction ends, so two returns against the same
+/// sale cannot both count the same returnable quantity.
+#[cfg(feature = "ssr")]
+pub async fn lock_sale_for_return(
+    conn: &mut Transaction<'_, Postgres>,
+    sale_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Sale>, SystemError> {
+    sqlx::query_as!(
+        Sale,
+        r#"
+        SELECT id, system_id, invoice_number, total_amount, deposit_applied, sold_by, customer_id,
+               created_at
+        FROM sales
+        WHERE id = $1 AND system_id = $2
+        FOR UPDATE
+        "#,
+        sale_id,
+        system_id
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// The sale's lines with the quantity already returned from each.
+#[cfg(feature = "ssr")]
+pub async fn list_returnable_lines(
+    conn: &mut sqlx::PgConnection,
+    sale_id: &Uuid,
+) -> Result<Vec<ReturnableLine>, SystemError> {
+    sqlx::query_as!(
+        ReturnableLine,
+        r#"
+        SELECT sl.id, sl.product_id, p.name as product_name, sl.tracked_unit_id,
+               sl.quantity, sl.unit_price,
+               COALESCE((SELECT SUM(rl.quantity) FROM sale_return_lines rl WHERE rl.sale_line_id = sl.id), 0)::int as "returned_quantity!"
+        FROM sale_lines sl
+        JOIN products p ON p.id = sl.product_id
+        WHERE sl.sale_id = $1
+        ORDER BY p.name, sl.id
+        "#,
+        sale_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Total refunded against a sale so far.
+#[cfg(feature = "ssr")]
+pub async fn refunded_amount(
+    conn: &mut sqlx::PgConnection,
+    sale_id: &Uuid,
+) -> Result<Decimal, SystemError> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT COALESCE(SUM(refund_amount), 0) as "total!"
+        FROM sale_returns
+        WHERE sale_id = $1
+        "#,
+        sale_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_sale_return(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+    sale_id: &Uuid,
+    refund_amount: Decimal,
+    refund_method: RefundMethod,
+    reason: Option<&str>,
+    processed_by: &Uuid,
+) -> Result<SaleReturn, SystemError> {
+    sqlx::query_as!(
+        SaleReturn,
+        r#"
+        INSERT INTO sale_returns (system_id, sale_id, refund_amount, refund_method, reason, processed_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, sale_id, refund_amount, refund_method as "refund_method: RefundMethod",
+                  reason, processed_by, created_at
+        "#,
+        system_id,
+        sale_id,
+        refund_amount,
+        refund_method as RefundMethod,
+        reason,
+        processed_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert sale return: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_sale_return_line(
+    conn: &mut Transaction<'_, Postgres>,
+    return_id: &Uuid,
+    sale_line_id: &Uuid,
+    quantity: i32,
+    condition: ReturnCondition,
+) -> Result<SaleReturnLine, SystemError> {
+    sqlx::query_as!(
+        SaleReturnLine,
+        r#"
+        INSERT INTO sale_return_lines (return_id, sale_line_id, quantity, condition)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, return_id, sale_line_id, quantity, condition as "condition: ReturnCondition"
+        "#,
+        return_id,
+        sale_line_id,
+        quantity,
+        condition as ReturnCondition
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert sale return line: {}", e);
+        SystemError::database(e.to_string())
+    })
+}
+
+/// Moves a sold unit back to `status`. Returns `false` when the unit is no longer `Sold`.
+#[cfg(feature = "ssr")]
+pub async fn return_tracked_unit(
+    conn: &mut Transaction<'_, Postgres>,
+    unit_id: &Uuid,
+    status: TrackedUnitStatus,
+    updated_by: &Uuid,
+) -> Result<bool, SystemError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE tracked_units
+        SET status = $2, last_updated_by = $3
+        WHERE id = $1 AND status = 'Sold'
+        "#,
+        unit_id,
+        status as TrackedUnitStatus,
+        updated_by
+    )
+    .execute(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to return tracked unit: {}", e);
+        SystemError::database(e.to_string())
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
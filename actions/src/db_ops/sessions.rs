@@ -0,0 +1,94 @@
+#[cfg(feature = "ssr")]
+use models::entities::{ActiveSession, SessionStats};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use sqlx::PgConnection;
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+/// The handler's unexpired sessions, most recently used first.
+#[cfg(feature = "ssr")]
+pub async fn list_handler_sessions(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    current_token_hash: &str,
+) -> Result<Vec<ActiveSession>, SystemError> {
+    sqlx::query_as!(
+        ActiveSession,
+        r#"
+        SELECT id, user_agent, ip_address, created_at, last_seen_at, expires_at,
+               token_hash = $2 as "is_current!"
+        FROM sessions
+        WHERE handler_id = $1 AND expires_at > NOW()
+        ORDER BY last_seen_at DESC
+        "#,
+        handler_id,
+        current_token_hash
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Deletes one of the handler's sessions. Returns `false` if it was not theirs.
+#[cfg(feature = "ssr")]
+pub async fn delete_handler_session(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    session_id: &Uuid,
+) -> Result<bool, SystemError> {
+    let result = sqlx::query!(
+        "DELETE FROM sessions WHERE id = $1 AND handler_id = $2",
+        session_id,
+        handler_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Signs the handler out everywhere except the session with `keep_token_hash`. Returns how many sessions ended.
+#[cfg(feature = "ssr")]
+pub async fn delete_other_sessions(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    keep_token_hash: &str,
+) -> Result<u64, SystemError> {
+    let result = sqlx::query!(
+        "DELETE FROM sessions WHERE handler_id = $1 AND token_hash <> $2",
+        handler_id,
+        keep_token_hash
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn session_stats(
+    conn: &mut PgConnection,
+    system_id: &Uuid,
+) -> Result<SessionStats, SystemError> {
+    sqlx::query_as!(
+        SessionStats,
+        r#"
+        SELECT
+            COUNT(*) as "active_sessions!",
+            COUNT(DISTINCT handler_id) FILTER (
+                WHERE last_seen_at > NOW() - make_interval(mins => $2)
+            ) as "online_handlers!"
+        FROM sessions
+        WHERE system_id = $1 AND expires_at > NOW()
+        "#,
+        system_id,
+        SessionStats::ONLINE_WINDOW_MINUTES
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
@@ -0,0 +1,235 @@
+#[cfg(feature = "ssr")]
+use models::HandlerRole;
+#[cfg(feature = "ssr")]
+use models::entities::{StaffInvite, StaffMember};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use models::payloads::InviteHandlerPayload;
+#[cfg(feature = "ssr")]
+use sqlx::PgConnection;
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+pub async fn email_is_registered(
+    conn: &mut PgConnection,
+    email: &str,
+) -> Result<bool, SystemError> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM handlers WHERE lower(email) = lower($1)) as "exists!""#,
+        email
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Issues an invite, replacing any still-open invite for the same email in this workspace.
+#[cfg(feature = "ssr")]
+pub async fn insert_invite(
+    conn: &mut PgConnection,
+    system_id: &Uuid,
+    payload: &InviteHandlerPayload,
+    token_hash: &str,
+    invited_by: &Uuid,
+) -> Result<StaffInvite, SystemError> {
+    sqlx::query!(
+        "DELETE FROM handler_invites
+         WHERE system_id = $1 AND lower(email) = lower($2) AND accepted_at IS NULL",
+        system_id,
+        payload.email.trim()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    sqlx::query_as!(
+        StaffInvite,
+        r#"
+        INSERT INTO handler_invites (system_id, email, user_name, handler_role, token_hash, invited_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, email, user_name, handler_role as "handler_role: HandlerRole", invited_by, expires_at
+        "#,
+        system_id,
+        payload.email.trim(),
+        payload.user_name.trim(),
+        payload.handler_role as _,
+        token_hash,
+        invited_by
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// An unexpired, unaccepted invite, locked until the surrounding transaction ends.
+#[cfg(feature = "ssr")]
+pub async fn find_open_invite_for_update(
+    conn: &mut PgConnection,
+    token_hash: &str,
+) -> Result<Option<(Uuid, StaffInvite)>, SystemError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT id, system_id, email, user_name, handler_role as "handler_role: HandlerRole", invited_by, expires_at
+        FROM handler_invites
+        WHERE token_hash = $1 AND accepted_at IS NULL AND expires_at > NOW()
+        FOR UPDATE
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(record.map(|r| {
+        (
+            r.system_id,
+            StaffInvite {
+                id: r.id,
+                email: r.email,
+                user_name: r.user_name,
+                handler_role: r.handler_role,
+                invited_by: r.invited_by,
+                expires_at: r.expires_at,
+            },
+        )
+    }))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn mark_invite_accepted(
+    conn: &mut PgConnection,
+    invite_id: &Uuid,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE handler_invites SET accepted_at = NOW() WHERE id = $1",
+        invite_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_invited_handler(
+    conn: &mut PgConnection,
+    system_id: &Uuid,
+    invite: &StaffInvite,
+    password_hash: &str,
+) -> Result<Uuid, SystemError> {
+    sqlx::query_scalar!(
+        "INSERT INTO handlers (system_id, email, password_hash, user_name, handler_role)
+         VALUES ($1, $2, $3, $4, $5::public.handler_role)
+         RETURNING id",
+        system_id,
+        invite.email.trim().to_lowercase(),
+        password_hash,
+        invite.user_name,
+        invite.handler_role as _
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(super::auth::map_handler_insert_error)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn list_handlers(
+    conn: &mut PgConnection,
+    system_id: &Uuid,
+) -> Result<Vec<StaffMember>, SystemError> {
+    sqlx::query_as!(
+        StaffMember,
+        r#"
+        SELECT id, email, user_name, handler_role as "handler_role!: HandlerRole", is_active, created_at
+        FROM handlers
+        WHERE system_id = $1
+        ORDER BY is_active DESC, user_name
+        "#,
+        system_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_staff_member(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<StaffMember>, SystemError> {
+    sqlx::query_as!(
+        StaffMember,
+        r#"
+        SELECT id, email, user_name, handler_role as "handler_role!: HandlerRole", is_active, created_at
+        FROM handlers
+        WHERE id = $1 AND system_id = $2
+        "#,
+        handler_id,
+        system_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Changes a handler's role, including the copy cached on their live sessions.
+#[cfg(feature = "ssr")]
+pub async fn update_handler_role(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    role: HandlerRole,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE handlers SET handler_role = $2::public.handler_role, updated_at = NOW() WHERE id = $1",
+        handler_id,
+        role as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    sqlx::query!(
+        "UPDATE sessions SET handler_role = $2::public.handler_role WHERE handler_id = $1",
+        handler_id,
+        role as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn deactivate_handler(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE handlers SET is_active = FALSE, updated_at = NOW() WHERE id = $1",
+        handler_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Signs a handler out everywhere. Returns how many sessions were revoked.
+#[cfg(feature = "ssr")]
+pub async fn revoke_handler_sessions(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+) -> Result<u64, SystemError> {
+    let result = sqlx::query!("DELETE FROM sessions WHERE handler_id = $1", handler_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,210 @@
+#[cfg(feature = "ssr")]
+use models::entities::{Supplier, SupplierReport};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use models::payloads::SupplierPayload;
+#[cfg(feature = "ssr")]
+use sqlx::{Postgres, Transaction};
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+fn map_supplier_write_error(e: sqlx::Error) -> SystemError {
+    if let Some(db_err) = e.as_database_error() {
+        match db_err.constraint() {
+            Some("suppliers_system_name_key") => {
+                return SystemError::validation("A supplier with this name already exists.");
+            }
+            Some(
+                "tracked_units_supplier_id_fkey"
+                | "stock_adjustments_supplier_id_fkey"
+                | "purchase_orders_supplier_id_fkey",
+            ) => {
+                return SystemError::validation(
+                    "This supplier has stock or purchase orders on record and cannot be deleted.",
+                );
+            }
+            _ => {}
+        }
+    }
+    SystemError::database(e.to_string())
+}
+
+/// Expects a payload that has been through `SupplierPayload::normalized`.
+#[cfg(feature = "ssr")]
+pub async fn insert_supplier(
+    conn: &mut Transaction<'_, Postgres>,
+    system_id: &Uuid,
+    payload: &SupplierPayload,
+    created_by: &Uuid,
+) -> Result<Supplier, SystemError> {
+    sqlx::query_as!(
+        Supplier,
+        r#"
+        INSERT INTO suppliers (system_id, name, contact_name, phone, email, notes, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, system_id, name, contact_name, phone, email, notes, created_at, updated_at
+        "#,
+        system_id,
+        payload.name,
+        payload.contact_name,
+        payload.phone,
+        payload.email,
+        payload.notes,
+        created_by
+    )
+    .fetch_one(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to insert supplier: {}", e);
+        map_supplier_write_error(e)
+    })
+}
+
+/// Expects a payload that has been through `SupplierPayload::normalized`.
+#[cfg(feature = "ssr")]
+pub async fn update_supplier(
+    conn: &mut Transaction<'_, Postgres>,
+    supplier_id: &Uuid,
+    system_id: &Uuid,
+    payload: &SupplierPayload,
+) -> Result<Option<Supplier>, SystemError> {
+    sqlx::query_as!(
+        Supplier,
+        r#"
+        UPDATE suppliers
+        SET name = $3, contact_name = $4, phone = $5, email = $6, notes = $7
+        WHERE id = $1 AND system_id = $2
+        RETURNING id, system_id, name, contact_name, phone, email, notes, created_at, updated_at
+        "#,
+        supplier_id,
+        system_id,
+        payload.name,
+        payload.contact_name,
+        payload.phone,
+        payload.email,
+        payload.notes
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update supplier: {}", e);
+        map_supplier_write_error(e)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_supplier(
+    conn: &mut sqlx::PgConnection,
+    supplier_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Supplier>, SystemError> {
+    sqlx::query_as!(
+        Supplier,
+        r#"
+        SELECT id, system_id, name, contact_name, phone, email, notes, created_at, updated_at
+        FROM suppliers
+        WHERE id = $1 AND system_id = $2
+        "#,
+        supplier_id,
+        system_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Only succeeds for suppliers nothing was ever ordered or received from.
+#[cfg(feature = "ssr")]
+pub async fn delete_supplier(
+    conn: &mut Transaction<'_, Postgres>,
+    supplier_id: &Uuid,
+    system_id: &Uuid,
+) -> Result<Option<Supplier>, SystemError> {
+    sqlx::query_as!(
+        Supplier,
+        r#"
+        DELETE FROM suppliers
+        WHERE id = $1 AND system_id = $2
+        RETURNING id, system_id, name, contact_name, phone, email, notes, created_at, updated_at
+        "#,
+        supplier_id,
+        system_id
+    )
+    .fetch_optional(&mut **conn)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to delete supplier: {}", e);
+        map_supplier_write_error(e)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub async fn list_suppliers(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+) -> Result<Vec<Supplier>, SystemError> {
+    sqlx::query_as!(
+        Supplier,
+        r#"
+        SELECT id, system_id, name, contact_name, phone, email, notes, created_at, updated_at
+        FROM suppliers
+        WHERE system_id = $1
+        ORDER BY name ASC
+        "#,
+        system_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// One row per supplier, including those nothing has been received from yet.
+#[cfg(feature = "ssr")]
+pub async fn list_supplier_reports(
+    conn: &mut sqlx::PgConnection,
+    system_id: &Uuid,
+) -> Result<Vec<SupplierReport>, SystemError> {
+    sqlx::query_as!(
+        SupplierReport,
+        r#"
+        SELECT s.id as supplier_id, s.name as supplier_name,
+               COALESCE(t.tracked_units, 0) as "tracked_units!",
+               COALESCE(b.bulk_units, 0) as "bulk_units!",
+               COALESCE(t.tracked_cost, 0) + COALESCE(b.bulk_cost, 0) as "total_cost!",
+               COALESCE(t.units_sold, 0) as "units_sold!",
+               COALESCE(t.units_returned, 0) as "units_returned!",
+               COALESCE(t.units_in_rma, 0) as "units_in_rma!"
+        FROM suppliers s
+        LEFT JOIN LATERAL (
+            SELECT COUNT(*) as tracked_units,
+                   SUM(tu.acquisition_cost) as tracked_cost,
+                   COUNT(*) FILTER (WHERE EXISTS (
+                       SELECT 1 FROM sale_lines sl WHERE sl.tracked_unit_id = tu.id
+                   )) as units_sold,
+                   COUNT(*) FILTER (WHERE EXISTS (
+                       SELECT 1
+                       FROM sale_lines sl
+                       JOIN sale_return_lines rl ON rl.sale_line_id = sl.id
+                       WHERE sl.tracked_unit_id = tu.id
+                   )) as units_returned,
+                   COUNT(*) FILTER (WHERE tu.status = 'RMA') as units_in_rma
+            FROM tracked_units tu
+            WHERE tu.supplier_id = s.id
+        ) t ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT SUM(sa.quantity_delta)::BIGINT as bulk_units,
+                   SUM(sa.quantity_delta * sa.unit_cost) as bulk_cost
+            FROM stock_adjustments sa
+            WHERE sa.supplier_id = s.id AND sa.reason = 'received'
+        ) b ON TRUE
+        WHERE s.system_id = $1
+        ORDER BY s.name ASC
+        "#,
+        system_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
@@ -0,0 +1,281 @@
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use sqlx::PgConnection;
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "public.login_challenge_kind", rename_all = "snake_case")]
+pub enum LoginChallengeKind {
+    /// Redeemable with a TOTP or recovery code.
+    Verify,
+    /// Only good for enrolling 2FA, which then completes the sign-in.
+    Enrol,
+}
+
+#[cfg(feature = "ssr")]
+pub struct TwoFactorState {
+    pub totp_secret: Option<String>,
+    pub totp_pending_secret: Option<String>,
+    pub workspace_requires: bool,
+}
+
+#[cfg(feature = "ssr")]
+pub struct LoginChallenge {
+    pub id: Uuid,
+    pub handler_id: Uuid,
+    pub kind: LoginChallengeKind,
+}
+
+#[cfg(feature = "ssr")]
+pub async fn get_two_factor_state(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+) -> Result<TwoFactorState, SystemError> {
+    sqlx::query_as!(
+        TwoFactorState,
+        r#"
+        SELECT h.totp_secret, h.totp_pending_secret, s.require_two_factor as workspace_requires
+        FROM handlers h
+        JOIN systems s ON s.id = h.system_id
+        WHERE h.id = $1
+        "#,
+        handler_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn set_pending_totp_secret(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    secret: &str,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE handlers SET totp_pending_secret = $2 WHERE id = $1",
+        handler_id,
+        secret
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Promotes the pending secret, recording `step` as already used.
+#[cfg(feature = "ssr")]
+pub async fn activate_totp(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    step: i64,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE handlers
+         SET totp_secret = totp_pending_secret, totp_pending_secret = NULL, totp_last_step = $2
+         WHERE id = $1 AND totp_pending_secret IS NOT NULL",
+        handler_id,
+        step
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn disable_totp(conn: &mut PgConnection, handler_id: &Uuid) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE handlers
+         SET totp_secret = NULL, totp_pending_secret = NULL, totp_last_step = NULL
+         WHERE id = $1",
+        handler_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    sqlx::query!(
+        "DELETE FROM handler_recovery_codes WHERE handler_id = $1",
+        handler_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Claims a TOTP time step. Fails if this or a later step was already used (replay).
+#[cfg(feature = "ssr")]
+pub async fn claim_totp_step(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    step: i64,
+) -> Result<bool, SystemError> {
+    let result = sqlx::query!(
+        "UPDATE handlers SET totp_last_step = $2
+         WHERE id = $1 AND (totp_last_step IS NULL OR totp_last_step < $2)",
+        handler_id,
+        step
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn replace_recovery_codes(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    code_hashes: &[String],
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "DELETE FROM handler_recovery_codes WHERE handler_id = $1",
+        handler_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    sqlx::query!(
+        "INSERT INTO handler_recovery_codes (handler_id, code_hash)
+         SELECT $1, UNNEST($2::TEXT[])",
+        handler_id,
+        code_hashes
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn consume_recovery_code(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    code_hash: &str,
+) -> Result<bool, SystemError> {
+    let result = sqlx::query!(
+        "UPDATE handler_recovery_codes SET used_at = NOW()
+         WHERE handler_id = $1 AND code_hash = $2 AND used_at IS NULL",
+        handler_id,
+        code_hash
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn count_recovery_codes(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+) -> Result<i64, SystemError> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM handler_recovery_codes WHERE handler_id = $1 AND used_at IS NULL"#,
+        handler_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn insert_login_challenge(
+    conn: &mut PgConnection,
+    handler_id: &Uuid,
+    kind: LoginChallengeKind,
+    token_hash: &str,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "INSERT INTO login_challenges (handler_id, kind, token_hash) VALUES ($1, $2, $3)",
+        handler_id,
+        kind as _,
+        token_hash
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+/// An unexpired challenge, locked until the surrounding transaction ends.
+#[cfg(feature = "ssr")]
+pub async fn find_login_challenge_for_update(
+    conn: &mut PgConnection,
+    token_hash: &str,
+) -> Result<Option<LoginChallenge>, SystemError> {
+    sqlx::query_as!(
+        LoginChallenge,
+        r#"
+        SELECT id, handler_id, kind as "kind: LoginChallengeKind"
+        FROM login_challenges
+        WHERE token_hash = $1 AND expires_at > NOW()
+        FOR UPDATE
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Counts a wrong code against the challenge. Returns the new failure count.
+#[cfg(feature = "ssr")]
+pub async fn record_challenge_failure(
+    conn: &mut PgConnection,
+    challenge_id: &Uuid,
+) -> Result<i32, SystemError> {
+    sqlx::query_scalar!(
+        "UPDATE login_challenges SET failed_attempts = failed_attempts + 1
+         WHERE id = $1
+         RETURNING failed_attempts",
+        challenge_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn delete_login_challenge(
+    conn: &mut PgConnection,
+    challenge_id: &Uuid,
+) -> Result<(), SystemError> {
+    sqlx::query!("DELETE FROM login_challenges WHERE id = $1", challenge_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn set_workspace_two_factor(
+    conn: &mut PgConnection,
+    system_id: &Uuid,
+    required: bool,
+) -> Result<(), SystemError> {
+    sqlx::query!(
+        "UPDATE systems SET require_two_factor = $2, updated_at = NOW() WHERE id = $1",
+        system_id,
+        required
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(())
+}
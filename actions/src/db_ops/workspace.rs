@@ -0,0 +1,48 @@
+#[cfg(feature = "ssr")]
+use models::entities::Workspace;
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use sqlx::PgConnection;
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+pub async fn find_workspace_by_handle(
+    conn: &mut PgConnection,
+    handle: &str,
+) -> Result<Option<Workspace>, SystemError> {
+    sqlx::query_as!(
+        Workspace,
+        r#"
+        SELECT id, system_handle as "handle!", system_name as name
+        FROM systems
+        WHERE system_handle = $1
+        "#,
+        handle
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// The System and handle a live session belongs to.
+#[cfg(feature = "ssr")]
+pub async fn get_session_workspace(
+    conn: &mut PgConnection,
+    token_hash: &str,
+) -> Result<Option<(Uuid, String)>, SystemError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT system_id as "system_id!", system_handle
+        FROM sessions
+        WHERE token_hash = $1 AND expires_at > NOW()
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(record.map(|r| (r.system_id, r.system_handle)))
+}
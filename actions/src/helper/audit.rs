@@ -0,0 +1,132 @@
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(any(feature = "ssr", test))]
+use serde_json::{Map, Value};
+
+/// An entry for the audit log, written in the same transaction as the change it describes:
+///
+/// ```ignore
+/// Audit::new(AuditEntity::Product, AuditAction::Updated)
+///     .entity_id(product.id)
+///     .before(&current)
+///     .after(&product)
+///     .record(&mut tx, &user.system_id, Some(&user.id))
+///     .await?;
+/// ```
+#[cfg(feature = "ssr")]
+#[must_use = "an audit entry does nothing until it is recorded"]
+pub struct Audit {
+    entity_type: AuditEntity,
+    entity_id: Option<uuid::Uuid>,
+    action: AuditAction,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+#[cfg(feature = "ssr")]
+impl Audit {
+    pub fn new(entity_type: AuditEntity, action: AuditAction) -> Self {
+        Self {
+            entity_type,
+            entity_id: None,
+            action,
+            before: None,
+            after: None,
+        }
+    }
+
+    pub fn entity_id(mut self, id: uuid::Uuid) -> Self {
+        self.entity_id = Some(id);
+        self
+    }
+
+    /// The entity as it was before the change.
+    pub fn before(mut self, state: &impl serde::Serialize) -> Self {
+        self.before = serde_json::to_value(state).ok();
+        self
+    }
+
+    /// The entity as it is after the change.
+    pub fn after(mut self, state: &impl serde::Serialize) -> Self {
+        self.after = serde_json::to_value(state).ok();
+        self
+    }
+
+    /// Writes the entry on `conn`. `actor_id` is `None` only when nobody is signed in.
+    pub async fn record(
+        self,
+        conn: &mut sqlx::PgConnection,
+        system_id: &uuid::Uuid,
+        actor_id: Option<&uuid::Uuid>,
+    ) -> Result<(), SystemError> {
+        let (before_state, after_state) = match (self.before, self.after) {
+            (Some(before), Some(after)) => changed_fields(before, after),
+            states => states,
+        };
+
+        let record = crate::db_ops::AuditRecord {
+            entity_type: self.entity_type,
+            entity_id: self.entity_id,
+            action: self.action,
+            before_state,
+            after_state,
+            ip_address: super::client_ip().await,
+        };
+
+        crate::db_ops::insert_audit_event(conn, system_id, actor_id, &record).await
+    }
+}
+
+/// Reduces two JSON objects to the top-level fields whose values differ. Anything other
+/// than a pair of objects is kept whole.
+#[cfg(any(feature = "ssr", test))]
+pub fn changed_fields(before: Value, after: Value) -> (Option<Value>, Option<Value>) {
+    let (mut before, mut after) = match (before, after) {
+        (Value::Object(before), Value::Object(after)) => (before, after),
+        (before, after) => return (Some(before), Some(after)),
+    };
+
+    let keys: Vec<String> = before.keys().chain(after.keys()).cloned().collect();
+    let mut old = Map::new();
+    let mut new = Map::new();
+    for key in keys {
+        let was = before.remove(&key);
+        let is = after.remove(&key);
+        if was != is {
+            old.insert(key.clone(), was.unwrap_or(Value::Null));
+            new.insert(key, is.unwrap_or(Value::Null));
+        }
+    }
+
+    (Some(Value::Object(old)), Some(Value::Object(new)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_changed_fields_keeps_only_differences() {
+        let (before, after) = changed_fields(
+            json!({ "name": "Cable", "sku": "C-1", "category": null }),
+            json!({ "name": "USB-C Cable", "sku": "C-1", "category": "Accessories" }),
+        );
+
+        assert_eq!(before, Some(json!({ "name": "Cable", "category": null })));
+        assert_eq!(
+            after,
+            Some(json!({ "name": "USB-C Cable", "category": "Accessories" }))
+        );
+    }
+
+    #[test]
+    fn test_changed_fields_handles_added_and_removed_keys() {
+        let (before, after) = changed_fields(json!({ "a": 1 }), json!({ "b": 2 }));
+
+        assert_eq!(before, Some(json!({ "a": 1, "b": null })));
+        assert_eq!(after, Some(json!({ "a": null, "b": 2 })));
+    }
+}
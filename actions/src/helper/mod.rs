@@ -1,3 +1,12 @@
+mod audit;
+mod session_context;
 mod session_helper;
+mod tokens;
 
+#[cfg(feature = "ssr")]
+pub use audit::*;
+#[cfg(feature = "ssr")]
+pub use session_context::*;
 pub use session_helper::*;
+#[cfg(feature = "ssr")]
+pub use tokens::*;
@@ -0,0 +1,96 @@
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use models::HandlerRole;
+#[cfg(feature = "ssr")]
+use models::entities::User;
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// The authenticated caller of a server action.
+#[cfg(feature = "ssr")]
+pub struct SessionContext {
+    pub pool: PgPool,
+    pub user: User,
+    /// Digest of the session cookie, as stored in `sessions.token_hash`.
+    pub token_hash: String,
+}
+
+#[cfg(feature = "ssr")]
+impl SessionContext {
+    pub fn role(&self) -> HandlerRole {
+        self.user.handler_role
+    }
+
+    pub fn can(&self, permission: Permission) -> bool {
+        permission.is_granted_to(self.role())
+    }
+
+    pub fn require(&self, permission: Permission) -> Result<(), SystemError> {
+        crate::permissions::require(self.role(), permission)
+    }
+
+    /// A read-only view of the caller's workspace. Anything written through it is rolled
+    /// back when it is dropped; use [`Self::begin`] and commit to make changes.
+    pub async fn acquire(&self) -> Result<Transaction<'static, Postgres>, SystemError> {
+        self.begin().await
+    }
+
+    /// A transaction scoped to the caller's workspace by row-level security.
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>, SystemError> {
+        crate::tenant::begin_tenant_transaction(&self.pool, &self.user.system_id).await
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub fn db_pool() -> Result<PgPool, SystemError> {
+    use_context::<PgPool>()
+        .ok_or_else(|| SystemError::database("Database connection pool not found in context."))
+}
+
+/// Resolves the session cookie to its handler, sliding the expiry forward.
+/// Returns `None` when there is no cookie or the session has expired.
+#[cfg(feature = "ssr")]
+pub async fn current_session() -> Result<Option<SessionContext>, SystemError> {
+    let pool = db_pool()?;
+
+    let Some(token) = super::get_session_token().await else {
+        return Ok(None);
+    };
+    let token_hash = super::digest_token(&token);
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    let Some(user) = crate::db_ops::get_session_user(&mut conn, &token_hash).await? else {
+        return Ok(None);
+    };
+
+    let config = crate::config::app_config()?;
+    if let Err(e) =
+        crate::db_ops::refresh_session_expiry(&mut conn, &token_hash, &config.session).await
+    {
+        tracing::warn!(error = %e, "Failed to refresh session expiry");
+    }
+    drop(conn);
+
+    Ok(Some(SessionContext {
+        pool,
+        user,
+        token_hash,
+    }))
+}
+
+/// Like [`current_session`], but an anonymous caller is an `Unauthorized` error.
+#[cfg(feature = "ssr")]
+pub async fn require_session() -> Result<SessionContext, SystemError> {
+    current_session().await?.ok_or_else(|| {
+        SystemError::unauthorized("You must be logged in. Your session may have expired.")
+    })
+}
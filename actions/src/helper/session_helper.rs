@@ -9,15 +9,19 @@ pub fn set_session_token(token: &str) -> Result<(), SystemError> {
     use axum_extra::extract::cookie::{Cookie, SameSite};
     use leptos_axum::ResponseOptions;
 
+    let config = crate::config::app_config()?;
     let response_options = use_context::<ResponseOptions>()
         .ok_or_else(|| SystemError::general("ResponseOptions context not found"))?;
 
-    let cookie = Cookie::build(("session_token", token.to_string()))
+    let mut cookie = Cookie::build(("session_token", token.to_string()))
         .path("/")
         .http_only(true)
         .same_site(SameSite::Strict)
-        .secure(false)
+        .secure(config.cookie.secure)
         .build();
+    if let Some(domain) = &config.cookie.domain {
+        cookie.set_domain(domain.clone());
+    }
 
     let header_value = HeaderValue::from_str(&cookie.to_string())
         .map_err(|e| SystemError::general(e.to_string()))?;
@@ -34,6 +38,69 @@ pub async fn get_session_token() -> Option<String> {
     jar.get("session_token").map(|c| c.value().to_string())
 }
 
+/// Best-effort client address: the hop the trusted proxy appended to `X-Forwarded-For` when
+/// proxy headers are trusted, otherwise the peer address of the TCP connection.
+#[cfg(feature = "ssr")]
+pub async fn client_ip() -> Option<String> {
+    use axum::extract::ConnectInfo;
+    use axum::http::HeaderMap;
+    use leptos_axum::extract;
+    use std::net::SocketAddr;
+
+    let trust_proxy_headers = crate::config::app_config()
+        .map(|c| c.trust_proxy_headers)
+        .unwrap_or(false);
+    if trust_proxy_headers {
+        let headers: HeaderMap = extract().await.ok()?;
+        let forwarded = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .next_back()
+            .and_then(forwarded_client);
+        if let Some(ip) = forwarded {
+            return Some(ip);
+        }
+    }
+
+    let ConnectInfo(addr): ConnectInfo<SocketAddr> = extract().await.ok()?;
+    Some(addr.ip().to_string())
+}
+
+/// The rightmost `X-Forwarded-For` entry. Proxies append the address they saw, so anything
+/// to its left came from the client and cannot be trusted.
+#[cfg(feature = "ssr")]
+fn forwarded_client(header: &str) -> Option<String> {
+    header
+        .rsplit(',')
+        .next()
+        .map(str::trim)
+        .and_then(|v| v.parse::<std::net::IpAddr>().ok())
+        .map(|ip| ip.to_string())
+}
+
+/// The device details recorded on a new session.
+#[cfg(feature = "ssr")]
+pub async fn client_device() -> crate::db_ops::SessionDevice {
+    use axum::http::HeaderMap;
+    use leptos_axum::extract;
+
+    // Long enough for any real browser, short enough that a client cannot bloat the table
+    const MAX_USER_AGENT_LEN: usize = 512;
+
+    let user_agent = extract::<HeaderMap>().await.ok().and_then(|headers| {
+        headers
+            .get(axum::http::header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.chars().take(MAX_USER_AGENT_LEN).collect())
+    });
+
+    crate::db_ops::SessionDevice {
+        user_agent,
+        ip_address: client_ip().await,
+    }
+}
+
 #[cfg(feature = "ssr")]
 pub fn remove_session_token() -> Result<(), SystemError> {
     use axum::http::HeaderValue;
@@ -44,6 +111,12 @@ pub fn remove_session_token() -> Result<(), SystemError> {
         .ok_or_else(|| SystemError::general("ResponseOptions context not found"))?;
 
     let mut cookie = Cookie::build("session_token").path("/").build();
+    // The removal only matches a cookie set with the same domain
+    if let Ok(config) = crate::config::app_config()
+        && let Some(domain) = &config.cookie.domain
+    {
+        cookie.set_domain(domain.clone());
+    }
 
     // This tells the browser to delete the cookie immediately
     cookie.make_removal();
@@ -54,3 +127,22 @@ pub fn remove_session_token() -> Result<(), SystemError> {
     response_options.insert_header(axum::http::header::SET_COOKIE, header_value);
     Ok(())
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forwarded_client_takes_proxy_hop() {
+        assert_eq!(
+            forwarded_client("6.6.6.6, 203.0.113.7").as_deref(),
+            Some("203.0.113.7")
+        ); // Spoofed entry on the left is ignored
+        assert_eq!(
+            forwarded_client("203.0.113.7").as_deref(),
+            Some("203.0.113.7")
+        );
+        assert_eq!(forwarded_client("203.0.113.7, not-an-ip"), None);
+        assert_eq!(forwarded_client(""), None);
+    }
+}
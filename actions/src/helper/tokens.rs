@@ -0,0 +1,46 @@
+#[cfg(feature = "ssr")]
+use sha2::{Digest, Sha256};
+
+/// A fresh 256-bit token from the OS CSPRNG, hex encoded for use in links and cookies.
+#[cfg(feature = "ssr")]
+pub fn generate_token() -> String {
+    use rand_core::{OsRng, RngCore};
+
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// The SHA-256 digest stored in place of a raw token, so a leaked table cannot be replayed.
+#[cfg(feature = "ssr")]
+pub fn digest_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.trim().as_bytes()))
+}
+
+/// A one-time recovery code such as `k7qm-2xfa-p3wd-6zrt`: 80 random bits, base32 encoded
+/// so it stays short enough to type from paper yet too large to guess against its digest.
+#[cfg(feature = "ssr")]
+pub fn generate_recovery_code() -> String {
+    use data_encoding::BASE32_NOPAD;
+    use rand_core::{OsRng, RngCore};
+
+    let mut bytes = [0u8; 10];
+    OsRng.fill_bytes(&mut bytes);
+    let code = BASE32_NOPAD.encode(&bytes).to_ascii_lowercase();
+    format!(
+        "{}-{}-{}-{}",
+        &code[..4],
+        &code[4..8],
+        &code[8..12],
+        &code[12..]
+    )
+}
+
+/// Recovery codes are compared case-insensitively and without separators.
+#[cfg(feature = "ssr")]
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
@@ -1,11 +1,22 @@
 use leptos::prelude::*;
-use models::entities::Product;
+use models::entities::{
+    LookupResult, Product, ProductPage, ProductRemoval, TrackedUnitIntakeReport, UntrackedInventory,
+};
 use models::errors::SystemError;
-use models::payloads::AddProductPayload;
+use models::payloads::{
+    AddProductPayload, AdjustBulkStockPayload, ProductListQuery, ReceiveBulkStockPayload,
+    ReceiveTrackedUnitsPayload, UpdateProductPayload,
+};
 use tracing::instrument;
 
 #[cfg(feature = "ssr")]
-use sqlx::PgPool;
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use crate::purchase_orders::ReceivingLine;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
 #[cfg(feature = "ssr")]
 use validator::Validate;
 
@@ -19,51 +30,470 @@ pub async fn add_product(payload: AddProductPayload) -> Result<Product, SystemEr
             .validate()
             .map_err(|e| SystemError::validation(e.to_string()))?;
 
-        // 2. Get the database pool
-        let pool = use_context::<PgPool>().ok_or_else(|| {
-            SystemError::database("Database connection pool not found in context.")
-        })?;
+        // 2. Resolve the caller's session
+        let session = require_session().await?;
+        session.require(Permission::ManageCatalog)?;
+        let user = &session.user;
+
+        let mut tx = session.begin().await?;
+
+        // 3. Insert the actual product!
+        let product =
+            crate::db_ops::insert_product(&mut tx, &payload, &user.system_id, &user.id).await?;
+
+        // 4. If it's an untracked (bulk) item, initialize its bucket
+        if !product.is_tracked {
+            crate::db_ops::initialize_untracked_inventory(&mut tx, &product.id).await?;
+        }
+
+        Audit::new(AuditEntity::Product, AuditAction::Created)
+            .entity_id(product.id)
+            .after(&product)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(product)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all)]
+#[server(GetProducts)]
+pub async fn get_products(query: ProductListQuery) -> Result<ProductPage, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let query = query.normalized();
+
+        // 1. Resolve the caller's session
+        let session = require_session().await?;
+        let user = &session.user;
+        let mut conn = session.acquire().await?;
+
+        // 2. Fetch the requested page
+        let (items, total) =
+            crate::db_ops::list_products(&mut conn, &user.system_id, &query).await?;
+        Ok(ProductPage {
+            items,
+            total,
+            page: query.page,
+            page_size: query.page_size,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = query;
+        unreachable!()
+    }
+}
+
+#[instrument(ret, err, skip_all, fields(product_id = %payload.product_id))]
+#[server(UpdateProduct)]
+pub async fn update_product(payload: UpdateProductPayload) -> Result<Product, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::ManageCatalog)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        // Locked so no delivery lands between the footprint check and the mode change
+        let current =
+            crate::db_ops::lock_product_for_system(&mut tx, &payload.product_id, &user.system_id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Product not found in this workspace."))?;
+
+        // Stock rows are shaped by the tracking mode, so it is frozen once stock exists
+        let toggles_tracking = current.is_tracked != payload.is_tracked;
+        if toggles_tracking {
+            let footprint = crate::db_ops::get_product_footprint(&mut tx, &current.id).await?;
+            if footprint.has_stock || footprint.has_history {
+                return Err(SystemError::inventory(format!(
+                    "'{}' already has stock or sales, so its tracking mode cannot change.",
+                    current.name
+                )));
+            }
+        }
+
+        let product = crate::db_ops::update_product(&mut tx, &payload, &user.system_id, &user.id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Product not found in this workspace."))?;
+
+        if toggles_tracking {
+            if product.is_tracked {
+                crate::db_ops::remove_untracked_inventory(&mut tx, &product.id).await?;
+            } else {
+                crate::db_ops::initialize_untracked_inventory(&mut tx, &product.id).await?;
+            }
+        }
+
+        Audit::new(AuditEntity::Product, AuditAction::Updated)
+            .entity_id(product.id)
+            .before(&current)
+            .after(&product)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(product)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Retires a product. Anything with sales or stock history is archived so invoices and
+/// ledgers keep resolving; a product nobody has touched yet is deleted outright.
+#[instrument(err, skip_all, fields(product_id = %product_id))]
+#[server(ArchiveProduct)]
+pub async fn archive_product(product_id: uuid::Uuid) -> Result<ProductRemoval, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ManageCatalog)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let product = crate::db_ops::get_product_for_system(&mut tx, &product_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Product not found in this workspace."))?;
+
+        let footprint = crate::db_ops::get_product_footprint(&mut tx, &product.id).await?;
+
+        let (removal, audit) = if footprint.has_history {
+            let archived = crate::db_ops::archive_product(&mut tx, &product.id, &user.id).await?;
+            let audit = Audit::new(AuditEntity::Product, AuditAction::Archived)
+                .before(&product)
+                .after(&archived);
+            (ProductRemoval::Archived(archived), audit)
+        } else {
+            crate::db_ops::delete_product(&mut tx, &product.id).await?;
+            let audit = Audit::new(AuditEntity::Product, AuditAction::Deleted).before(&product);
+            (
+                ProductRemoval::Deleted {
+                    product_id: product.id,
+                },
+                audit,
+            )
+        };
+        audit
+            .entity_id(product.id)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(removal)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = product_id;
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all, fields(product_id = %payload.product_id, units = payload.units.len()))]
+#[server(ReceiveTrackedUnits)]
+pub async fn receive_tracked_units(
+    payload: ReceiveTrackedUnitsPayload,
+) -> Result<TrackedUnitIntakeReport, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use models::entities::{SerialIntakeOutcome, SerialIntakeResult};
+        use std::collections::{HashMap, HashSet};
+
+        // 1. Validate the payload
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        // 2. Resolve the caller's session
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
 
-        // 3. Get the active user's session
-        use crate::helper::get_session_token;
-        let token = get_session_token().await;
-
-        if let Some(t) = token {
-            let mut conn = pool
-                .acquire()
-                .await
-                .map_err(|e| SystemError::database(e.to_string()))?;
-
-            // Look up who is making this request
-            let user = crate::db_ops::get_session_user(&mut conn, &t).await?;
-
-            if let Some(user) = user {
-                let mut tx = pool
-                    .begin()
-                    .await
-                    .map_err(|e| SystemError::database(e.to_string()))?;
-
-                // 4. Insert the actual product!
-                let product =
-                    crate::db_ops::insert_product(&mut tx, &payload, &user.system_id, &user.id)
-                        .await?;
-
-                // 5. If it's an untracked (bulk) item, initialize its bucket
-                if !product.is_tracked {
-                    crate::db_ops::initialize_untracked_inventory(&mut tx, &product.id).await?;
+        let product =
+            crate::db_ops::lock_product_for_system(&mut tx, &payload.product_id, &user.system_id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Product not found in this workspace."))?;
+
+        if product.is_archived {
+            return Err(SystemError::inventory(format!(
+                "'{}' is archived and cannot receive new stock.",
+                product.name
+            )));
+        }
+        if !product.is_tracked {
+            return Err(SystemError::inventory(format!(
+                "'{}' is a bulk product. Receive it as untracked stock instead.",
+                product.name
+            )));
+        }
+
+        if let Some(supplier_id) = &payload.supplier_id {
+            require_supplier(&mut tx, supplier_id, &user.system_id).await?;
+        }
+
+        let units = payload.normalized_units();
+
+        // 3. Flag serials scanned more than once in this batch
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for serial in units.iter().flatten() {
+            *occurrences.entry(serial.clone()).or_default() += 1;
+        }
+
+        crate::db_ops::lock_serial_registry(&mut tx, &user.system_id).await?;
+
+        // Deliveries against a purchase order take its supplier and count towards its line
+        let receiving = match &payload.purchase_order_line_id {
+            Some(line_id) => {
+                let receiving = ReceivingLine::lock(
+                    &mut tx,
+                    line_id,
+                    &user.system_id,
+                    &product.id,
+                    payload.supplier_id.as_ref(),
+                )
+                .await?;
+                receiving.check_quantity(units.len())?;
+                receiving.check_unit_cost(payload.acquisition_cost)?;
+                Some(receiving)
+            }
+            None => None,
+        };
+        let supplier_id = receiving
+            .as_ref()
+            .map(|r| r.order.supplier_id)
+            .or(payload.supplier_id);
+
+        // 4. Flag serials that already live in this workspace
+        let all_serials: Vec<String> = occurrences.keys().cloned().collect();
+        let existing: HashSet<String> =
+            crate::db_ops::find_existing_serials(&mut tx, &user.system_id, &all_serials)
+                .await?
+                .into_iter()
+                .collect();
+
+        // 5. Insert every clean unit
+        let mut results = Vec::with_capacity(units.len());
+        for serial_numbers in units {
+            let duplicated: Vec<String> = serial_numbers
+                .iter()
+                .filter(|s| occurrences[*s] > 1)
+                .cloned()
+                .collect();
+            let conflicting: Vec<String> = serial_numbers
+                .iter()
+                .filter(|s| existing.contains(*s))
+                .cloned()
+                .collect();
+
+            let outcome = if !conflicting.is_empty() {
+                SerialIntakeOutcome::AlreadyExists { conflicting }
+            } else if !duplicated.is_empty() {
+                SerialIntakeOutcome::DuplicateInBatch {
+                    conflicting: duplicated,
                 }
+            } else {
+                let unit = crate::db_ops::insert_tracked_unit(
+                    &mut tx,
+                    &product.id,
+                    &serial_numbers,
+                    payload.acquisition_cost,
+                    payload.target_msrp,
+                    supplier_id.as_ref(),
+                    &user.id,
+                )
+                .await?;
+                Audit::new(AuditEntity::TrackedUnit, AuditAction::Received)
+                    .entity_id(unit.id)
+                    .after(&unit)
+                    .record(&mut tx, &user.system_id, Some(&user.id))
+                    .await?;
+                SerialIntakeOutcome::Received { unit_id: unit.id }
+            };
+
+            results.push(SerialIntakeResult {
+                serial_numbers,
+                outcome,
+            });
+        }
+
+        let report = TrackedUnitIntakeReport {
+            product_id: product.id,
+            results,
+        };
+        if let Some(receiving) = &receiving {
+            receiving
+                .record(&mut tx, report.received_count() as i32, &user.id)
+                .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(
+            received = report.received_count(),
+            rejected = report.rejected_count(),
+            "Tracked unit batch processed"
+        );
 
-                tx.commit()
-                    .await
-                    .map_err(|e| SystemError::database(e.to_string()))?;
+        Ok(report)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Checks that a supplier chosen for a delivery belongs to the caller's workspace.
+#[cfg(feature = "ssr")]
+async fn require_supplier(
+    conn: &mut sqlx::PgConnection,
+    supplier_id: &uuid::Uuid,
+    system_id: &uuid::Uuid,
+) -> Result<(), SystemError> {
+    crate::db_ops::get_supplier(conn, supplier_id, system_id)
+        .await?
+        .ok_or_else(|| SystemError::not_found("Supplier not found in this workspace."))?;
+    Ok(())
+}
+
+/// Locks a bulk product in the caller's workspace, rejecting serialized products.
+#[cfg(feature = "ssr")]
+async fn require_bulk_product(
+    conn: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    product_id: &uuid::Uuid,
+    system_id: &uuid::Uuid,
+) -> Result<Product, SystemError> {
+    let product = crate::db_ops::lock_product_for_system(conn, product_id, system_id)
+        .await?
+        .ok_or_else(|| SystemError::not_found("Product not found in this workspace."))?;
+
+    if product.is_tracked {
+        return Err(SystemError::inventory(format!(
+            "'{}' is a tracked product. Scan its serial numbers instead.",
+            product.name
+        )));
+    }
+
+    Ok(product)
+}
+
+#[instrument(err, skip_all, fields(product_id = %payload.product_id, quantity = payload.quantity))]
+#[server(ReceiveBulkStock)]
+pub async fn receive_bulk_stock(
+    payload: ReceiveBulkStockPayload,
+) -> Result<UntrackedInventory, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use models::entities::StockAdjustmentReason;
 
-                return Ok(product);
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let product = require_bulk_product(&mut tx, &payload.product_id, &user.system_id).await?;
+        if product.is_archived {
+            return Err(SystemError::inventory(format!(
+                "'{}' is archived and cannot receive new stock.",
+                product.name
+            )));
+        }
+        if let Some(supplier_id) = &payload.supplier_id {
+            require_supplier(&mut tx, supplier_id, &user.system_id).await?;
+        }
+        let receiving = match &payload.purchase_order_line_id {
+            Some(line_id) => {
+                let receiving = ReceivingLine::lock(
+                    &mut tx,
+                    line_id,
+                    &user.system_id,
+                    &product.id,
+                    payload.supplier_id.as_ref(),
+                )
+                .await?;
+                receiving.check_quantity(payload.quantity as usize)?;
+                receiving.check_unit_cost(payload.unit_cost)?;
+                Some(receiving)
             }
+            None => None,
+        };
+        let supplier_id = receiving
+            .as_ref()
+            .map(|r| r.order.supplier_id)
+            .or(payload.supplier_id);
+
+        let level = crate::db_ops::receive_bulk_stock(
+            &mut tx,
+            &product.id,
+            payload.quantity,
+            payload.unit_cost,
+            &user.id,
+        )
+        .await?;
+
+        crate::db_ops::record_stock_adjustment(
+            &mut tx,
+            &user.system_id,
+            &product.id,
+            payload.quantity,
+            StockAdjustmentReason::Received,
+            Some(payload.unit_cost),
+            payload.note.as_deref(),
+            supplier_id.as_ref(),
+            &user.id,
+        )
+        .await?;
+
+        Audit::new(AuditEntity::BulkStock, AuditAction::Received)
+            .entity_id(product.id)
+            .before(&serde_json::json!({ "quantity": level.quantity - payload.quantity }))
+            .after(&serde_json::json!({
+                "quantity": level.quantity,
+                "unit_cost": payload.unit_cost,
+                "supplier_id": supplier_id,
+                "note": payload.note,
+            }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+        if let Some(receiving) = &receiving {
+            receiving
+                .record(&mut tx, payload.quantity, &user.id)
+                .await?;
         }
 
-        Err(SystemError::unauthorized(
-            "You must be logged in to modify the catalog.",
-        ))
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(level)
     }
     #[cfg(not(feature = "ssr"))]
     {
@@ -72,42 +502,144 @@ pub async fn add_product(payload: AddProductPayload) -> Result<Product, SystemEr
     }
 }
 
-#[server(GetProducts)]
-pub async fn get_products() -> Result<Vec<Product>, SystemError> {
+#[instrument(err, skip_all, fields(product_id = %payload.product_id, delta = payload.quantity_delta))]
+#[server(AdjustBulkStock)]
+pub async fn adjust_bulk_stock(
+    payload: AdjustBulkStockPayload,
+) -> Result<UntrackedInventory, SystemError> {
     #[cfg(feature = "ssr")]
     {
-        // 1. Get the database pool
-        let pool = use_context::<PgPool>().ok_or_else(|| {
-            SystemError::database("Database connection pool not found in context.")
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::AdjustStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let product = require_bulk_product(&mut tx, &payload.product_id, &user.system_id).await?;
+
+        let level = crate::db_ops::adjust_bulk_stock(
+            &mut tx,
+            &product.id,
+            payload.quantity_delta,
+            &user.id,
+        )
+        .await?
+        .ok_or_else(|| {
+            SystemError::inventory(format!(
+                "'{}' has no stock on hand to adjust.",
+                product.name
+            ))
         })?;
 
-        // 2. Get the session token
-        use crate::helper::get_session_token;
-        let token = get_session_token().await;
+        crate::db_ops::record_stock_adjustment(
+            &mut tx,
+            &user.system_id,
+            &product.id,
+            payload.quantity_delta,
+            payload.reason,
+            None,
+            payload.note.as_deref(),
+            None,
+            &user.id,
+        )
+        .await?;
+
+        Audit::new(AuditEntity::BulkStock, AuditAction::Adjusted)
+            .entity_id(product.id)
+            .before(&serde_json::json!({ "quantity": level.quantity - payload.quantity_delta }))
+            .after(&serde_json::json!({
+                "quantity": level.quantity,
+                "reason": payload.reason,
+                "note": payload.note,
+            }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
 
-        if let Some(t) = token {
-            let mut conn = pool
-                .acquire()
-                .await
-                .map_err(|e| SystemError::database(e.to_string()))?;
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
 
-            // 3. Get the user
-            let user = crate::db_ops::get_session_user(&mut conn, &t).await?;
+        Ok(level)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Maximum number of fuzzy matches returned to the scanner box.
+#[cfg(feature = "ssr")]
+const LOOKUP_RESULT_LIMIT: i64 = 20;
+
+#[instrument(err, skip_all, fields(query = %query))]
+#[server(UniversalLookup)]
+pub async fn universal_lookup(query: String) -> Result<LookupResult, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(LookupResult::NoMatch);
+        }
+
+        let session = require_session().await?;
+        let user = &session.user;
+        let mut conn = session.acquire().await?;
+
+        // 1. Exact serial/IMEI
+        if let Some(unit) =
+            crate::db_ops::find_tracked_unit_by_serial(&mut conn, &user.system_id, query).await?
+        {
+            let product =
+                crate::db_ops::get_product_for_system(&mut conn, &unit.product_id, &user.system_id)
+                    .await?
+                    .ok_or_else(|| {
+                        SystemError::not_found("Product not found in this workspace.")
+                    })?;
+            // The scanner is open to every role; only show the cost to those allowed
+            let mut unit = unit;
+            if !session.can(Permission::ViewCosts) {
+                unit.acquisition_cost = rust_decimal::Decimal::ZERO;
+            }
+            return Ok(LookupResult::TrackedUnitHit {
+                unit: Box::new(unit),
+                product: Box::new(product),
+            });
+        }
+
+        // 2. A known customer's phone number
+        if let Some(phone) = models::payloads::normalize_phone(query)
+            && let Some(customer) =
+                crate::db_ops::find_customer_by_phone(&mut conn, &user.system_id, &phone).await?
+        {
+            return Ok(LookupResult::CustomerHit {
+                customer: Box::new(customer),
+            });
+        }
 
-            if let Some(user) = user {
-                // 4. Fetch the products!
-                let products =
-                    crate::db_ops::get_products_for_system(&mut conn, &user.system_id).await?;
-                return Ok(products);
+        // 3. Exact SKU, then 4. fuzzy name/category
+        for exact in [true, false] {
+            let products = crate::db_ops::search_stocked_products(
+                &mut conn,
+                &user.system_id,
+                query,
+                exact,
+                LOOKUP_RESULT_LIMIT,
+            )
+            .await?;
+            if !products.is_empty() {
+                return Ok(LookupResult::ProductHit { products, exact });
             }
         }
 
-        Err(SystemError::unauthorized(
-            "You must be logged in to view the catalog.",
-        ))
+        Ok(LookupResult::NoMatch)
     }
     #[cfg(not(feature = "ssr"))]
     {
+        let _ = query;
         unreachable!()
     }
 }
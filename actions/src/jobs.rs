@@ -0,0 +1,134 @@
+//! Periodic housekeeping run alongside the web server.
+//!
+//! Each [`Job`] gets its own tokio interval so a slow purge never delays the others.
+//! All of them stop at the next tick boundary once shutdown is signalled.
+
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use sqlx::{PgConnection, PgPool};
+#[cfg(feature = "ssr")]
+use std::time::Duration;
+#[cfg(feature = "ssr")]
+use tokio::sync::watch;
+#[cfg(feature = "ssr")]
+use tokio::task::JoinSet;
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Job {
+    PurgeExpiredSessions,
+    PurgeSpentResetTokens,
+    PurgeExpiredInvites,
+    PurgeExpiredLoginChallenges,
+    PurgeOldLoginAttempts,
+    PurgeOldResetRequests,
+    ReleaseExpiredReservations,
+}
+
+#[cfg(feature = "ssr")]
+impl Job {
+    pub const ALL: [Self; 7] = [
+        Self::PurgeExpiredSessions,
+        Self::PurgeSpentResetTokens,
+        Self::PurgeExpiredInvites,
+        Self::PurgeExpiredLoginChallenges,
+        Self::PurgeOldLoginAttempts,
+        Self::PurgeOldResetRequests,
+        Self::ReleaseExpiredReservations,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PurgeExpiredSessions => "purge_expired_sessions",
+            Self::PurgeSpentResetTokens => "purge_spent_reset_tokens",
+            Self::PurgeExpiredInvites => "purge_expired_invites",
+            Self::PurgeExpiredLoginChallenges => "purge_expired_login_challenges",
+            Self::PurgeOldLoginAttempts => "purge_old_login_attempts",
+            Self::PurgeOldResetRequests => "purge_old_reset_requests",
+            Self::ReleaseExpiredReservations => "release_expired_reservations",
+        }
+    }
+
+    pub fn every(&self) -> Duration {
+        match self {
+            Self::PurgeExpiredSessions => Duration::from_secs(15 * 60),
+            Self::PurgeSpentResetTokens => Duration::from_secs(60 * 60),
+            Self::PurgeExpiredInvites => Duration::from_secs(6 * 60 * 60),
+            Self::PurgeExpiredLoginChallenges => Duration::from_secs(10 * 60),
+            Self::PurgeOldLoginAttempts => Duration::from_secs(24 * 60 * 60),
+            Self::PurgeOldResetRequests => Duration::from_secs(6 * 60 * 60),
+            Self::ReleaseExpiredReservations => Duration::from_secs(15 * 60),
+        }
+    }
+
+    /// Runs the job once. Returns how many rows it touched.
+    pub async fn run(&self, conn: &mut PgConnection) -> Result<u64, SystemError> {
+        use crate::db_ops::{
+            purge_expired_invites, purge_expired_login_challenges, purge_expired_sessions,
+            purge_old_login_attempts, purge_old_password_reset_requests,
+            purge_spent_password_reset_tokens,
+        };
+        use crate::reservations::release_expired_reservations;
+
+        match self {
+            Self::PurgeExpiredSessions => purge_expired_sessions(conn).await,
+            Self::PurgeSpentResetTokens => purge_spent_password_reset_tokens(conn).await,
+            Self::PurgeExpiredInvites => purge_expired_invites(conn).await,
+            Self::PurgeExpiredLoginChallenges => purge_expired_login_challenges(conn).await,
+            Self::PurgeOldLoginAttempts => purge_old_login_attempts(conn).await,
+            Self::PurgeOldResetRequests => purge_old_password_reset_requests(conn).await,
+            Self::ReleaseExpiredReservations => release_expired_reservations(conn).await,
+        }
+    }
+}
+
+/// Starts every [`Job`]. The returned set finishes once `shutdown` flips to `true`.
+#[cfg(feature = "ssr")]
+pub fn spawn_jobs(pool: PgPool, shutdown: watch::Receiver<bool>) -> JoinSet<()> {
+    let mut jobs = JoinSet::new();
+    for job in Job::ALL {
+        jobs.spawn(run_on_interval(job, pool.clone(), shutdown.clone()));
+    }
+    jobs
+}
+
+#[cfg(feature = "ssr")]
+async fn run_on_interval(job: Job, pool: PgPool, mut shutdown: watch::Receiver<bool>) {
+    let mut interval = tokio::time::interval(job.every());
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => run_once(job, &pool).await,
+            changed = shutdown.changed() => {
+                if changed.is_err() || *shutdown.borrow() {
+                    tracing::debug!(job = job.name(), "Background job stopped");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn run_once(job: Job, pool: &PgPool) {
+    let started = std::time::Instant::now();
+
+    let result = match pool.acquire().await {
+        Ok(mut conn) => job.run(&mut conn).await,
+        Err(e) => Err(SystemError::database(e.to_string())),
+    };
+
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(rows) => tracing::info!(
+            job = job.name(),
+            rows,
+            elapsed_ms,
+            next_run_secs = job.every().as_secs(),
+            "Background job finished"
+        ),
+        Err(e) => tracing::warn!(job = job.name(), error = %e, elapsed_ms, "Background job failed"),
+    }
+}
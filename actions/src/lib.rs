@@ -1,9 +1,37 @@
+pub mod audit;
 pub mod auth;
+pub mod config;
+pub mod customers;
 pub(crate) mod db_ops;
 pub(crate) mod helper;
 pub mod inventory;
+pub mod jobs;
+pub mod mail;
+pub mod permissions;
+pub mod purchase_orders;
+pub mod reservations;
+pub mod sales;
+pub mod sessions;
+pub mod staff;
 mod status;
+pub mod suppliers;
+#[cfg(feature = "ssr")]
+pub mod tenant;
+pub mod totp;
+pub mod two_factor;
+pub mod workspace;
 
+pub use audit::*;
 pub use auth::*;
+pub use customers::*;
 pub use inventory::*;
+pub use permissions::*;
+pub use purchase_orders::*;
+pub use reservations::*;
+pub use sales::*;
+pub use sessions::*;
+pub use staff::*;
 pub use status::*;
+pub use suppliers::*;
+pub use two_factor::*;
+pub use workspace::*;
@@ -0,0 +1,102 @@
+//! Outgoing email. Actions fetch the [`Mailer`] from context and never talk to a transport
+//! directly, so production can swap in an SMTP/API sender without touching them.
+
+#[cfg(feature = "ssr")]
+use models::errors::SystemError;
+#[cfg(feature = "ssr")]
+use std::future::Future;
+#[cfg(feature = "ssr")]
+use std::path::PathBuf;
+#[cfg(feature = "ssr")]
+use std::pin::Pin;
+#[cfg(feature = "ssr")]
+use std::sync::Arc;
+
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone)]
+pub struct OutgoingMail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[cfg(feature = "ssr")]
+pub type MailFuture<'a> = Pin<Box<dyn Future<Output = Result<(), SystemError>> + Send + 'a>>;
+
+#[cfg(feature = "ssr")]
+pub trait MailSender: Send + Sync {
+    fn send<'a>(&'a self, mail: &'a OutgoingMail) -> MailFuture<'a>;
+}
+
+/// The mail transport plus the public URL that links in messages are built against.
+#[cfg(feature = "ssr")]
+#[derive(Clone)]
+pub struct Mailer {
+    sender: Arc<dyn MailSender>,
+    base_url: String,
+}
+
+#[cfg(feature = "ssr")]
+impl Mailer {
+    pub fn new(sender: impl MailSender + 'static, base_url: impl Into<String>) -> Self {
+        Self {
+            sender: Arc::new(sender),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Absolute link to an in-app path such as `/auth/reset-password/<token>`.
+    pub fn link(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    pub async fn send(&self, mail: OutgoingMail) -> Result<(), SystemError> {
+        self.sender.send(&mail).await
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub fn mailer() -> Result<Mailer, SystemError> {
+    leptos::prelude::use_context::<Mailer>()
+        .ok_or_else(|| SystemError::general("Mailer not found in context."))
+}
+
+/// Development sender: writes every message to a file in `dir` and logs where it went.
+#[cfg(feature = "ssr")]
+pub struct OutboxMailer {
+    dir: PathBuf,
+}
+
+#[cfg(feature = "ssr")]
+impl OutboxMailer {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl MailSender for OutboxMailer {
+    fn send<'a>(&'a self, mail: &'a OutgoingMail) -> MailFuture<'a> {
+        Box::pin(async move {
+            tokio::fs::create_dir_all(&self.dir)
+                .await
+                .map_err(|e| SystemError::general(format!("Mail outbox unavailable: {e}")))?;
+
+            let path = self.dir.join(format!(
+                "{}-{}.eml",
+                chrono::Utc::now().format("%Y%m%dT%H%M%S"),
+                uuid::Uuid::new_v4()
+            ));
+            let contents = format!(
+                "To: {}\nSubject: {}\n\n{}\n",
+                mail.to, mail.subject, mail.body
+            );
+            tokio::fs::write(&path, contents)
+                .await
+                .map_err(|e| SystemError::general(format!("Failed to write mail: {e}")))?;
+
+            tracing::info!(to = %mail.to, subject = %mail.subject, path = %path.display(), "Mail written to outbox");
+            Ok(())
+        })
+    }
+}
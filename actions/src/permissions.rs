@@ -0,0 +1,143 @@
+//! Central role → permission model used by every server action.
+//!
+//! | Permission        | SystemAdmin | SystemManager | SystemSalesman |
+//! |-------------------|:-----------:|:-------------:|:--------------:|
+//! | `ManageCatalog`   |      ✓      |       ✓       |                |
+//! | `ReceiveStock`    |      ✓      |       ✓       |                |
+//! | `AdjustStock`     |      ✓      |       ✓       |                |
+//! | `Sell`            |      ✓      |       ✓       |       ✓        |
+//! | `Refund`          |      ✓      |       ✓       |                |
+//! | `DeleteCustomers` |      ✓      |       ✓       |                |
+//! | `ViewCosts`       |      ✓      |       ✓       |                |
+//! | `ManageStaff`     |      ✓      |               |                |
+//! | `ManageWorkspace` |      ✓      |               |                |
+//! | `ViewAuditLog`    |      ✓      |               |                |
+//!
+//! Browsing the catalog and the POS lookup only need an authenticated session.
+
+use models::HandlerRole;
+use models::errors::SystemError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Create, edit and archive catalog products.
+    ManageCatalog,
+    /// Book incoming tracked units and bulk deliveries.
+    ReceiveStock,
+    /// Write off or correct bulk quantities.
+    AdjustStock,
+    /// Ring up sales at the POS and put stock on hold for customers.
+    Sell,
+    /// Process returns and refunds.
+    Refund,
+    /// Remove customer records. Adding and editing them only needs `Sell`.
+    DeleteCustomers,
+    /// See acquisition costs and margins.
+    ViewCosts,
+    /// Invite, re-role and deactivate handlers.
+    ManageStaff,
+    /// Change workspace-wide settings such as the two-factor policy.
+    ManageWorkspace,
+    /// Read the history of who changed what.
+    ViewAuditLog,
+}
+
+impl Permission {
+    pub const ALL: [Self; 10] = [
+        Self::ManageCatalog,
+        Self::ReceiveStock,
+        Self::AdjustStock,
+        Self::Sell,
+        Self::Refund,
+        Self::DeleteCustomers,
+        Self::ViewCosts,
+        Self::ManageStaff,
+        Self::ManageWorkspace,
+        Self::ViewAuditLog,
+    ];
+
+    /// Whether `role` holds this permission.
+    pub fn is_granted_to(&self, role: HandlerRole) -> bool {
+        use HandlerRole::*;
+
+        match self {
+            Self::Sell => true,
+            Self::ManageCatalog
+            | Self::ReceiveStock
+            | Self::AdjustStock
+            | Self::Refund
+            | Self::DeleteCustomers
+            | Self::ViewCosts => matches!(role, SystemAdmin | SystemManager),
+            Self::ManageStaff | Self::ManageWorkspace | Self::ViewAuditLog => {
+                matches!(role, SystemAdmin)
+            }
+        }
+    }
+
+    fn action(&self) -> &'static str {
+        match self {
+            Self::ManageCatalog => "manage the product catalog",
+            Self::ReceiveStock => "receive stock",
+            Self::AdjustStock => "adjust stock levels",
+            Self::Sell => "complete sales",
+            Self::Refund => "process refunds",
+            Self::DeleteCustomers => "delete customer records",
+            Self::ViewCosts => "view acquisition costs",
+            Self::ManageStaff => "manage staff",
+            Self::ManageWorkspace => "change workspace settings",
+            Self::ViewAuditLog => "view the audit log",
+        }
+    }
+}
+
+/// Fails with `SystemError::unauthorized` unless `role` holds `permission`.
+pub fn require(role: HandlerRole, permission: Permission) -> Result<(), SystemError> {
+    if permission.is_granted_to(role) {
+        Ok(())
+    } else {
+        Err(SystemError::unauthorized(format!(
+            "Your role does not allow you to {}.",
+            permission.action()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn granted(role: HandlerRole) -> Vec<Permission> {
+        Permission::ALL
+            .into_iter()
+            .filter(|p| p.is_granted_to(role))
+            .collect()
+    }
+
+    #[test]
+    fn test_permission_matrix() {
+        use Permission::*;
+
+        assert_eq!(granted(HandlerRole::SystemAdmin), Permission::ALL.to_vec());
+        assert_eq!(
+            granted(HandlerRole::SystemManager),
+            vec![
+                ManageCatalog,
+                ReceiveStock,
+                AdjustStock,
+                Sell,
+                Refund,
+                DeleteCustomers,
+                ViewCosts
+            ]
+        );
+        assert_eq!(granted(HandlerRole::SystemSalesman), vec![Sell]);
+    }
+
+    #[test]
+    fn test_require() {
+        assert!(require(HandlerRole::SystemSalesman, Permission::Sell).is_ok());
+        assert!(require(HandlerRole::SystemManager, Permission::ManageStaff).is_err());
+        assert!(require(HandlerRole::SystemSalesman, Permission::ManageCatalog).is_err());
+    }
+}
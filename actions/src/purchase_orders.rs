@@ -0,0 +1,351 @@
+use leptos::prelude::*;
+use models::entities::{PurchaseOrder, PurchaseOrderDetail};
+use models::errors::SystemError;
+use models::payloads::CreatePurchaseOrderPayload;
+use tracing::instrument;
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity, PurchaseOrderLine, PurchaseOrderStatus};
+#[cfg(feature = "ssr")]
+use rust_decimal::Decimal;
+#[cfg(feature = "ssr")]
+use sqlx::{Postgres, Transaction};
+#[cfg(feature = "ssr")]
+use validator::Validate;
+
+/// Drafts an order to a supplier. Nothing is expected until it is marked as ordered.
+#[instrument(err, skip_all, fields(lines = payload.lines.len()))]
+#[server(CreatePurchaseOrder)]
+pub async fn create_purchase_order(
+    payload: CreatePurchaseOrderPayload,
+) -> Result<PurchaseOrder, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        crate::db_ops::get_supplier(&mut tx, &payload.supplier_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Supplier not found in this workspace."))?;
+        for line in &payload.lines {
+            let product =
+                crate::db_ops::get_product_for_system(&mut tx, &line.product_id, &user.system_id)
+                    .await?
+                    .ok_or_else(|| {
+                        SystemError::not_found("Product not found in this workspace.")
+                    })?;
+            if product.is_archived {
+                return Err(SystemError::inventory(format!(
+                    "'{}' is archived and cannot be ordered.",
+                    product.name
+                )));
+            }
+        }
+
+        let po_number = crate::db_ops::next_purchase_order_number(&mut tx, &user.system_id).await?;
+        let notes = payload
+            .notes
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+        let order = crate::db_ops::insert_purchase_order(
+            &mut tx,
+            &user.system_id,
+            po_number,
+            &payload.supplier_id,
+            notes,
+            &user.id,
+        )
+        .await?;
+        for line in &payload.lines {
+            crate::db_ops::insert_purchase_order_line(&mut tx, &order.id, line).await?;
+        }
+
+        Audit::new(AuditEntity::PurchaseOrder, AuditAction::Created)
+            .entity_id(order.id)
+            .after(&serde_json::json!({
+                "po_number": order.po_number,
+                "supplier_id": order.supplier_id,
+                "lines": payload.lines,
+            }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(order)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Purchase orders with their lines, newest first. `open_only` hides received and
+/// cancelled orders.
+#[instrument(err, skip_all)]
+#[server(GetPurchaseOrders)]
+pub async fn get_purchase_orders(open_only: bool) -> Result<Vec<PurchaseOrderDetail>, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use std::collections::HashMap;
+
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut conn = session.acquire().await?;
+
+        let orders =
+            crate::db_ops::list_purchase_orders(&mut conn, &user.system_id, open_only).await?;
+        let ids: Vec<Uuid> = orders.iter().map(|o| o.id).collect();
+        let mut lines: HashMap<Uuid, Vec<_>> = HashMap::new();
+        for line in crate::db_ops::list_purchase_order_lines(&mut conn, &ids).await? {
+            lines.entry(line.purchase_order_id).or_default().push(line);
+        }
+        let suppliers: HashMap<Uuid, String> =
+            crate::db_ops::list_suppliers(&mut conn, &user.system_id)
+                .await?
+                .into_iter()
+                .map(|s| (s.id, s.name))
+                .collect();
+
+        Ok(orders
+            .into_iter()
+            .map(|order| PurchaseOrderDetail {
+                supplier_name: suppliers
+                    .get(&order.supplier_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                lines: lines.remove(&order.id).unwrap_or_default(),
+                order,
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = open_only;
+        unreachable!()
+    }
+}
+
+/// Records that a draft has been sent to the supplier, opening it for receiving.
+#[instrument(err, skip_all, fields(%purchase_order_id))]
+#[server(MarkPurchaseOrderOrdered)]
+pub async fn mark_purchase_order_ordered(
+    purchase_order_id: Uuid,
+) -> Result<PurchaseOrder, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let order =
+            crate::db_ops::lock_purchase_order(&mut tx, &purchase_order_id, &user.system_id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Purchase order not found."))?;
+        if order.status != PurchaseOrderStatus::Draft {
+            return Err(SystemError::validation(format!(
+                "PO #{} is already {}.",
+                order.po_number,
+                order.status.label().to_lowercase()
+            )));
+        }
+
+        let ordered = crate::db_ops::set_purchase_order_status(
+            &mut tx,
+            &order.id,
+            PurchaseOrderStatus::Ordered,
+        )
+        .await?;
+
+        Audit::new(AuditEntity::PurchaseOrder, AuditAction::Ordered)
+            .entity_id(order.id)
+            .before(&order)
+            .after(&ordered)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(ordered)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = purchase_order_id;
+        unreachable!()
+    }
+}
+
+/// Closes an open order. Stock already received against it stays on the books.
+#[instrument(err, skip_all, fields(%purchase_order_id))]
+#[server(CancelPurchaseOrder)]
+pub async fn cancel_purchase_order(purchase_order_id: Uuid) -> Result<PurchaseOrder, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let order =
+            crate::db_ops::lock_purchase_order(&mut tx, &purchase_order_id, &user.system_id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Purchase order not found."))?;
+        if !order.status.is_open() {
+            return Err(SystemError::validation(format!(
+                "PO #{} is already {}.",
+                order.po_number,
+                order.status.label().to_lowercase()
+            )));
+        }
+
+        let cancelled = crate::db_ops::set_purchase_order_status(
+            &mut tx,
+            &order.id,
+            PurchaseOrderStatus::Cancelled,
+        )
+        .await?;
+
+        Audit::new(AuditEntity::PurchaseOrder, AuditAction::Cancelled)
+            .entity_id(order.id)
+            .before(&order)
+            .after(&cancelled)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(cancelled)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = purchase_order_id;
+        unreachable!()
+    }
+}
+
+/// A purchase order line locked for an incoming delivery, with its order.
+#[cfg(feature = "ssr")]
+pub(crate) struct ReceivingLine {
+    pub order: PurchaseOrder,
+    pub line: PurchaseOrderLine,
+}
+
+#[cfg(feature = "ssr")]
+impl ReceivingLine {
+    /// Locks the line and its order and checks a delivery of `product_id` from `supplier_id`
+    /// may be booked against it. A delivery without a supplier takes the order's.
+    pub async fn lock(
+        conn: &mut Transaction<'_, Postgres>,
+        line_id: &Uuid,
+        system_id: &Uuid,
+        product_id: &Uuid,
+        supplier_id: Option<&Uuid>,
+    ) -> Result<Self, SystemError> {
+        let line = crate::db_ops::lock_purchase_order_line(conn, line_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Purchase order line not found."))?;
+        let order = crate::db_ops::lock_purchase_order(conn, &line.purchase_order_id, system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Purchase order line not found."))?;
+
+        if !order.status.is_receivable() {
+            return Err(SystemError::inventory(format!(
+                "PO #{} is {} and cannot receive stock.",
+                order.po_number,
+                order.status.label().to_lowercase()
+            )));
+        }
+        if line.product_id != *product_id {
+            return Err(SystemError::validation(format!(
+                "That line of PO #{} is for '{}'.",
+                order.po_number, line.product_name
+            )));
+        }
+        if supplier_id.is_some_and(|s| *s != order.supplier_id) {
+            return Err(SystemError::validation(format!(
+                "PO #{} was ordered from a different supplier.",
+                order.po_number
+            )));
+        }
+
+        Ok(Self { order, line })
+    }
+
+    /// Rejects a delivery larger than what the line still expects.
+    pub fn check_quantity(&self, quantity: usize) -> Result<(), SystemError> {
+        let outstanding = self.line.outstanding_quantity();
+        if quantity > outstanding as usize {
+            return Err(SystemError::inventory(format!(
+                "PO #{} only has {} '{}' outstanding.",
+                self.order.po_number, outstanding, self.line.product_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rejects a delivery valued at anything other than the cost agreed on the line, so the
+    /// order and the stock it brings in never disagree on what it cost.
+    pub fn check_unit_cost(&self, unit_cost: Decimal) -> Result<(), SystemError> {
+        if unit_cost != self.line.unit_cost {
+            return Err(SystemError::validation(format!(
+                "PO #{} was ordered at {} per '{}'.",
+                self.order.po_number, self.line.unit_cost, self.line.product_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Counts `quantity` received on the line and moves the order to partially or fully
+    /// received, closing it once nothing is outstanding.
+    pub async fn record(
+        &self,
+        conn: &mut Transaction<'_, Postgres>,
+        quantity: i32,
+        actor_id: &Uuid,
+    ) -> Result<PurchaseOrder, SystemError> {
+        if quantity == 0 {
+            return Ok(self.order.clone());
+        }
+
+        crate::db_ops::add_received_quantity(conn, &self.line.id, quantity).await?;
+        let order = crate::db_ops::refresh_purchase_order_progress(conn, &self.order.id).await?;
+
+        Audit::new(AuditEntity::PurchaseOrder, AuditAction::Received)
+            .entity_id(order.id)
+            .before(&serde_json::json!({
+                "status": self.order.status,
+                "line_id": self.line.id,
+                "received_quantity": self.line.received_quantity,
+            }))
+            .after(&serde_json::json!({
+                "status": order.status,
+                "line_id": self.line.id,
+                "received_quantity": self.line.received_quantity + quantity,
+            }))
+            .record(conn, &order.system_id, Some(actor_id))
+            .await?;
+
+        Ok(order)
+    }
+}
@@ -0,0 +1,401 @@
+use leptos::prelude::*;
+use models::entities::{ConvertedReservation, Reservation, ReservationDetail, ReservationStatus};
+use models::errors::SystemError;
+use models::payloads::CreateReservationPayload;
+use tracing::instrument;
+
+#[cfg(feature = "ssr")]
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
+#[cfg(feature = "ssr")]
+use sqlx::PgConnection;
+#[cfg(feature = "ssr")]
+use uuid::Uuid;
+#[cfg(feature = "ssr")]
+use validator::Validate;
+
+/// Holds specific tracked units or a bulk quantity for a customer until `expires_at`.
+/// Held stock is not offered at the till.
+#[instrument(err, skip_all, fields(lines = payload.lines.len()))]
+#[server(CreateReservation)]
+pub async fn create_reservation(
+    payload: CreateReservationPayload,
+) -> Result<ReservationDetail, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        // 1. Validate the hold
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+        if payload.expires_at <= chrono::Utc::now() {
+            return Err(SystemError::validation(
+                "The reservation must expire in the future.",
+            ));
+        }
+
+        // 2. Resolve the caller's session
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let phone = payload
+            .customer_phone
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+        let reservation = crate::db_ops::insert_reservation(
+            &mut tx,
+            &user.system_id,
+            payload.customer_name.trim(),
+            phone,
+            payload.deposit,
+            payload.expires_at,
+            &user.id,
+        )
+        .await?;
+
+        // 3. Take every line off the shelf
+        for line in &payload.lines {
+            let product =
+                crate::db_ops::get_product_for_system(&mut tx, &line.product_id, &user.system_id)
+                    .await?
+                    .ok_or_else(|| {
+                        SystemError::not_found("Product not found in this workspace.")
+                    })?;
+
+            let held = match (product.is_tracked, line.tracked_unit_id) {
+                (true, Some(unit_id)) => {
+                    crate::db_ops::reserve_tracked_unit(
+                        &mut tx,
+                        &unit_id,
+                        &product.id,
+                        &user.system_id,
+                        &user.id,
+                    )
+                    .await?
+                }
+                (true, None) => {
+                    return Err(SystemError::inventory(format!(
+                        "'{}' is tracked. Scan the unit's serial number to reserve it.",
+                        product.name
+                    )));
+                }
+                (false, Some(_)) => {
+                    return Err(SystemError::inventory(format!(
+                        "'{}' is a bulk product and has no individual units.",
+                        product.name
+                    )));
+                }
+                (false, None) => {
+                    crate::db_ops::reserve_bulk_stock(&mut tx, &product.id, line.quantity, &user.id)
+                        .await?
+                }
+            };
+            if !held {
+                return Err(SystemError::inventory(format!(
+                    "Not enough '{}' available to reserve.",
+                    product.name
+                )));
+            }
+
+            crate::db_ops::insert_reservation_line(&mut tx, &reservation.id, line).await?;
+        }
+
+        let items = crate::db_ops::list_reserved_items(&mut tx, &[reservation.id]).await?;
+
+        Audit::new(AuditEntity::Reservation, AuditAction::Created)
+            .entity_id(reservation.id)
+            .after(&serde_json::json!({
+                "customer_name": reservation.customer_name,
+                "deposit": reservation.deposit,
+                "expires_at": reservation.expires_at,
+                "items": items,
+            }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(reservation_id = %reservation.id, "Reservation created");
+
+        Ok(ReservationDetail { reservation, items })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Reservations with their held items; `None` lists every status.
+#[instrument(err, skip_all)]
+#[server(GetReservations)]
+pub async fn get_reservations(
+    status: Option<ReservationStatus>,
+) -> Result<Vec<ReservationDetail>, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use std::collections::HashMap;
+
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut conn = session.acquire().await?;
+
+        let reservations =
+            crate::db_ops::list_reservations(&mut conn, &user.system_id, status).await?;
+        let ids: Vec<Uuid> = reservations.iter().map(|r| r.id).collect();
+        let mut items: HashMap<Uuid, Vec<_>> = HashMap::new();
+        for item in crate::db_ops::list_reserved_items(&mut conn, &ids).await? {
+            items.entry(item.reservation_id).or_default().push(item);
+        }
+
+        Ok(reservations
+            .into_iter()
+            .map(|reservation| ReservationDetail {
+                items: items.remove(&reservation.id).unwrap_or_default(),
+                reservation,
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = status;
+        unreachable!()
+    }
+}
+
+/// Sells everything held by an active reservation at the agreed prices. The deposit
+/// already taken is recorded on the sale and subtracted from what the customer pays now.
+#[instrument(err, skip_all, fields(%reservation_id))]
+#[server(ConvertReservation)]
+pub async fn convert_reservation(
+    reservation_id: uuid::Uuid,
+) -> Result<ConvertedReservation, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use models::entities::SaleReceipt;
+        use models::payloads::{MAX_MONEY_AMOUNT, SaleLineInput, lines_total};
+
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        // 1. Lock the hold; an expired one is still honoured until the job releases it
+        let reservation =
+            crate::db_ops::lock_reservation(&mut tx, &reservation_id, &user.system_id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Reservation not found."))?;
+        require_active(&reservation)?;
+        let held = crate::db_ops::list_reservation_lines(&mut tx, &reservation.id).await?;
+        let inputs: Vec<SaleLineInput> = held
+            .iter()
+            .map(|line| SaleLineInput {
+                product_id: line.product_id,
+                tracked_unit_id: line.tracked_unit_id,
+                quantity: line.quantity,
+                unit_price: line.unit_price,
+            })
+            .collect();
+        let total = lines_total(&inputs)
+            .filter(|total| *total <= MAX_MONEY_AMOUNT)
+            .ok_or_else(|| SystemError::validation("The reservation total is too large."))?;
+
+        // 2. Open the sale at the reserved prices, recording the deposit already taken
+        let invoice_number = crate::db_ops::next_invoice_number(&mut tx, &user.system_id).await?;
+        let sale = crate::db_ops::insert_sale(
+            &mut tx,
+            &user.system_id,
+            invoice_number,
+            total,
+            reservation.deposit.min(total),
+            &user.id,
+            None,
+        )
+        .await?;
+
+        // 3. Move the held stock out of inventory
+        let mut lines = Vec::with_capacity(held.len());
+        for (line, input) in held.iter().zip(&inputs) {
+            let unit_cost = match line.tracked_unit_id {
+                Some(unit_id) => {
+                    crate::db_ops::sell_reserved_unit(&mut tx, &unit_id, &user.id).await?
+                }
+                None => {
+                    crate::db_ops::sell_reserved_bulk_stock(
+                        &mut tx,
+                        &line.product_id,
+                        line.quantity,
+                        &user.id,
+                    )
+                    .await?
+                }
+            }
+            .ok_or_else(|| {
+                SystemError::inventory("Part of this reservation is no longer on hold.")
+            })?;
+
+            lines.push(crate::db_ops::insert_sale_line(&mut tx, &sale.id, input, unit_cost).await?);
+        }
+
+        let converted = crate::db_ops::close_reservation(
+            &mut tx,
+            &reservation.id,
+            ReservationStatus::Converted,
+            Some(&sale.id),
+        )
+        .await?;
+
+        Audit::new(AuditEntity::Sale, AuditAction::Created)
+            .entity_id(sale.id)
+            .after(&serde_json::json!({
+                "invoice_number": sale.invoice_number,
+                "total_amount": sale.total_amount,
+                "deposit_applied": sale.deposit_applied,
+                "reservation_id": reservation.id,
+                "lines": lines,
+            }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+        Audit::new(AuditEntity::Reservation, AuditAction::Converted)
+            .entity_id(reservation.id)
+            .before(&reservation)
+            .after(&converted)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        let balance_due = sale.amount_collected();
+        tracing::info!(invoice_number, %balance_due, "Reservation converted to sale");
+
+        Ok(ConvertedReservation {
+            reservation: converted,
+            receipt: crate::sales::receipt_for(session.role(), SaleReceipt { sale, lines }),
+            balance_due,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = reservation_id;
+        unreachable!()
+    }
+}
+
+/// Releases an active reservation early and puts its stock back on sale. Any deposit is
+/// refunded outside the system.
+#[instrument(err, skip_all, fields(%reservation_id))]
+#[server(CancelReservation)]
+pub async fn cancel_reservation(reservation_id: uuid::Uuid) -> Result<Reservation, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let reservation =
+            crate::db_ops::lock_reservation(&mut tx, &reservation_id, &user.system_id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Reservation not found."))?;
+        require_active(&reservation)?;
+
+        let cancelled = release_reservation(
+            &mut tx,
+            &reservation,
+            ReservationStatus::Cancelled,
+            Some(&user.id),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(cancelled)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = reservation_id;
+        unreachable!()
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn require_active(reservation: &Reservation) -> Result<(), SystemError> {
+    if reservation.status == ReservationStatus::Active {
+        Ok(())
+    } else {
+        Err(SystemError::validation(format!(
+            "This reservation is already {}.",
+            reservation.status.label().to_lowercase()
+        )))
+    }
+}
+
+/// Puts everything a locked, active reservation holds back on sale and closes it with
+/// `status`. `actor_id` is `None` when the expiry job does it.
+#[cfg(feature = "ssr")]
+async fn release_reservation(
+    conn: &mut PgConnection,
+    reservation: &Reservation,
+    status: ReservationStatus,
+    actor_id: Option<&Uuid>,
+) -> Result<Reservation, SystemError> {
+    for line in crate::db_ops::list_reservation_lines(conn, &reservation.id).await? {
+        match line.tracked_unit_id {
+            // A unit that left `Reserved` some other way has nothing left to release
+            Some(unit_id) => {
+                crate::db_ops::release_tracked_unit(conn, &unit_id, actor_id).await?;
+            }
+            None => {
+                crate::db_ops::release_bulk_stock(conn, &line.product_id, line.quantity, actor_id)
+                    .await?
+            }
+        }
+    }
+
+    let released = crate::db_ops::close_reservation(conn, &reservation.id, status, None).await?;
+
+    Audit::new(AuditEntity::Reservation, AuditAction::Released)
+        .entity_id(reservation.id)
+        .before(reservation)
+        .after(&released)
+        .record(conn, &reservation.system_id, actor_id)
+        .await?;
+
+    Ok(released)
+}
+
+/// Releases every reservation whose hold has run out. Run by the background jobs as the
+/// owning role, so it covers every workspace.
+#[cfg(feature = "ssr")]
+pub async fn release_expired_reservations(conn: &mut PgConnection) -> Result<u64, SystemError> {
+    use sqlx::Connection;
+
+    // Bounded so one run never holds locks on a huge backlog; the next tick takes the rest
+    const BATCH: i64 = 500;
+
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+    let expired = crate::db_ops::lock_expired_reservations(&mut tx, BATCH).await?;
+    for reservation in &expired {
+        release_reservation(&mut tx, reservation, ReservationStatus::Expired, None).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(expired.len() as u64)
+}
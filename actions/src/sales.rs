@@ -0,0 +1,448 @@
+use leptos::prelude::*;
+use models::entities::{ReturnReceipt, ReturnableSale, SaleReceipt};
+use models::errors::SystemError;
+use models::payloads::{CompleteSalePayload, ProcessReturnPayload};
+use tracing::instrument;
+
+#[cfg(feature = "ssr")]
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use models::HandlerRole;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity, ReturnCondition, StockAdjustmentReason};
+#[cfg(feature = "ssr")]
+use validator::Validate;
+
+/// The receipt as `role` may see it. Every role can sell, but what the goods cost us is
+/// only shown to those allowed to view costs.
+#[cfg(feature = "ssr")]
+pub(crate) fn receipt_for(role: HandlerRole, mut receipt: SaleReceipt) -> SaleReceipt {
+    if !Permission::ViewCosts.is_granted_to(role) {
+        for line in &mut receipt.lines {
+            line.unit_cost = rust_decimal::Decimal::ZERO;
+        }
+    }
+    receipt
+}
+
+/// Ledger movements for a bulk line coming back. Defective goods are booked in and written
+/// off together, so they are accounted for without going back on sale.
+#[cfg(feature = "ssr")]
+fn bulk_return_movements(
+    condition: ReturnCondition,
+    quantity: i32,
+) -> Vec<(i32, StockAdjustmentReason)> {
+    match condition {
+        ReturnCondition::Resellable => vec![(quantity, StockAdjustmentReason::Returned)],
+        ReturnCondition::Defective => vec![
+            (quantity, StockAdjustmentReason::Returned),
+            (-quantity, StockAdjustmentReason::Damaged),
+        ],
+    }
+}
+
+#[instrument(err, skip_all, fields(lines = payload.lines.len()))]
+#[server(CompleteSale)]
+pub async fn complete_sale(payload: CompleteSalePayload) -> Result<SaleReceipt, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        // 1. Validate the cart
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+        let total_amount = payload
+            .total_amount()
+            .ok_or_else(|| SystemError::validation("The sale total is too large."))?;
+
+        // 2. Resolve the caller's session
+        let session = require_session().await?;
+        session.require(Permission::Sell)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        // 3. Allocate the invoice number and open the sale
+        if let Some(customer_id) = &payload.customer_id {
+            crate::db_ops::get_customer(&mut tx, customer_id, &user.system_id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Customer not found."))?;
+        }
+        let invoice_number = crate::db_ops::next_invoice_number(&mut tx, &user.system_id).await?;
+        let sale = crate::db_ops::insert_sale(
+            &mut tx,
+            &user.system_id,
+            invoice_number,
+            total_amount,
+            rust_decimal::Decimal::ZERO,
+            &user.id,
+            payload.customer_id.as_ref(),
+        )
+        .await?;
+
+        // 4. Take every line out of stock and record what it cost us
+        let mut lines = Vec::with_capacity(payload.lines.len());
+        for line in &payload.lines {
+            let product =
+                crate::db_ops::get_product_for_system(&mut tx, &line.product_id, &user.system_id)
+                    .await?
+                    .ok_or_else(|| {
+                        SystemError::not_found("Product not found in this workspace.")
+                    })?;
+
+            let unit_cost = match (product.is_tracked, line.tracked_unit_id) {
+                (true, Some(unit_id)) => crate::db_ops::mark_tracked_unit_sold(
+                    &mut tx,
+                    &unit_id,
+                    &product.id,
+                    &user.system_id,
+                    &user.id,
+                )
+                .await?
+                .ok_or_else(|| {
+                    SystemError::inventory(format!(
+                        "This '{}' unit is no longer in stock.",
+                        product.name
+                    ))
+                })?,
+                (true, None) => {
+                    return Err(SystemError::inventory(format!(
+                        "'{}' is tracked. Scan the unit's serial number to sell it.",
+                        product.name
+                    )));
+                }
+                (false, Some(_)) => {
+                    return Err(SystemError::inventory(format!(
+                        "'{}' is a bulk product and has no individual units.",
+                        product.name
+                    )));
+                }
+                (false, None) => {
+                    crate::db_ops::sell_bulk_stock(&mut tx, &product.id, line.quantity, &user.id)
+                        .await?
+                        .ok_or_else(|| {
+                            SystemError::inventory(format!(
+                                "Not enough '{}' in stock to sell {}.",
+                                product.name, line.quantity
+                            ))
+                        })?
+                }
+            };
+
+            lines.push(crate::db_ops::insert_sale_line(&mut tx, &sale.id, line, unit_cost).await?);
+        }
+
+        Audit::new(AuditEntity::Sale, AuditAction::Created)
+            .entity_id(sale.id)
+            .after(&serde_json::json!({
+                "invoice_number": sale.invoice_number,
+                "total_amount": sale.total_amount,
+                "customer_id": sale.customer_id,
+                "lines": lines,
+            }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(invoice_number, total = %sale.total_amount, "Sale completed");
+
+        Ok(receipt_for(session.role(), SaleReceipt { sale, lines }))
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Looks up a completed sale by invoice number, with what can still be returned from it.
+#[instrument(err, skip_all, fields(invoice_number))]
+#[server(GetReturnableSale)]
+pub async fn get_returnable_sale(invoice_number: i32) -> Result<ReturnableSale, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::Refund)?;
+        let user = &session.user;
+        let mut conn = session.acquire().await?;
+
+        let sale = crate::db_ops::find_sale_by_invoice(&mut conn, &user.system_id, invoice_number)
+            .await?
+            .ok_or_else(|| SystemError::not_found(format!("No sale #{invoice_number} found.")))?;
+        let lines = crate::db_ops::list_returnable_lines(&mut conn, &sale.id).await?;
+        let refunded_amount = crate::db_ops::refunded_amount(&mut conn, &sale.id).await?;
+
+        Ok(ReturnableSale {
+            sale,
+            lines,
+            refunded_amount,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = invoice_number;
+        unreachable!()
+    }
+}
+
+/// Takes goods back against a completed sale and records the refund. Resellable goods go
+/// back in stock; defective tracked units go to RMA and defective bulk quantities are
+/// written off.
+#[instrument(err, skip_all, fields(sale_id = %payload.sale_id, lines = payload.lines.len()))]
+#[server(ProcessReturn)]
+pub async fn process_return(payload: ProcessReturnPayload) -> Result<ReturnReceipt, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use models::entities::TrackedUnitStatus;
+        use rust_decimal::Decimal;
+        use std::collections::HashMap;
+
+        // 1. Validate the request
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        // 2. Resolve the caller's session
+        let session = require_session().await?;
+        session.require(Permission::Refund)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        // 3. Lock the sale so concurrent returns see each other's quantities
+        let sale = crate::db_ops::lock_sale_for_return(&mut tx, &payload.sale_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Sale not found in this workspace."))?;
+        let sold: HashMap<_, _> = crate::db_ops::list_returnable_lines(&mut tx, &sale.id)
+            .await?
+            .into_iter()
+            .map(|line| (line.id, line))
+            .collect();
+
+        // 4. Never take back more than was sold
+        let mut returned_value = Decimal::ZERO;
+        for input in &payload.lines {
+            let line = sold.get(&input.sale_line_id).ok_or_else(|| {
+                SystemError::validation("A returned item is not part of this sale.")
+            })?;
+            if input.quantity > line.returnable_quantity() {
+                return Err(SystemError::validation(format!(
+                    "Only {} of '{}' can still be returned from this sale.",
+                    line.returnable_quantity(),
+                    line.product_name
+                )));
+            }
+            returned_value += line.unit_price * Decimal::from(input.quantity);
+        }
+        if payload.refund_amount > returned_value {
+            return Err(SystemError::validation(format!(
+                "The refund cannot exceed the {returned_value} paid for the returned items."
+            )));
+        }
+        // A reservation deposit is refunded outside the till, so only what was collected here counts
+        let refunded = crate::db_ops::refunded_amount(&mut tx, &sale.id).await?;
+        let refundable = (sale.amount_collected() - refunded).max(Decimal::ZERO);
+        if payload.refund_amount > refundable {
+            return Err(SystemError::validation(format!(
+                "Only {refundable} of sale #{} can still be refunded at the till. The {} reservation deposit is refunded outside the system.",
+                sale.invoice_number, sale.deposit_applied
+            )));
+        }
+
+        // 5. Record the refund, then put every line back where it belongs
+        let reason = payload
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty());
+        let sale_return = crate::db_ops::insert_sale_return(
+            &mut tx,
+            &user.system_id,
+            &sale.id,
+            payload.refund_amount,
+            payload.refund_method,
+            reason,
+            &user.id,
+        )
+        .await?;
+
+        let mut lines = Vec::with_capacity(payload.lines.len());
+        for input in &payload.lines {
+            let line = &sold[&input.sale_line_id];
+
+            match line.tracked_unit_id {
+                Some(unit_id) => {
+                    let status = match input.condition {
+                        ReturnCondition::Resellable => TrackedUnitStatus::InStock,
+                        ReturnCondition::Defective => TrackedUnitStatus::Rma,
+                    };
+                    if !crate::db_ops::return_tracked_unit(&mut tx, &unit_id, status, &user.id)
+                        .await?
+                    {
+                        return Err(SystemError::inventory(format!(
+                            "This '{}' unit is no longer marked as sold.",
+                            line.product_name
+                        )));
+                    }
+                }
+                None => {
+                    let movements = bulk_return_movements(input.condition, input.quantity);
+                    let restocked: i32 = movements.iter().map(|(delta, _)| delta).sum();
+                    if restocked != 0 {
+                        crate::db_ops::adjust_bulk_stock(
+                            &mut tx,
+                            &line.product_id,
+                            restocked,
+                            &user.id,
+                        )
+                        .await?
+                        .ok_or_else(|| {
+                            SystemError::inventory(format!(
+                                "'{}' no longer has a bulk stock level to return into.",
+                                line.product_name
+                            ))
+                        })?;
+                    }
+                    for (delta, reason) in movements {
+                        let note = match reason {
+                            StockAdjustmentReason::Damaged => format!(
+                                "Written off: defective return against sale #{}",
+                                sale.invoice_number
+                            ),
+                            _ => format!("Return against sale #{}", sale.invoice_number),
+                        };
+                        crate::db_ops::record_stock_adjustment(
+                            &mut tx,
+                            &user.system_id,
+                            &line.product_id,
+                            delta,
+                            reason,
+                            None,
+                            Some(&note),
+                            None,
+                            &user.id,
+                        )
+                        .await?;
+                    }
+                    if input.condition == ReturnCondition::Defective {
+                        Audit::new(AuditEntity::BulkStock, AuditAction::Adjusted)
+                            .entity_id(line.product_id)
+                            .after(&serde_json::json!({
+                                "written_off": input.quantity,
+                                "reason": StockAdjustmentReason::Damaged,
+                                "sale_id": sale.id,
+                            }))
+                            .record(&mut tx, &user.system_id, Some(&user.id))
+                            .await?;
+                    }
+                }
+            }
+
+            lines.push(
+                crate::db_ops::insert_sale_return_line(
+                    &mut tx,
+                    &sale_return.id,
+                    &line.id,
+                    input.quantity,
+                    input.condition,
+                )
+                .await?,
+            );
+        }
+
+        Audit::new(AuditEntity::Sale, AuditAction::Returned)
+            .entity_id(sale.id)
+            .after(&serde_json::json!({
+                "return_id": sale_return.id,
+                "refund_amount": sale_return.refund_amount,
+                "refund_method": sale_return.refund_method,
+                "lines": lines,
+            }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(
+            invoice_number = sale.invoice_number,
+            refund = %sale_return.refund_amount,
+            "Return processed"
+        );
+
+        Ok(ReturnReceipt { sale_return, lines })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use models::entities::{Sale, SaleLine};
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn receipt() -> SaleReceipt {
+        let sale_id = Uuid::new_v4();
+        SaleReceipt {
+            sale: Sale {
+                id: sale_id,
+                system_id: Uuid::new_v4(),
+                invoice_number: 1,
+                total_amount: Decimal::new(99900, 2),
+                deposit_applied: Decimal::ZERO,
+                sold_by: Uuid::new_v4(),
+                customer_id: None,
+                created_at: Utc::now(),
+            },
+            lines: vec![SaleLine {
+                id: Uuid::new_v4(),
+                sale_id,
+                product_id: Uuid::new_v4(),
+                tracked_unit_id: Some(Uuid::new_v4()),
+                quantity: 1,
+                unit_price: Decimal::new(99900, 2),
+                unit_cost: Decimal::new(65000, 2),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_receipt_hides_cost_from_salesman() {
+        let receipt = receipt_for(HandlerRole::SystemSalesman, receipt());
+
+        assert!(receipt.lines.iter().all(|l| l.unit_cost.is_zero()));
+        assert_eq!(receipt.lines[0].unit_price, Decimal::new(99900, 2));
+    }
+
+    #[test]
+    fn test_defective_bulk_return_is_written_off() {
+        let movements = bulk_return_movements(ReturnCondition::Defective, 3);
+
+        assert_eq!(movements.iter().map(|(delta, _)| delta).sum::<i32>(), 0);
+        assert!(movements.contains(&(-3, StockAdjustmentReason::Damaged)));
+    }
+
+    #[test]
+    fn test_resellable_bulk_return_is_restocked() {
+        assert_eq!(
+            bulk_return_movements(ReturnCondition::Resellable, 3),
+            vec![(3, StockAdjustmentReason::Returned)]
+        );
+    }
+
+    #[test]
+    fn test_receipt_keeps_cost_for_manager() {
+        let receipt = receipt_for(HandlerRole::SystemManager, receipt());
+
+        assert_eq!(receipt.lines[0].unit_cost, Decimal::new(65000, 2));
+    }
+}
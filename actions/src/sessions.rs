@@ -0,0 +1,102 @@
+use leptos::prelude::*;
+use models::entities::{ActiveSession, SessionStats};
+use models::errors::SystemError;
+use tracing::instrument;
+
+#[cfg(feature = "ssr")]
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
+
+/// Every device the current handler is signed in on.
+#[instrument(err, skip_all)]
+#[server(ListMySessions)]
+pub async fn list_my_sessions() -> Result<Vec<ActiveSession>, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        let mut conn = session.acquire().await?;
+
+        crate::db_ops::list_handler_sessions(&mut conn, &session.user.id, &session.token_hash).await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Signs one of the current handler's other devices out.
+#[instrument(err, skip_all, fields(session_id = %session_id))]
+#[server(RevokeSession)]
+pub async fn revoke_session(session_id: uuid::Uuid) -> Result<(), SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        let mut tx = session.begin().await?;
+
+        if !crate::db_ops::delete_handler_session(&mut tx, &session.user.id, &session_id).await? {
+            return Err(SystemError::not_found("Session not found."));
+        }
+
+        Audit::new(AuditEntity::Session, AuditAction::Revoked)
+            .entity_id(session_id)
+            .record(&mut tx, &session.user.system_id, Some(&session.user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = session_id;
+        unreachable!()
+    }
+}
+
+/// Signs the current handler out everywhere but here. Returns how many sessions ended.
+#[instrument(err, skip_all)]
+#[server(RevokeOtherSessions)]
+pub async fn revoke_other_sessions() -> Result<u64, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        let mut tx = session.begin().await?;
+
+        let revoked =
+            crate::db_ops::delete_other_sessions(&mut tx, &session.user.id, &session.token_hash)
+                .await?;
+
+        Audit::new(AuditEntity::Session, AuditAction::Revoked)
+            .after(
+                &serde_json::json!({ "handler_id": session.user.id, "sessions_revoked": revoked }),
+            )
+            .record(&mut tx, &session.user.system_id, Some(&session.user.id))
+            .await?;
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(revoked)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all)]
+#[server(GetSessionStats)]
+pub async fn get_session_stats() -> Result<SessionStats, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        let mut conn = session.acquire().await?;
+
+        crate::db_ops::session_stats(&mut conn, &session.user.system_id).await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
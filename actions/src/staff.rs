@@ -0,0 +1,285 @@
+use leptos::prelude::*;
+use models::HandlerRole;
+use models::entities::{IssuedInvite, StaffMember, User};
+use models::errors::SystemError;
+use models::payloads::{AcceptInvitePayload, InviteHandlerPayload};
+use tracing::instrument;
+
+#[cfg(feature = "ssr")]
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
+
+#[instrument(err, skip_all, fields(email = %payload.email, role = ?payload.handler_role))]
+#[server(InviteHandler)]
+pub async fn invite_handler(payload: InviteHandlerPayload) -> Result<IssuedInvite, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::helper::{digest_token, generate_token};
+        use crate::mail::OutgoingMail;
+
+        payload.validate()?;
+
+        let session = require_session().await?;
+        session.require(Permission::ManageStaff)?;
+        let user = &session.user;
+
+        // Emails are unique across every workspace, so this check looks past the tenant scope
+        let mut conn = session
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+        if crate::db_ops::email_is_registered(&mut conn, payload.email.trim()).await? {
+            return Err(SystemError::validation(
+                "This email address is already registered.",
+            ));
+        }
+        drop(conn);
+
+        let mut tx = session.begin().await?;
+        let token = generate_token();
+        let invite = crate::db_ops::insert_invite(
+            &mut tx,
+            &user.system_id,
+            &payload,
+            &digest_token(&token),
+            &user.id,
+        )
+        .await?;
+
+        Audit::new(AuditEntity::Invite, AuditAction::Created)
+            .entity_id(invite.id)
+            .after(&invite)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        let accept_path = format!("/auth/invite/{token}");
+
+        // The inviter also gets the link back, so a mail failure is not fatal
+        let mailer = crate::mail::mailer()?;
+        if let Err(e) = mailer
+            .send(OutgoingMail {
+                to: invite.email.clone(),
+                subject: format!("You've been invited to {}", user.system_name),
+                body: format!(
+                    "{} invited you to join {} on Sharp System as {}.\n\n\
+                     Open this link to choose your password:\n{}",
+                    user.user_name,
+                    user.system_name,
+                    invite.handler_role.label(),
+                    mailer.link(&accept_path)
+                ),
+            })
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to send invite mail");
+        }
+
+        Ok(IssuedInvite {
+            invite,
+            accept_path,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+/// Redeems an invite: creates the handler with the chosen password and signs them in.
+#[instrument(err, skip_all)]
+#[server(AcceptInvite)]
+pub async fn accept_invite(payload: AcceptInvitePayload) -> Result<User, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::auth::hash_password;
+        use crate::helper::{db_pool, digest_token};
+
+        payload.validate()?;
+
+        let pool = db_pool()?;
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        let (system_id, invite) =
+            crate::db_ops::find_open_invite_for_update(&mut tx, &digest_token(&payload.token))
+                .await?
+                .ok_or_else(|| {
+                    SystemError::not_found("This invite link is invalid or has expired.")
+                })?;
+
+        let password_hash = hash_password(payload.password.clone()).await?;
+        crate::db_ops::insert_invited_handler(&mut tx, &system_id, &invite, &password_hash).await?;
+        crate::db_ops::mark_invite_accepted(&mut tx, &invite.id).await?;
+
+        let auth_data = crate::db_ops::get_handler_auth_data(&mut tx, &invite.email)
+            .await?
+            .ok_or_else(|| SystemError::general("The new handler could not be loaded."))?;
+
+        Audit::new(AuditEntity::Handler, AuditAction::Created)
+            .entity_id(auth_data.handler_id)
+            .after(&serde_json::json!({
+                "email": auth_data.email,
+                "user_name": auth_data.user_name,
+                "handler_role": auth_data.handler_role,
+                "invite_id": invite.id,
+            }))
+            .record(&mut tx, &system_id, Some(&auth_data.handler_id))
+            .await?;
+        crate::auth::start_session(tx, &auth_data).await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all)]
+#[server(ListHandlers)]
+pub async fn list_handlers() -> Result<Vec<StaffMember>, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ManageStaff)?;
+        let mut conn = session.acquire().await?;
+
+        crate::db_ops::list_handlers(&mut conn, &session.user.system_id).await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Resolves a colleague in the caller's workspace, refusing to act on the caller themselves.
+#[cfg(feature = "ssr")]
+async fn require_other_handler(
+    conn: &mut sqlx::PgConnection,
+    handler_id: &uuid::Uuid,
+    caller: &User,
+) -> Result<StaffMember, SystemError> {
+    if handler_id == &caller.id {
+        return Err(SystemError::validation(
+            "You cannot change your own role or access.",
+        ));
+    }
+
+    crate::db_ops::get_staff_member(conn, handler_id, &caller.system_id)
+        .await?
+        .ok_or_else(|| SystemError::not_found("Handler not found in this workspace."))
+}
+
+#[instrument(err, skip_all, fields(handler_id = %handler_id, role = ?handler_role))]
+#[server(ChangeHandlerRole)]
+pub async fn change_handler_role(
+    handler_id: uuid::Uuid,
+    handler_role: HandlerRole,
+) -> Result<StaffMember, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ManageStaff)?;
+        let mut tx = session.begin().await?;
+
+        let mut member = require_other_handler(&mut tx, &handler_id, &session.user).await?;
+        crate::db_ops::update_handler_role(&mut tx, &member.id, handler_role).await?;
+
+        Audit::new(AuditEntity::Handler, AuditAction::RoleChanged)
+            .entity_id(member.id)
+            .before(&serde_json::json!({ "handler_role": member.handler_role }))
+            .after(&serde_json::json!({ "handler_role": handler_role }))
+            .record(&mut tx, &session.user.system_id, Some(&session.user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        member.handler_role = handler_role;
+        Ok(member)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (handler_id, handler_role);
+        unreachable!()
+    }
+}
+
+/// Blocks a handler from signing in and ends every session they currently hold.
+#[instrument(err, skip_all, fields(handler_id = %handler_id))]
+#[server(DeactivateHandler)]
+pub async fn deactivate_handler(handler_id: uuid::Uuid) -> Result<StaffMember, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ManageStaff)?;
+        let mut tx = session.begin().await?;
+
+        let mut member = require_other_handler(&mut tx, &handler_id, &session.user).await?;
+        crate::db_ops::deactivate_handler(&mut tx, &member.id).await?;
+        let revoked = crate::db_ops::revoke_handler_sessions(&mut tx, &member.id).await?;
+
+        Audit::new(AuditEntity::Handler, AuditAction::Deactivated)
+            .entity_id(member.id)
+            .before(&serde_json::json!({ "is_active": member.is_active }))
+            .after(&serde_json::json!({ "is_active": false, "sessions_revoked": revoked }))
+            .record(&mut tx, &session.user.system_id, Some(&session.user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(revoked, "Handler deactivated");
+        member.is_active = false;
+        Ok(member)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = handler_id;
+        unreachable!()
+    }
+}
+
+/// Signs another handler out of every device without deactivating them.
+#[instrument(err, skip_all, fields(handler_id = %handler_id))]
+#[server(RevokeHandlerSessions)]
+pub async fn revoke_handler_sessions(handler_id: uuid::Uuid) -> Result<u64, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ManageStaff)?;
+        let mut tx = session.begin().await?;
+
+        let member = require_other_handler(&mut tx, &handler_id, &session.user).await?;
+        let revoked = crate::db_ops::revoke_handler_sessions(&mut tx, &member.id).await?;
+
+        Audit::new(AuditEntity::Session, AuditAction::Revoked)
+            .after(&serde_json::json!({ "handler_id": member.id, "sessions_revoked": revoked }))
+            .record(&mut tx, &session.user.system_id, Some(&session.user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(revoked, "Handler sessions revoked by admin");
+        Ok(revoked)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = handler_id;
+        unreachable!()
+    }
+}
@@ -0,0 +1,167 @@
+use leptos::prelude::*;
+use models::entities::{Supplier, SupplierReport};
+use models::errors::SystemError;
+use models::payloads::SupplierPayload;
+use tracing::instrument;
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+use crate::helper::{Audit, require_session};
+#[cfg(feature = "ssr")]
+use crate::permissions::Permission;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
+#[cfg(feature = "ssr")]
+use validator::Validate;
+
+#[instrument(err, skip_all)]
+#[server(AddSupplier)]
+pub async fn add_supplier(payload: SupplierPayload) -> Result<Supplier, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let payload = payload.normalized();
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let supplier =
+            crate::db_ops::insert_supplier(&mut tx, &user.system_id, &payload, &user.id).await?;
+
+        Audit::new(AuditEntity::Supplier, AuditAction::Created)
+            .entity_id(supplier.id)
+            .after(&supplier)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(supplier)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = payload;
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all, fields(%supplier_id))]
+#[server(UpdateSupplier)]
+pub async fn update_supplier(
+    supplier_id: Uuid,
+    payload: SupplierPayload,
+) -> Result<Supplier, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let payload = payload.normalized();
+        payload
+            .validate()
+            .map_err(|e| SystemError::validation(e.to_string()))?;
+
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let current = crate::db_ops::get_supplier(&mut tx, &supplier_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Supplier not found."))?;
+        let supplier =
+            crate::db_ops::update_supplier(&mut tx, &supplier_id, &user.system_id, &payload)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Supplier not found."))?;
+
+        Audit::new(AuditEntity::Supplier, AuditAction::Updated)
+            .entity_id(supplier.id)
+            .before(&current)
+            .after(&supplier)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(supplier)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (supplier_id, payload);
+        unreachable!()
+    }
+}
+
+/// Removes a supplier nothing was ever ordered or received from; others keep their history.
+#[instrument(err, skip_all, fields(%supplier_id))]
+#[server(DeleteSupplier)]
+pub async fn delete_supplier(supplier_id: Uuid) -> Result<(), SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let supplier = crate::db_ops::delete_supplier(&mut tx, &supplier_id, &user.system_id)
+            .await?
+            .ok_or_else(|| SystemError::not_found("Supplier not found."))?;
+
+        Audit::new(AuditEntity::Supplier, AuditAction::Deleted)
+            .entity_id(supplier.id)
+            .before(&supplier)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = supplier_id;
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all)]
+#[server(GetSuppliers)]
+pub async fn get_suppliers() -> Result<Vec<Supplier>, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ReceiveStock)?;
+        let mut conn = session.acquire().await?;
+
+        crate::db_ops::list_suppliers(&mut conn, &session.user.system_id).await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Units purchased, their cost and the return/RMA record of every supplier.
+#[instrument(err, skip_all)]
+#[server(GetSupplierReports)]
+pub async fn get_supplier_reports() -> Result<Vec<SupplierReport>, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let session = require_session().await?;
+        session.require(Permission::ViewCosts)?;
+        let mut conn = session.acquire().await?;
+
+        crate::db_ops::list_supplier_reports(&mut conn, &session.user.system_id).await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
@@ -0,0 +1,43 @@
+//! Tenant-scoped database access.
+//!
+//! Row-level security policies restrict every tenant table to the System named by the
+//! `app.current_system_id` setting, but only for the `sharp_tenant` role. Work done for an
+//! authenticated caller goes through [`begin_tenant_transaction`], which switches to that
+//! role and sets the System for the lifetime of the transaction, so a query that forgets its
+//! `system_id` filter still cannot see another tenant's rows.
+//!
+//! Sign-in, invite acceptance and background jobs run before a tenant is known and use the
+//! pool directly as the owning role, which the policies do not apply to.
+
+use models::errors::SystemError;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Role the policies apply to; created by the row-level security migration.
+pub const TENANT_ROLE: &str = "sharp_tenant";
+
+/// Opens a transaction that can only see and write rows belonging to `system_id`.
+///
+/// Both settings are transaction-local and are reset on commit or rollback, so the
+/// connection goes back to the pool unscoped.
+pub async fn begin_tenant_transaction(
+    pool: &PgPool,
+    system_id: &Uuid,
+) -> Result<Transaction<'static, Postgres>, SystemError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    sqlx::query(&format!("SET LOCAL ROLE {TENANT_ROLE}"))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+    sqlx::query("SELECT set_config('app.current_system_id', $1, true)")
+        .bind(system_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))?;
+
+    Ok(tx)
+}
@@ -0,0 +1,421 @@
+use leptos::prelude::*;
+use models::entities::{TotpActivation, TotpEnrolment, TwoFactorStatus, User};
+use models::errors::SystemError;
+use tracing::instrument;
+
+#[cfg(feature = "ssr")]
+use crate::db_ops::handler::HandlerAuthData;
+#[cfg(feature = "ssr")]
+use crate::db_ops::{LoginChallenge, LoginChallengeKind};
+#[cfg(feature = "ssr")]
+use crate::helper::Audit;
+#[cfg(feature = "ssr")]
+use models::entities::{AuditAction, AuditEntity};
+#[cfg(feature = "ssr")]
+use sqlx::{PgConnection, Postgres, Transaction};
+
+/// Number of recovery codes issued whenever 2FA is switched on.
+#[cfg(feature = "ssr")]
+const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Wrong codes a single login challenge tolerates before it is thrown away.
+#[cfg(feature = "ssr")]
+const MAX_CHALLENGE_FAILURES: i32 = 5;
+
+#[cfg(feature = "ssr")]
+fn expired_challenge() -> SystemError {
+    SystemError::unauthorized("This sign-in has expired. Please sign in again.")
+}
+
+#[cfg(feature = "ssr")]
+fn invalid_code() -> SystemError {
+    SystemError::validation("That code is not valid.")
+}
+
+/// Accepts a current TOTP code (at most once per time step) or an unused recovery code.
+#[cfg(feature = "ssr")]
+async fn check_second_factor(
+    conn: &mut PgConnection,
+    handler_id: &uuid::Uuid,
+    secret: &str,
+    code: &str,
+) -> Result<bool, SystemError> {
+    use crate::db_ops::{claim_totp_step, consume_recovery_code};
+    use crate::helper::{digest_token, normalize_recovery_code};
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| SystemError::general(e.to_string()))?
+        .as_secs();
+
+    if let Some(step) = crate::totp::verify_code(secret, code, now) {
+        return claim_totp_step(conn, handler_id, step as i64).await;
+    }
+
+    let normalized = normalize_recovery_code(code);
+    if normalized.is_empty() {
+        return Ok(false);
+    }
+    consume_recovery_code(conn, handler_id, &digest_token(&normalized)).await
+}
+
+/// Counts a wrong code against `challenge`, discarding it once it has had too many.
+/// It also counts as a failed sign-in, so the account backoff covers the second factor.
+#[cfg(feature = "ssr")]
+async fn fail_challenge(
+    mut tx: Transaction<'static, Postgres>,
+    challenge: &LoginChallenge,
+    data: &HandlerAuthData,
+    ip_address: Option<&str>,
+) -> Result<(), SystemError> {
+    use crate::db_ops::auth::record_login_attempt;
+    use crate::db_ops::{delete_login_challenge, record_challenge_failure};
+
+    record_login_attempt(&mut tx, &data.email, ip_address, false).await?;
+    let failures = record_challenge_failure(&mut tx, &challenge.id).await?;
+    if failures >= MAX_CHALLENGE_FAILURES {
+        delete_login_challenge(&mut tx, &challenge.id).await?;
+        tracing::warn!(handler_id = %challenge.handler_id, "Login challenge discarded after repeated failures");
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| SystemError::database(e.to_string()))
+}
+
+/// Locks the challenge behind `token` if it is still live and of the expected kind.
+#[cfg(feature = "ssr")]
+async fn open_challenge(
+    tx: &mut PgConnection,
+    token: &str,
+    kind: LoginChallengeKind,
+) -> Result<(LoginChallenge, HandlerAuthData), SystemError> {
+    use crate::db_ops::{find_login_challenge_for_update, get_handler_auth_data_by_id};
+    use crate::helper::digest_token;
+
+    let challenge = find_login_challenge_for_update(tx, &digest_token(token))
+        .await?
+        .filter(|c| c.kind == kind)
+        .ok_or_else(expired_challenge)?;
+
+    let data = get_handler_auth_data_by_id(tx, &challenge.handler_id)
+        .await?
+        .ok_or_else(expired_challenge)?;
+
+    Ok((challenge, data))
+}
+
+/// Whoever is enrolling: a signed-in handler, or one stopped at sign-in by the workspace policy.
+#[cfg(feature = "ssr")]
+async fn open_enrolment(
+    challenge: Option<&str>,
+) -> Result<
+    (
+        Transaction<'static, Postgres>,
+        HandlerAuthData,
+        Option<LoginChallenge>,
+    ),
+    SystemError,
+> {
+    match challenge {
+        Some(token) => {
+            let mut tx = crate::helper::db_pool()?
+                .begin()
+                .await
+                .map_err(|e| SystemError::database(e.to_string()))?;
+            let (challenge, data) =
+                open_challenge(&mut tx, token, LoginChallengeKind::Enrol).await?;
+            Ok((tx, data, Some(challenge)))
+        }
+        None => {
+            let session = crate::helper::require_session().await?;
+            let mut tx = session.begin().await?;
+            let data = crate::db_ops::get_handler_auth_data_by_id(&mut tx, &session.user.id)
+                .await?
+                .ok_or_else(|| SystemError::not_found("Handler not found."))?;
+            Ok((tx, data, None))
+        }
+    }
+}
+
+/// Second step of signing in for handlers with 2FA enabled.
+#[instrument(err, skip_all)]
+#[server(VerifyTwoFactor)]
+pub async fn verify_two_factor(challenge: String, code: String) -> Result<User, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::auth::record_login_attempt;
+        use crate::db_ops::{delete_login_challenge, get_two_factor_state};
+
+        let ip_address = crate::helper::client_ip().await;
+        let mut tx = crate::helper::db_pool()?
+            .begin()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        let (challenge, data) =
+            open_challenge(&mut tx, &challenge, LoginChallengeKind::Verify).await?;
+        crate::auth::enforce_login_throttle(&mut tx, &data.email, ip_address.as_deref()).await?;
+
+        let state = get_two_factor_state(&mut tx, &data.handler_id).await?;
+        let Some(secret) = state.totp_secret else {
+            return Err(expired_challenge());
+        };
+
+        if !check_second_factor(&mut tx, &data.handler_id, &secret, &code).await? {
+            fail_challenge(tx, &challenge, &data, ip_address.as_deref()).await?;
+            tracing::warn!(handler_id = %data.handler_id, "Invalid two-factor code");
+            return Err(invalid_code());
+        }
+
+        delete_login_challenge(&mut tx, &challenge.id).await?;
+        record_login_attempt(&mut tx, &data.email, ip_address.as_deref(), true).await?;
+
+        crate::auth::start_session(tx, &data).await
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (challenge, code);
+        unreachable!()
+    }
+}
+
+/// Generates a fresh secret to show the handler. Nothing changes until it is confirmed.
+#[instrument(err, skip_all)]
+#[server(BeginTotpEnrolment)]
+pub async fn begin_totp_enrolment(challenge: Option<String>) -> Result<TotpEnrolment, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::{get_two_factor_state, set_pending_totp_secret};
+        use crate::totp::{generate_secret, otpauth_uri};
+
+        let (mut tx, data, _) = open_enrolment(challenge.as_deref()).await?;
+
+        let state = get_two_factor_state(&mut tx, &data.handler_id).await?;
+        if state.totp_secret.is_some() {
+            return Err(SystemError::validation(
+                "Two-factor authentication is already enabled.",
+            ));
+        }
+
+        let secret = generate_secret();
+        set_pending_totp_secret(&mut tx, &data.handler_id, &secret).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        let issuer = format!("Sharp System ({})", data.system_name);
+        Ok(TotpEnrolment {
+            otpauth_uri: otpauth_uri(&issuer, &data.email, &secret),
+            secret,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = challenge;
+        unreachable!()
+    }
+}
+
+/// Switches 2FA on once the handler proves their app produces matching codes.
+/// When enrolment was forced at sign-in, this also completes that sign-in.
+#[instrument(err, skip_all)]
+#[server(ConfirmTotpEnrolment)]
+pub async fn confirm_totp_enrolment(
+    code: String,
+    challenge: Option<String>,
+) -> Result<TotpActivation, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::auth::record_login_attempt;
+        use crate::db_ops::{
+            activate_totp, delete_login_challenge, get_two_factor_state, replace_recovery_codes,
+        };
+        use crate::helper::{digest_token, generate_recovery_code, normalize_recovery_code};
+
+        let ip_address = crate::helper::client_ip().await;
+        let (mut tx, data, challenge) = open_enrolment(challenge.as_deref()).await?;
+        // Enrolling at sign-in is still part of that sign-in, and backs off like it
+        if challenge.is_some() {
+            crate::auth::enforce_login_throttle(&mut tx, &data.email, ip_address.as_deref())
+                .await?;
+        }
+
+        let state = get_two_factor_state(&mut tx, &data.handler_id).await?;
+        let Some(pending) = state.totp_pending_secret else {
+            return Err(SystemError::validation(
+                "Start two-factor setup before confirming a code.",
+            ));
+        };
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| SystemError::general(e.to_string()))?
+            .as_secs();
+
+        let Some(step) = crate::totp::verify_code(&pending, &code, now) else {
+            if let Some(challenge) = &challenge {
+                fail_challenge(tx, challenge, &data, ip_address.as_deref()).await?;
+            }
+            return Err(invalid_code());
+        };
+
+        activate_totp(&mut tx, &data.handler_id, step as i64).await?;
+
+        let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| generate_recovery_code())
+            .collect();
+        let hashes: Vec<String> = recovery_codes
+            .iter()
+            .map(|c| digest_token(&normalize_recovery_code(c)))
+            .collect();
+        replace_recovery_codes(&mut tx, &data.handler_id, &hashes).await?;
+
+        Audit::new(AuditEntity::Handler, AuditAction::TwoFactorEnabled)
+            .entity_id(data.handler_id)
+            .record(&mut tx, &data.system_id, Some(&data.handler_id))
+            .await?;
+
+        tracing::info!(handler_id = %data.handler_id, "Two-factor authentication enabled");
+
+        let user = match challenge {
+            Some(challenge) => {
+                delete_login_challenge(&mut tx, &challenge.id).await?;
+                record_login_attempt(&mut tx, &data.email, ip_address.as_deref(), true).await?;
+                Some(crate::auth::start_session(tx, &data).await?)
+            }
+            None => {
+                tx.commit()
+                    .await
+                    .map_err(|e| SystemError::database(e.to_string()))?;
+                None
+            }
+        };
+
+        Ok(TotpActivation {
+            recovery_codes,
+            user,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (code, challenge);
+        unreachable!()
+    }
+}
+
+/// Turns 2FA off for the current handler, unless the workspace policy requires it.
+#[instrument(err, skip_all)]
+#[server(DisableTotp)]
+pub async fn disable_totp(code: String) -> Result<TwoFactorStatus, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::get_two_factor_state;
+
+        let session = crate::helper::require_session().await?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let state = get_two_factor_state(&mut tx, &user.id).await?;
+        if state.workspace_requires && user.handler_role.is_privileged() {
+            return Err(SystemError::validation(
+                "Your workspace requires two-factor authentication for your role.",
+            ));
+        }
+        let Some(secret) = state.totp_secret else {
+            return Err(SystemError::validation(
+                "Two-factor authentication is not enabled.",
+            ));
+        };
+
+        if !check_second_factor(&mut tx, &user.id, &secret, &code).await? {
+            return Err(invalid_code());
+        }
+
+        crate::db_ops::disable_totp(&mut tx, &user.id).await?;
+
+        Audit::new(AuditEntity::Handler, AuditAction::TwoFactorDisabled)
+            .entity_id(user.id)
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        tracing::info!(handler_id = %user.id, "Two-factor authentication disabled");
+
+        Ok(TwoFactorStatus {
+            enabled: false,
+            workspace_requires: state.workspace_requires,
+            recovery_codes_remaining: 0,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = code;
+        unreachable!()
+    }
+}
+
+#[instrument(err, skip_all)]
+#[server(GetTwoFactorStatus)]
+pub async fn get_two_factor_status() -> Result<TwoFactorStatus, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::db_ops::{count_recovery_codes, get_two_factor_state};
+
+        let session = crate::helper::require_session().await?;
+        let mut conn = session.acquire().await?;
+
+        let state = get_two_factor_state(&mut conn, &session.user.id).await?;
+        let recovery_codes_remaining = count_recovery_codes(&mut conn, &session.user.id).await?;
+
+        Ok(TwoFactorStatus {
+            enabled: state.totp_secret.is_some(),
+            workspace_requires: state.workspace_requires,
+            recovery_codes_remaining,
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Requires admins and managers to use 2FA. Those without it are asked to enrol at next sign-in.
+#[instrument(err, skip_all, fields(required))]
+#[server(SetWorkspaceTwoFactorRequirement)]
+pub async fn set_workspace_two_factor_requirement(required: bool) -> Result<bool, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::permissions::Permission;
+
+        let session = crate::helper::require_session().await?;
+        session.require(Permission::ManageWorkspace)?;
+        let user = &session.user;
+        let mut tx = session.begin().await?;
+
+        let state = crate::db_ops::get_two_factor_state(&mut tx, &user.id).await?;
+        crate::db_ops::set_workspace_two_factor(&mut tx, &user.system_id, required).await?;
+
+        Audit::new(AuditEntity::Workspace, AuditAction::Updated)
+            .entity_id(user.system_id)
+            .before(&serde_json::json!({ "require_two_factor": state.workspace_requires }))
+            .after(&serde_json::json!({ "require_two_factor": required }))
+            .record(&mut tx, &user.system_id, Some(&user.id))
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| SystemError::database(e.to_string()))?;
+
+        Ok(required)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = required;
+        unreachable!()
+    }
+}
@@ -0,0 +1,191 @@
+//! Workspaces are served from `<handle>.<base_domain>`. A middleware resolves the request's
+//! `Host` to a [`Workspace`] before Leptos sees it and turns away sessions from other Systems.
+
+use leptos::prelude::*;
+use models::entities::WorkspaceContext;
+use models::errors::SystemError;
+use tracing::instrument;
+
+#[cfg(feature = "ssr")]
+use models::entities::Workspace;
+
+/// The workspace handle in `host` (which may carry a port), if it is a direct
+/// subdomain of `base_domain`.
+pub fn workspace_handle_from_host(host: &str, base_domain: &str) -> Option<String> {
+    let host = host.trim().to_lowercase();
+    let host = host.rsplit_once(':').map_or(host.as_str(), |(h, _)| h);
+    let handle = host.strip_suffix(base_domain)?.strip_suffix('.')?;
+
+    if handle.is_empty() || handle.contains('.') || handle == "www" {
+        return None;
+    }
+    Some(handle.to_string())
+}
+
+/// `base_url` with `handle` prepended to its host, e.g. `https://acme.sharp.example`.
+pub fn workspace_url(base_url: &str, handle: &str) -> String {
+    match base_url.split_once("://") {
+        Some((scheme, rest)) => format!("{scheme}://{handle}.{rest}"),
+        None => format!("{handle}.{base_url}"),
+    }
+}
+
+/// The workspace resolved for this request, as provided to context by `main`.
+#[cfg(feature = "ssr")]
+pub fn current_workspace() -> Option<Workspace> {
+    use_context::<Workspace>()
+}
+
+#[instrument(err, skip_all)]
+#[server(GetWorkspaceContext)]
+pub async fn get_workspace_context() -> Result<WorkspaceContext, SystemError> {
+    #[cfg(feature = "ssr")]
+    {
+        let config = crate::config::app_config()?;
+
+        Ok(WorkspaceContext {
+            workspace: current_workspace(),
+            base_url: config.base_url.clone(),
+            base_domain: config.base_domain.clone(),
+        })
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        unreachable!()
+    }
+}
+
+/// What [`resolve_workspace`] needs from the server.
+#[cfg(feature = "ssr")]
+#[derive(Clone)]
+pub struct WorkspaceRouting {
+    pub pool: sqlx::PgPool,
+    pub config: std::sync::Arc<crate::config::AppConfig>,
+}
+
+/// Axum middleware: stores the host's [`Workspace`] in the request extensions.
+///
+/// Unknown workspaces go back to the base domain, and a session belonging to another
+/// System is sent to its own workspace instead of being served here.
+#[cfg(feature = "ssr")]
+pub async fn resolve_workspace(
+    axum::extract::State(routing): axum::extract::State<WorkspaceRouting>,
+    mut req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::http::{Method, StatusCode, header};
+    use axum::response::{IntoResponse, Redirect};
+    use axum_extra::extract::cookie::CookieJar;
+
+    let Some(base_domain) = routing.config.base_domain.as_deref() else {
+        return next.run(req).await;
+    };
+    // Static assets are the same for every workspace
+    if req.uri().path().starts_with("/pkg/") {
+        return next.run(req).await;
+    }
+    let Some(handle) = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|host| workspace_handle_from_host(host, base_domain))
+    else {
+        return next.run(req).await;
+    };
+
+    // Pages get a redirect; server functions and other calls a plain status
+    let is_page = req.method() == Method::GET;
+    let turn_away = |location: String, status: StatusCode| {
+        if is_page {
+            Redirect::to(&location).into_response()
+        } else {
+            status.into_response()
+        }
+    };
+
+    let mut conn = match routing.pool.acquire().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            tracing::error!(error = %e, "Workspace resolution could not reach the database");
+            return StatusCode::SERVICE_UNAVAILABLE.into_response();
+        }
+    };
+
+    let workspace = match crate::db_ops::find_workspace_by_handle(&mut conn, &handle).await {
+        Ok(Some(workspace)) => workspace,
+        Ok(None) => {
+            tracing::debug!(handle, "Request for unknown workspace");
+            return turn_away(routing.config.base_url.clone(), StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Workspace lookup failed");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let token = CookieJar::from_headers(req.headers())
+        .get("session_token")
+        .map(|c| c.value().to_string());
+    if let Some(token) = token {
+        let token_hash = crate::helper::digest_token(&token);
+        match crate::db_ops::get_session_workspace(&mut conn, &token_hash).await {
+            Ok(Some((system_id, session_handle))) if system_id != workspace.id => {
+                tracing::warn!(
+                    requested = %workspace.handle,
+                    session = %session_handle,
+                    "Session used on another workspace's subdomain"
+                );
+                let path = req
+                    .uri()
+                    .path_and_query()
+                    .map_or("/", |p| p.as_str())
+                    .to_string();
+                let own = workspace_url(&routing.config.base_url, &session_handle);
+                return turn_away(format!("{own}{path}"), StatusCode::FORBIDDEN);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(error = %e, "Session workspace lookup failed");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        }
+    }
+    drop(conn);
+
+    req.extensions_mut().insert(workspace);
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_handle_from_host() {
+        let handle = |host| workspace_handle_from_host(host, "sharp.example");
+
+        assert_eq!(handle("acme.sharp.example"), Some("acme".to_string()));
+        assert_eq!(handle("ACME.Sharp.Example:443"), Some("acme".to_string()));
+        assert_eq!(handle("sharp.example"), None);
+        assert_eq!(handle("www.sharp.example"), None);
+        assert_eq!(handle("a.b.sharp.example"), None);
+        assert_eq!(handle("acmesharp.example"), None);
+        assert_eq!(handle("acme.other.example"), None);
+        assert_eq!(
+            workspace_handle_from_host("acme.localhost:3000", "localhost"),
+            Some("acme".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_url() {
+        assert_eq!(
+            workspace_url("http://localhost:3000", "acme"),
+            "http://acme.localhost:3000"
+        );
+        assert_eq!(
+            workspace_url("https://sharp.example", "acme"),
+            "https://acme.sharp.example"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Row-level security must keep each System's data out of every other System's requests.
+//!
+//! Needs a migrated database in `DATABASE_URL` and the `ssr` feature:
+//! `cargo test -p actions --features ssr --test tenant_isolation`.
+#![cfg(feature = "ssr")]
+
+use actions::tenant::begin_tenant_transaction;
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+use uuid::Uuid;
+
+/// A System with one handler, one tracked and one untracked product.
+struct Tenant {
+    system_id: Uuid,
+    handler_id: Uuid,
+    product_id: Uuid,
+}
+
+async fn connect() -> Option<PgPool> {
+    let Ok(url) = std::env::var("DATABASE_URL") else {
+        eprintln!("DATABASE_URL is not set; skipping tenant isolation tests");
+        return None;
+    };
+    // A single connection, so the scope test below sees the connection it just released
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&url)
+        .await
+        .expect("connect to DATABASE_URL");
+    Some(pool)
+}
+
+/// Seeds a tenant as the owning role, the way sign-up does.
+async fn seed_tenant(pool: &PgPool) -> Tenant {
+    let tag = Uuid::new_v4().simple().to_string();
+    let mut tx = pool.begin().await.unwrap();
+
+    let system_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO systems (system_name, system_handle) VALUES ($1, $2) RETURNING id",
+    )
+    .bind(format!("RLS test {tag}"))
+    .bind(format!("rls-{tag}"))
+    .fetch_one(&mut *tx)
+    .await
+    .unwrap();
+
+    let handler_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO handlers (system_id, email, password_hash, user_name, handler_role)
+         VALUES ($1, $2, 'x', 'RLS Tester', 'system_admin') RETURNING id",
+    )
+    .bind(system_id)
+    .bind(format!("rls-{tag}@example.com"))
+    .fetch_one(&mut *tx)
+    .await
+    .unwrap();
+
+    let product_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO products (system_id, name, is_tracked, added_by)
+         VALUES ($1, 'Tracked', true, $2) RETURNING id",
+    )
+    .bind(system_id)
+    .bind(handler_id)
+    .fetch_one(&mut *tx)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO tracked_units (product_id, serial_numbers, added_by)
+         VALUES ($1, ARRAY[$2], $3)",
+    )
+    .bind(product_id)
+    .bind(format!("SN-{tag}"))
+    .bind(handler_id)
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+
+    let untracked_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO products (system_id, name, is_tracked, added_by)
+         VALUES ($1, 'Untracked', false, $2) RETURNING id",
+    )
+    .bind(system_id)
+    .bind(handler_id)
+    .fetch_one(&mut *tx)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO untracked_inventory (product_id, quantity) VALUES ($1, 5)")
+        .bind(untracked_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    tx.commit().await.unwrap();
+
+    Tenant {
+        system_id,
+        handler_id,
+        product_id,
+    }
+}
+
+async fn remove_tenant(pool: &PgPool, tenant: Tenant) {
+    sqlx::query("DELETE FROM products WHERE system_id = $1")
+        .bind(tenant.system_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM systems WHERE id = $1")
+        .bind(tenant.system_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Rows counted by `query` for a request scoped to `viewer`, with `owner`'s System bound to `$1`.
+async fn visible_rows(pool: &PgPool, viewer: &Tenant, owner: &Tenant, query: &str) -> i64 {
+    let mut tx = begin_tenant_transaction(pool, &viewer.system_id)
+        .await
+        .unwrap();
+    sqlx::query_scalar(query)
+        .bind(owner.system_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_cross_tenant_reads_return_nothing() {
+    let Some(pool) = connect().await else {
+        return;
+    };
+    let a = seed_tenant(&pool).await;
+    let b = seed_tenant(&pool).await;
+
+    // Each query deliberately filters on the owner's System, as a buggy action might
+    let queries = [
+        "SELECT COUNT(*) FROM systems WHERE id = $1",
+        "SELECT COUNT(*) FROM handlers WHERE system_id = $1",
+        "SELECT COUNT(*) FROM products WHERE system_id = $1",
+        "SELECT COUNT(*) FROM tracked_units t
+         JOIN products p ON p.id = t.product_id WHERE p.system_id = $1",
+        "SELECT COUNT(*) FROM untracked_inventory u
+         JOIN products p ON p.id = u.product_id WHERE p.system_id = $1",
+    ];
+    for query in queries {
+        assert!(visible_rows(&pool, &a, &a, query).await > 0, "{query}");
+        assert_eq!(visible_rows(&pool, &a, &b, query).await, 0, "{query}");
+    }
+
+    // Without any filter a tenant still sees only its own rows
+    let mut tx = begin_tenant_transaction(&pool, &a.system_id).await.unwrap();
+    let systems: Vec<Uuid> = sqlx::query_scalar("SELECT DISTINCT system_id FROM products")
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(systems, vec![a.system_id]);
+    let units: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tracked_units")
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(units, 1);
+    drop(tx);
+
+    remove_tenant(&pool, a).await;
+    remove_tenant(&pool, b).await;
+}
+
+#[tokio::test]
+async fn test_cross_tenant_writes_are_rejected() {
+    let Some(pool) = connect().await else {
+        return;
+    };
+    let a = seed_tenant(&pool).await;
+    let b = seed_tenant(&pool).await;
+
+    let mut tx = begin_tenant_transaction(&pool, &a.system_id).await.unwrap();
+    let renamed = sqlx::query("UPDATE products SET name = 'Hijacked' WHERE id = $1")
+        .bind(b.product_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(renamed.rows_affected(), 0);
+    let deleted = sqlx::query("DELETE FROM handlers WHERE id = $1")
+        .bind(b.handler_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(deleted.rows_affected(), 0);
+    tx.commit().await.unwrap();
+
+    let mut tx = begin_tenant_transaction(&pool, &a.system_id).await.unwrap();
+    let planted = sqlx::query(
+        "INSERT INTO products (system_id, name, is_tracked, added_by)
+         VALUES ($1, 'Planted', false, $2)",
+    )
+    .bind(b.system_id)
+    .bind(a.handler_id)
+    .execute(&mut *tx)
+    .await;
+    assert!(planted.is_err(), "insert into another System must fail");
+    drop(tx);
+
+    let mut tx = begin_tenant_transaction(&pool, &a.system_id).await.unwrap();
+    let planted = sqlx::query(
+        "INSERT INTO tracked_units (product_id, serial_numbers, added_by)
+         VALUES ($1, ARRAY['PLANTED'], $2)",
+    )
+    .bind(b.product_id)
+    .bind(a.handler_id)
+    .execute(&mut *tx)
+    .await;
+    assert!(
+        planted.is_err(),
+        "unit under another System's product must fail"
+    );
+    drop(tx);
+
+    remove_tenant(&pool, a).await;
+    remove_tenant(&pool, b).await;
+}
+
+#[tokio::test]
+async fn test_tenant_scope_ends_with_the_transaction() {
+    let Some(pool) = connect().await else {
+        return;
+    };
+
+    let mut tx = begin_tenant_transaction(&pool, &Uuid::new_v4())
+        .await
+        .unwrap();
+    let role: String = sqlx::query_scalar("SELECT current_user::text")
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(role, actions::tenant::TENANT_ROLE);
+    tx.commit().await.unwrap();
+
+    let mut conn = pool.acquire().await.unwrap();
+    let role: String = sqlx::query_scalar("SELECT current_user::text")
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap();
+    assert_ne!(role, actions::tenant::TENANT_ROLE);
+    let setting: Option<String> =
+        sqlx::query_scalar("SELECT NULLIF(current_setting('app.current_system_id', true), '')")
+            .fetch_one(&mut *conn)
+            .await
+            .unwrap();
+    assert_eq!(setting, None);
+}
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS stock_adjustments;
+DROP TYPE IF EXISTS stock_adjustment_reason;
@@ -0,0 +1,23 @@
+-- Reason codes for every movement of bulk (untracked) stock
+CREATE TYPE stock_adjustment_reason AS ENUM (
+    'received',
+    'damaged',
+    'lost',
+    'theft',
+    'count_correction',
+    'other'
+);
+-- Ledger of bulk stock movements (one row per receipt/adjustment)
+CREATE TABLE stock_adjustments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    quantity_delta INTEGER NOT NULL CHECK (quantity_delta <> 0),
+    reason stock_adjustment_reason NOT NULL,
+    unit_cost NUMERIC(12, 2),
+    note TEXT,
+    adjusted_by UUID NOT NULL REFERENCES handlers(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_stock_adjustments_system_id ON stock_adjustments(system_id);
+CREATE INDEX idx_stock_adjustments_product_id ON stock_adjustments(product_id);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS sale_lines;
+DROP TABLE IF EXISTS sales;
+ALTER TABLE systems DROP COLUMN IF EXISTS last_invoice_number;
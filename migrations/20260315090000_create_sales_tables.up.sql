@@ -0,0 +1,31 @@
+-- Per-workspace invoice sequence (bumped under row lock by each completed sale)
+ALTER TABLE systems
+ADD COLUMN last_invoice_number INTEGER NOT NULL DEFAULT 0;
+-- 1. Sales (Invoice headers)
+CREATE TABLE sales (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    invoice_number INTEGER NOT NULL,
+    total_amount NUMERIC(12, 2) NOT NULL DEFAULT 0.00,
+    sold_by UUID NOT NULL REFERENCES handlers(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT sales_system_invoice_key UNIQUE (system_id, invoice_number)
+);
+CREATE INDEX idx_sales_system_id ON sales(system_id);
+-- 2. Sale Lines (One row per tracked unit or bulk quantity sold)
+CREATE TABLE sale_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    sale_id UUID NOT NULL REFERENCES sales(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id),
+    tracked_unit_id UUID REFERENCES tracked_units(id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_price NUMERIC(12, 2) NOT NULL,
+    unit_cost NUMERIC(12, 2) NOT NULL,
+    CONSTRAINT sale_lines_tracked_quantity_check CHECK (
+        tracked_unit_id IS NULL
+        OR quantity = 1
+    )
+);
+CREATE INDEX idx_sale_lines_sale_id ON sale_lines(sale_id);
+CREATE INDEX idx_sale_lines_product_id ON sale_lines(product_id);
+CREATE INDEX idx_sale_lines_tracked_unit_id ON sale_lines(tracked_unit_id);
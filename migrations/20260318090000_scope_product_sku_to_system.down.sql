@@ -0,0 +1,4 @@
+ALTER TABLE products DROP CONSTRAINT IF EXISTS products_system_sku_key;
+ALTER TABLE products
+ADD CONSTRAINT products_sku_key UNIQUE (sku);
+CREATE INDEX IF NOT EXISTS idx_products_sku ON products(sku);
@@ -0,0 +1,5 @@
+-- SKUs only need to be unique inside a System, not across every tenant
+ALTER TABLE products DROP CONSTRAINT IF EXISTS products_sku_key;
+DROP INDEX IF EXISTS idx_products_sku;
+ALTER TABLE products
+ADD CONSTRAINT products_system_sku_key UNIQUE (system_id, sku);
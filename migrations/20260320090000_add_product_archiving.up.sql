@@ -0,0 +1,5 @@
+-- Products referenced by sales or stock history are archived instead of deleted
+ALTER TABLE products
+ADD COLUMN is_archived BOOLEAN NOT NULL DEFAULT FALSE;
+CREATE INDEX idx_products_system_active ON products(system_id)
+WHERE is_archived = FALSE;
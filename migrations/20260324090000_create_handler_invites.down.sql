@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_handlers_system_id;
+DROP TABLE IF EXISTS handler_invites;
+ALTER TABLE handlers DROP COLUMN IF EXISTS is_active;
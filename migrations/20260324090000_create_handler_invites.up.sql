@@ -0,0 +1,20 @@
+-- Deactivated handlers keep their history but can no longer sign in
+ALTER TABLE handlers
+ADD COLUMN is_active BOOLEAN NOT NULL DEFAULT TRUE;
+-- Pending staff invitations (the raw token is only ever shown to the inviter)
+CREATE TABLE handler_invites (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    user_name TEXT NOT NULL,
+    handler_role handler_role NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    invited_by UUID NOT NULL REFERENCES handlers(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT (NOW() + INTERVAL '7 days'),
+    accepted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_handler_invites_system_id ON handler_invites(system_id);
+CREATE UNIQUE INDEX idx_handler_invites_open_email ON handler_invites(system_id, lower(email))
+WHERE accepted_at IS NULL;
+CREATE INDEX idx_handlers_system_id ON handlers(system_id);
@@ -0,0 +1,11 @@
+-- Sign-in attempts, used for per-account and per-client backoff and as a security trail
+CREATE TABLE login_attempts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email TEXT NOT NULL,
+    ip_address TEXT,
+    succeeded BOOLEAN NOT NULL,
+    attempted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_login_attempts_email ON login_attempts(email, attempted_at DESC);
+CREATE INDEX idx_login_attempts_ip ON login_attempts(ip_address, attempted_at DESC)
+WHERE ip_address IS NOT NULL;
@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS login_challenges;
+DROP TYPE IF EXISTS login_challenge_kind;
+DROP TABLE IF EXISTS handler_recovery_codes;
+ALTER TABLE systems DROP COLUMN IF EXISTS require_two_factor;
+ALTER TABLE handlers DROP COLUMN IF EXISTS totp_secret,
+    DROP COLUMN IF EXISTS totp_pending_secret,
+    DROP COLUMN IF EXISTS totp_last_step;
@@ -0,0 +1,30 @@
+-- TOTP secrets are needed in plain form to compute codes; a pending secret waits for
+-- its first confirmed code before it replaces the active one
+ALTER TABLE handlers
+ADD COLUMN totp_secret TEXT,
+ADD COLUMN totp_pending_secret TEXT,
+ADD COLUMN totp_last_step BIGINT;
+-- Workspace policy: admins and managers must have 2FA before they can sign in
+ALTER TABLE systems
+ADD COLUMN require_two_factor BOOLEAN NOT NULL DEFAULT FALSE;
+-- Single-use recovery codes (SHA-256 digests)
+CREATE TABLE handler_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    handler_id UUID NOT NULL REFERENCES handlers(id) ON DELETE CASCADE,
+    code_hash TEXT NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_handler_recovery_codes_handler_id ON handler_recovery_codes(handler_id);
+-- Short-lived state between a correct password and a session
+CREATE TYPE login_challenge_kind AS ENUM ('verify', 'enrol');
+CREATE TABLE login_challenges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    handler_id UUID NOT NULL REFERENCES handlers(id) ON DELETE CASCADE,
+    kind login_challenge_kind NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT (NOW() + INTERVAL '10 minutes'),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_login_challenges_expires_at ON login_challenges(expires_at);
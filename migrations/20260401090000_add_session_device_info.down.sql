@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_sessions_system_id_last_seen;
+DROP INDEX IF EXISTS idx_sessions_handler_id;
+ALTER TABLE sessions
+DROP COLUMN IF EXISTS last_seen_at,
+DROP COLUMN IF EXISTS ip_address,
+DROP COLUMN IF EXISTS user_agent;
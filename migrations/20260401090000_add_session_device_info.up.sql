@@ -0,0 +1,7 @@
+-- Which device a session belongs to, so handlers can recognise and revoke it
+ALTER TABLE sessions
+ADD COLUMN user_agent TEXT,
+ADD COLUMN ip_address TEXT,
+ADD COLUMN last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+CREATE INDEX idx_sessions_handler_id ON sessions(handler_id);
+CREATE INDEX idx_sessions_system_id_last_seen ON sessions(system_id, last_seen_at);
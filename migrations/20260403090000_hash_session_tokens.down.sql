@@ -0,0 +1,8 @@
+-- Digests cannot be turned back into tokens; sessions are dropped again.
+DELETE FROM sessions;
+ALTER INDEX IF EXISTS idx_sessions_token_hash
+    RENAME TO idx_sessions_token;
+ALTER TABLE sessions
+    RENAME CONSTRAINT sessions_token_hash_key TO sessions_token_key;
+ALTER TABLE sessions
+    RENAME COLUMN token_hash TO token;
@@ -0,0 +1,9 @@
+-- Sessions keep only the SHA-256 digest of their bearer token. Existing rows hold
+-- plaintext tokens that can never match a digest, so everyone signs in again.
+DELETE FROM sessions;
+ALTER TABLE sessions
+    RENAME COLUMN token TO token_hash;
+ALTER TABLE sessions
+    RENAME CONSTRAINT sessions_token_key TO sessions_token_hash_key;
+ALTER INDEX IF EXISTS idx_sessions_token
+    RENAME TO idx_sessions_token_hash;
@@ -0,0 +1,34 @@
+DROP POLICY IF EXISTS tenant_isolation ON password_reset_tokens;
+ALTER TABLE password_reset_tokens DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON login_challenges;
+ALTER TABLE login_challenges DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON handler_recovery_codes;
+ALTER TABLE handler_recovery_codes DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON sale_lines;
+ALTER TABLE sale_lines DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON untracked_inventory;
+ALTER TABLE untracked_inventory DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON tracked_units;
+ALTER TABLE tracked_units DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON sales;
+ALTER TABLE sales DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON stock_adjustments;
+ALTER TABLE stock_adjustments DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON products;
+ALTER TABLE products DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON handler_invites;
+ALTER TABLE handler_invites DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON sessions;
+ALTER TABLE sessions DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON handlers;
+ALTER TABLE handlers DISABLE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON systems;
+ALTER TABLE systems DISABLE ROW LEVEL SECURITY;
+DROP FUNCTION IF EXISTS app_current_system_id();
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+REVOKE USAGE, SELECT ON SEQUENCES FROM sharp_tenant;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+REVOKE SELECT, INSERT, UPDATE, DELETE ON TABLES FROM sharp_tenant;
+REVOKE ALL ON ALL TABLES IN SCHEMA public FROM sharp_tenant;
+REVOKE USAGE ON SCHEMA public FROM sharp_tenant;
+-- The role itself is cluster-wide and may be shared with other databases, so it is kept
@@ -0,0 +1,106 @@
+-- Row-level security: tenant-scoped requests run as `sharp_tenant` with
+-- `app.current_system_id` set, and only ever see rows of that System.
+-- The connecting (owner) role is unaffected, so sign-in, session lookup and
+-- background jobs keep working across tenants.
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'sharp_tenant') THEN
+        CREATE ROLE sharp_tenant NOLOGIN;
+    END IF;
+END
+$$;
+GRANT sharp_tenant TO CURRENT_USER;
+GRANT USAGE ON SCHEMA public TO sharp_tenant;
+GRANT SELECT, INSERT, UPDATE, DELETE ON
+    systems,
+    handlers,
+    sessions,
+    handler_invites,
+    handler_recovery_codes,
+    login_challenges,
+    password_reset_tokens,
+    products,
+    tracked_units,
+    untracked_inventory,
+    stock_adjustments,
+    sales,
+    sale_lines
+TO sharp_tenant;
+-- Future tables are usable by tenants once they add their own policy
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+GRANT SELECT, INSERT, UPDATE, DELETE ON TABLES TO sharp_tenant;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+GRANT USAGE, SELECT ON SEQUENCES TO sharp_tenant;
+
+-- NULL (no rows) when the setting is missing or empty
+CREATE OR REPLACE FUNCTION app_current_system_id() RETURNS UUID
+LANGUAGE sql STABLE AS $$
+    SELECT NULLIF(current_setting('app.current_system_id', true), '')::uuid
+$$;
+
+-- Tables carrying system_id directly
+ALTER TABLE systems ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON systems
+    USING (id = app_current_system_id())
+    WITH CHECK (id = app_current_system_id());
+
+ALTER TABLE handlers ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON handlers
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE sessions ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON sessions
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE handler_invites ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON handler_invites
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE products ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON products
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE stock_adjustments ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON stock_adjustments
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE sales ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON sales
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+-- Tables reached through a parent (the parent's own policy applies inside EXISTS)
+ALTER TABLE tracked_units ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON tracked_units
+    USING (EXISTS (SELECT 1 FROM products p WHERE p.id = product_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM products p WHERE p.id = product_id));
+
+ALTER TABLE untracked_inventory ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON untracked_inventory
+    USING (EXISTS (SELECT 1 FROM products p WHERE p.id = product_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM products p WHERE p.id = product_id));
+
+ALTER TABLE sale_lines ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON sale_lines
+    USING (EXISTS (SELECT 1 FROM sales s WHERE s.id = sale_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM sales s WHERE s.id = sale_id));
+
+ALTER TABLE handler_recovery_codes ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON handler_recovery_codes
+    USING (EXISTS (SELECT 1 FROM handlers h WHERE h.id = handler_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM handlers h WHERE h.id = handler_id));
+
+ALTER TABLE login_challenges ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON login_challenges
+    USING (EXISTS (SELECT 1 FROM handlers h WHERE h.id = handler_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM handlers h WHERE h.id = handler_id));
+
+ALTER TABLE password_reset_tokens ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON password_reset_tokens
+    USING (EXISTS (SELECT 1 FROM handlers h WHERE h.id = handler_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM handlers h WHERE h.id = handler_id));
@@ -0,0 +1,55 @@
+CREATE TYPE audit_entity AS ENUM (
+    'workspace',
+    'handler',
+    'session',
+    'invite',
+    'product',
+    'tracked_unit',
+    'bulk_stock',
+    'sale'
+);
+
+CREATE TYPE audit_action AS ENUM (
+    'created',
+    'updated',
+    'archived',
+    'deleted',
+    'signed_in',
+    'signed_out',
+    'password_reset',
+    'received',
+    'adjusted',
+    'role_changed',
+    'deactivated',
+    'revoked',
+    'two_factor_enabled',
+    'two_factor_disabled'
+);
+
+-- Append-only history of who changed what. `before_state`/`after_state` hold only the
+-- fields that changed (or the whole entity when it was created or removed).
+CREATE TABLE audit_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES handlers(id) ON DELETE SET NULL,
+    entity_type audit_entity NOT NULL,
+    entity_id UUID,
+    action audit_action NOT NULL,
+    before_state JSONB,
+    after_state JSONB,
+    ip_address TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_events_system_time ON audit_events(system_id, occurred_at DESC);
+CREATE INDEX idx_audit_events_entity ON audit_events(system_id, entity_type, entity_id);
+CREATE INDEX idx_audit_events_actor ON audit_events(actor_id);
+
+ALTER TABLE audit_events ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON audit_events
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+-- Tenants may add to the log but never rewrite it
+GRANT SELECT, INSERT ON audit_events TO sharp_tenant;
+REVOKE UPDATE, DELETE ON audit_events FROM sharp_tenant;
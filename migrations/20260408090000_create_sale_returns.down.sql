@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS sale_return_lines;
+DROP TABLE IF EXISTS sale_returns;
+DROP TYPE IF EXISTS return_condition;
+DROP TYPE IF EXISTS refund_method;
+-- Enum values cannot be dropped; the 'returned' stock reason and audit action are left in place
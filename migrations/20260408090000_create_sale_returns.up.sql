@@ -0,0 +1,42 @@
+-- Bulk stock put back on the shelf by a return
+ALTER TYPE stock_adjustment_reason ADD VALUE IF NOT EXISTS 'returned';
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'returned';
+
+CREATE TYPE refund_method AS ENUM ('cash', 'card', 'store_credit', 'other');
+-- Resellable goods go back on sale; defective tracked units are parked in RMA
+CREATE TYPE return_condition AS ENUM ('resellable', 'defective');
+
+-- 1. Returns (one per customer visit, carrying the refund issued)
+CREATE TABLE sale_returns (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    sale_id UUID NOT NULL REFERENCES sales(id) ON DELETE CASCADE,
+    refund_amount NUMERIC(12, 2) NOT NULL CHECK (refund_amount >= 0),
+    refund_method refund_method NOT NULL,
+    reason TEXT,
+    processed_by UUID NOT NULL REFERENCES handlers(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_sale_returns_system_id ON sale_returns(system_id);
+CREATE INDEX idx_sale_returns_sale_id ON sale_returns(sale_id);
+
+-- 2. Return lines (what came back from each original sale line)
+CREATE TABLE sale_return_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    return_id UUID NOT NULL REFERENCES sale_returns(id) ON DELETE CASCADE,
+    sale_line_id UUID NOT NULL REFERENCES sale_lines(id) ON DELETE CASCADE,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    condition return_condition NOT NULL
+);
+CREATE INDEX idx_sale_return_lines_return_id ON sale_return_lines(return_id);
+CREATE INDEX idx_sale_return_lines_sale_line_id ON sale_return_lines(sale_line_id);
+
+ALTER TABLE sale_returns ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON sale_returns
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE sale_return_lines ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON sale_return_lines
+    USING (EXISTS (SELECT 1 FROM sale_returns r WHERE r.id = return_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM sale_returns r WHERE r.id = return_id));
@@ -0,0 +1,9 @@
+-- Put any held units back on sale before the holds disappear
+UPDATE tracked_units SET status = 'InStock' WHERE status = 'Reserved';
+DROP TABLE IF EXISTS reservation_lines;
+DROP TABLE IF EXISTS reservations;
+DROP TYPE IF EXISTS reservation_status;
+ALTER TABLE untracked_inventory
+DROP CONSTRAINT IF EXISTS untracked_inventory_reserved_quantity_check,
+DROP COLUMN IF EXISTS reserved_quantity;
+-- Enum values cannot be dropped; the reservation audit entity and actions are left in place
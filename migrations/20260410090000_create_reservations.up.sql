@@ -0,0 +1,60 @@
+ALTER TYPE audit_entity ADD VALUE IF NOT EXISTS 'reservation';
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'converted';
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'released';
+
+-- Bulk stock on hold stays in `quantity` but is not offered at the till
+ALTER TABLE untracked_inventory
+ADD COLUMN reserved_quantity INTEGER NOT NULL DEFAULT 0,
+ADD CONSTRAINT untracked_inventory_reserved_quantity_check CHECK (
+    reserved_quantity >= 0
+    AND reserved_quantity <= quantity
+);
+
+CREATE TYPE reservation_status AS ENUM ('active', 'converted', 'cancelled', 'expired');
+
+-- 1. Reservations (a hold for a named customer, secured by a deposit)
+CREATE TABLE reservations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    customer_name VARCHAR(255) NOT NULL,
+    customer_phone VARCHAR(50),
+    deposit NUMERIC(12, 2) NOT NULL DEFAULT 0.00 CHECK (deposit >= 0),
+    expires_at TIMESTAMPTZ NOT NULL,
+    status reservation_status NOT NULL DEFAULT 'active',
+    -- Set once the hold is paid off and becomes a sale
+    sale_id UUID REFERENCES sales(id) ON DELETE SET NULL,
+    created_by UUID NOT NULL REFERENCES handlers(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    closed_at TIMESTAMPTZ
+);
+CREATE INDEX idx_reservations_system_status ON reservations(system_id, status);
+-- Served to the expiry job, which only looks at active holds
+CREATE INDEX idx_reservations_active_expiry ON reservations(expires_at)
+WHERE status = 'active';
+
+-- 2. Reservation lines (the exact units or bulk quantity held, at the agreed price)
+CREATE TABLE reservation_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    reservation_id UUID NOT NULL REFERENCES reservations(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id),
+    tracked_unit_id UUID REFERENCES tracked_units(id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_price NUMERIC(12, 2) NOT NULL CHECK (unit_price >= 0),
+    CONSTRAINT reservation_lines_tracked_quantity_check CHECK (
+        tracked_unit_id IS NULL
+        OR quantity = 1
+    )
+);
+CREATE INDEX idx_reservation_lines_reservation_id ON reservation_lines(reservation_id);
+CREATE INDEX idx_reservation_lines_product_id ON reservation_lines(product_id);
+CREATE INDEX idx_reservation_lines_tracked_unit_id ON reservation_lines(tracked_unit_id);
+
+ALTER TABLE reservations ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON reservations
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE reservation_lines ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON reservation_lines
+    USING (EXISTS (SELECT 1 FROM reservations r WHERE r.id = reservation_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM reservations r WHERE r.id = reservation_id));
@@ -0,0 +1,4 @@
+ALTER TABLE sales DROP COLUMN IF EXISTS customer_id;
+DROP TABLE IF EXISTS customers;
+ALTER TABLE products DROP COLUMN IF EXISTS warranty_months;
+-- Enum values cannot be dropped; the 'customer' audit entity is left in place
@@ -0,0 +1,33 @@
+ALTER TYPE audit_entity ADD VALUE IF NOT EXISTS 'customer';
+
+-- Months of cover a tracked unit carries from the day it is sold; 0 means none
+ALTER TABLE products
+ADD COLUMN warranty_months INTEGER NOT NULL DEFAULT 0 CHECK (warranty_months >= 0);
+
+CREATE TABLE customers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    -- Stored normalized (digits with an optional leading '+') so the scanner can match it
+    phone VARCHAR(50),
+    email VARCHAR(255),
+    notes TEXT,
+    created_by UUID NOT NULL REFERENCES handlers(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_customers_system_id ON customers(system_id);
+CREATE UNIQUE INDEX customers_system_phone_key ON customers(system_id, phone)
+WHERE phone IS NOT NULL;
+CREATE TRIGGER update_customers_modtime BEFORE
+UPDATE ON customers FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Sales stay anonymous unless a customer is attached at checkout
+ALTER TABLE sales
+ADD COLUMN customer_id UUID REFERENCES customers(id) ON DELETE SET NULL;
+CREATE INDEX idx_sales_customer_id ON sales(customer_id);
+
+ALTER TABLE customers ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON customers
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
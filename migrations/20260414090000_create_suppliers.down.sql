@@ -0,0 +1,5 @@
+ALTER TABLE stock_adjustments DROP COLUMN IF EXISTS supplier_id;
+DROP INDEX IF EXISTS idx_tracked_units_supplier_id;
+ALTER TABLE tracked_units DROP CONSTRAINT IF EXISTS tracked_units_supplier_id_fkey;
+DROP TABLE IF EXISTS suppliers;
+-- Enum values cannot be dropped; the 'supplier' audit entity is left in place
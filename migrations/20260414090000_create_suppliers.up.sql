@@ -0,0 +1,36 @@
+ALTER TYPE audit_entity ADD VALUE IF NOT EXISTS 'supplier';
+
+CREATE TABLE suppliers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    contact_name VARCHAR(255),
+    phone VARCHAR(50),
+    email VARCHAR(255),
+    notes TEXT,
+    created_by UUID NOT NULL REFERENCES handlers(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_suppliers_system_id ON suppliers(system_id);
+CREATE UNIQUE INDEX suppliers_system_name_key ON suppliers(system_id, lower(name));
+CREATE TRIGGER update_suppliers_modtime BEFORE
+UPDATE ON suppliers FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- The column predates the table; drop any values that never pointed anywhere
+UPDATE tracked_units SET supplier_id = NULL WHERE supplier_id IS NOT NULL;
+-- A supplier with received stock is kept so its purchase history stays attributable
+ALTER TABLE tracked_units
+ADD CONSTRAINT tracked_units_supplier_id_fkey
+FOREIGN KEY (supplier_id) REFERENCES suppliers(id) ON DELETE RESTRICT;
+CREATE INDEX idx_tracked_units_supplier_id ON tracked_units(supplier_id);
+
+ALTER TABLE stock_adjustments
+ADD COLUMN supplier_id UUID CONSTRAINT stock_adjustments_supplier_id_fkey
+REFERENCES suppliers(id) ON DELETE RESTRICT;
+CREATE INDEX idx_stock_adjustments_supplier_id ON stock_adjustments(supplier_id);
+
+ALTER TABLE suppliers ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON suppliers
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
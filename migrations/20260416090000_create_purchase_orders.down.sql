@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS purchase_order_lines;
+DROP TABLE IF EXISTS purchase_orders;
+DROP TYPE IF EXISTS purchase_order_status;
+ALTER TABLE systems DROP COLUMN IF EXISTS last_purchase_order_number;
+-- Enum values cannot be dropped; the 'purchase_order' audit entity and the 'ordered' and
+-- 'cancelled' audit actions are left in place
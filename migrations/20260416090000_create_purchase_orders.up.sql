@@ -0,0 +1,58 @@
+ALTER TYPE audit_entity ADD VALUE IF NOT EXISTS 'purchase_order';
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'ordered';
+ALTER TYPE audit_action ADD VALUE IF NOT EXISTS 'cancelled';
+
+ALTER TABLE systems
+ADD COLUMN last_purchase_order_number INTEGER NOT NULL DEFAULT 0;
+
+CREATE TYPE purchase_order_status AS ENUM (
+    'draft',
+    'ordered',
+    'partially_received',
+    'received',
+    'cancelled'
+);
+
+-- 1. Purchase orders (what was ordered from a supplier)
+CREATE TABLE purchase_orders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+    po_number INTEGER NOT NULL,
+    supplier_id UUID NOT NULL REFERENCES suppliers(id) ON DELETE RESTRICT,
+    status purchase_order_status NOT NULL DEFAULT 'draft',
+    notes TEXT,
+    created_by UUID NOT NULL REFERENCES handlers(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ordered_at TIMESTAMPTZ,
+    -- Set when the order is fully received or cancelled
+    closed_at TIMESTAMPTZ,
+    CONSTRAINT purchase_orders_system_po_number_key UNIQUE (system_id, po_number)
+);
+CREATE INDEX idx_purchase_orders_system_status ON purchase_orders(system_id, status);
+CREATE INDEX idx_purchase_orders_supplier_id ON purchase_orders(supplier_id);
+
+-- 2. Purchase order lines (expected quantity at the agreed cost, and how much has arrived)
+CREATE TABLE purchase_order_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    purchase_order_id UUID NOT NULL REFERENCES purchase_orders(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id),
+    expected_quantity INTEGER NOT NULL CHECK (expected_quantity > 0),
+    received_quantity INTEGER NOT NULL DEFAULT 0,
+    unit_cost NUMERIC(12, 2) NOT NULL CHECK (unit_cost >= 0),
+    CONSTRAINT purchase_order_lines_received_quantity_check CHECK (
+        received_quantity >= 0
+        AND received_quantity <= expected_quantity
+    )
+);
+CREATE INDEX idx_purchase_order_lines_order_id ON purchase_order_lines(purchase_order_id);
+CREATE INDEX idx_purchase_order_lines_product_id ON purchase_order_lines(product_id);
+
+ALTER TABLE purchase_orders ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON purchase_orders
+    USING (system_id = app_current_system_id())
+    WITH CHECK (system_id = app_current_system_id());
+
+ALTER TABLE purchase_order_lines ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON purchase_order_lines
+    USING (EXISTS (SELECT 1 FROM purchase_orders po WHERE po.id = purchase_order_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM purchase_orders po WHERE po.id = purchase_order_id));
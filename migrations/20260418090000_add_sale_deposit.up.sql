@@ -0,0 +1,11 @@
+-- Deposit taken on a reservation before it was converted into this sale
+ALTER TABLE sales ADD COLUMN deposit_applied NUMERIC(12, 2) NOT NULL DEFAULT 0.00;
+
+-- Sales already converted from a reservation carry its deposit
+UPDATE sales s
+SET deposit_applied = LEAST(r.deposit, s.total_amount)
+FROM reservations r
+WHERE r.sale_id = s.id;
+
+ALTER TABLE sales ADD CONSTRAINT sales_deposit_applied_check
+    CHECK (deposit_applied >= 0 AND deposit_applied <= total_amount);
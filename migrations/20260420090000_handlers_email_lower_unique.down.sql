@@ -0,0 +1 @@
+DROP INDEX IF EXISTS handlers_email_lower_key;
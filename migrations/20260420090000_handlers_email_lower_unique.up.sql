@@ -0,0 +1,5 @@
+-- Sign-in matches emails case-insensitively, so two handlers must never differ only by case.
+-- Existing rows are normalised first; a pre-existing case-only duplicate aborts the migration.
+UPDATE handlers SET email = lower(email) WHERE email <> lower(email);
+
+CREATE UNIQUE INDEX handlers_email_lower_key ON handlers (lower(email));
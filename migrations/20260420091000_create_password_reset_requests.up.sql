@@ -0,0 +1,11 @@
+-- Every password reset request, known email or not, so requests can be throttled per
+-- address and per client without revealing which addresses have accounts
+CREATE TABLE password_reset_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email TEXT NOT NULL,
+    ip_address TEXT,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_password_reset_requests_email ON password_reset_requests(email, requested_at DESC);
+CREATE INDEX idx_password_reset_requests_ip ON password_reset_requests(ip_address, requested_at DESC)
+WHERE ip_address IS NOT NULL;
@@ -10,6 +10,8 @@ pub enum AuthError {
     InvalidInput(String),
     #[error("Security Breach: Invalid credentials provided.")]
     InvalidCredentials,
+    #[error("Too many failed sign-in attempts. Try again in {0} seconds.")]
+    TooManyAttempts(u64),
     #[error("System Error: The core service is temporarily unavailable.")]
     InternalError,
 }
@@ -30,6 +32,7 @@ impl SystemNotification for AuthError {
             Self::UserAlreadyExists => "Conflict Detected".to_string(),
             Self::InvalidInput(_) => "Data Invalid".to_string(),
             Self::InvalidCredentials => "Auth Failure".to_string(),
+            Self::TooManyAttempts(_) => "Access Throttled".to_string(),
             Self::InternalError => "System Fault".to_string(),
         }
     }
@@ -40,9 +43,10 @@ impl SystemNotification for AuthError {
 
     fn level(&self) -> NotificationLevel {
         match self {
-            Self::UserAlreadyExists | Self::InvalidInput(_) | Self::InvalidCredentials => {
-                NotificationLevel::Warning
-            }
+            Self::UserAlreadyExists
+            | Self::InvalidInput(_)
+            | Self::InvalidCredentials
+            | Self::TooManyAttempts(_) => NotificationLevel::Warning,
             Self::InternalError => NotificationLevel::Error,
         }
     }
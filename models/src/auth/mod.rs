@@ -1,3 +1,5 @@
 pub mod errors;
+pub mod throttle;
 
 pub use errors::*;
+pub use throttle::*;
@@ -0,0 +1,66 @@
+/// Exponential backoff applied to repeated failed sign-ins.
+///
+/// The first `free_attempts` consecutive failures cost nothing; each failure after that
+/// doubles the wait (starting at `base_delay_secs`) up to `max_delay_secs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoginThrottle {
+    pub free_attempts: i64,
+    pub base_delay_secs: i64,
+    pub max_delay_secs: i64,
+}
+
+impl LoginThrottle {
+    /// Failures against a single email address.
+    pub const ACCOUNT: Self = Self {
+        free_attempts: 5,
+        base_delay_secs: 30,
+        max_delay_secs: 60 * 60,
+    };
+
+    /// Failures from a single client IP, across any number of emails.
+    pub const CLIENT: Self = Self {
+        free_attempts: 20,
+        base_delay_secs: 30,
+        max_delay_secs: 60 * 60,
+    };
+
+    /// How long to wait after the latest failure, given `failures` consecutive failures.
+    pub fn delay_secs(&self, failures: i64) -> i64 {
+        let over = failures - self.free_attempts;
+        if over <= 0 {
+            return 0;
+        }
+        // Cap the exponent well before it can overflow
+        let factor = 1i64 << (over - 1).min(20);
+        self.base_delay_secs
+            .saturating_mul(factor)
+            .min(self.max_delay_secs)
+    }
+
+    /// Seconds left before another attempt is allowed, if still locked out.
+    pub fn retry_after_secs(&self, failures: i64, secs_since_last_failure: i64) -> Option<u64> {
+        let remaining = self.delay_secs(failures) - secs_since_last_failure.max(0);
+        (remaining > 0).then_some(remaining as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_throttle_backoff() {
+        let throttle = LoginThrottle::ACCOUNT;
+
+        assert_eq!(throttle.delay_secs(0), 0);
+        assert_eq!(throttle.delay_secs(5), 0); // Free attempts
+        assert_eq!(throttle.delay_secs(6), 30);
+        assert_eq!(throttle.delay_secs(7), 60);
+        assert_eq!(throttle.delay_secs(9), 240);
+        assert_eq!(throttle.delay_secs(500), 60 * 60); // Capped
+
+        assert_eq!(throttle.retry_after_secs(7, 20), Some(40));
+        assert_eq!(throttle.retry_after_secs(7, 60), None);
+        assert_eq!(throttle.retry_after_secs(3, 0), None);
+    }
+}
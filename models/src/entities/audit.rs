@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// What kind of record an audit event is about.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(type_name = "public.audit_entity", rename_all = "snake_case")
+)]
+pub enum AuditEntity {
+    Workspace,
+    Handler,
+    Session,
+    Invite,
+    Product,
+    TrackedUnit,
+    BulkStock,
+    Sale,
+    Reservation,
+    Customer,
+    Supplier,
+    PurchaseOrder,
+}
+
+impl AuditEntity {
+    pub const ALL: [Self; 12] = [
+        Self::Workspace,
+        Self::Handler,
+        Self::Session,
+        Self::Invite,
+        Self::Product,
+        Self::TrackedUnit,
+        Self::BulkStock,
+        Self::Sale,
+        Self::Reservation,
+        Self::Customer,
+        Self::Supplier,
+        Self::PurchaseOrder,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Workspace => "Workspace",
+            Self::Handler => "Handler",
+            Self::Session => "Session",
+            Self::Invite => "Invite",
+            Self::Product => "Product",
+            Self::TrackedUnit => "Tracked Unit",
+            Self::BulkStock => "Bulk Stock",
+            Self::Sale => "Sale",
+            Self::Reservation => "Reservation",
+            Self::Customer => "Customer",
+            Self::Supplier => "Supplier",
+            Self::PurchaseOrder => "Purchase Order",
+        }
+    }
+}
+
+/// What happened to the entity.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(type_name = "public.audit_action", rename_all = "snake_case")
+)]
+pub enum AuditAction {
+    Created,
+    Updated,
+    Archived,
+    Deleted,
+    SignedIn,
+    SignedOut,
+    PasswordReset,
+    Received,
+    Adjusted,
+    RoleChanged,
+    Deactivated,
+    Revoked,
+    TwoFactorEnabled,
+    TwoFactorDisabled,
+    Returned,
+    Converted,
+    Released,
+    Ordered,
+    Cancelled,
+}
+
+impl AuditAction {
+    pub const ALL: [Self; 19] = [
+        Self::Created,
+        Self::Updated,
+        Self::Archived,
+        Self::Deleted,
+        Self::SignedIn,
+        Self::SignedOut,
+        Self::PasswordReset,
+        Self::Received,
+        Self::Adjusted,
+        Self::RoleChanged,
+        Self::Deactivated,
+        Self::Revoked,
+        Self::TwoFactorEnabled,
+        Self::TwoFactorDisabled,
+        Self::Returned,
+        Self::Converted,
+        Self::Released,
+        Self::Ordered,
+        Self::Cancelled,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Created => "Created",
+            Self::Updated => "Updated",
+            Self::Archived => "Archived",
+            Self::Deleted => "Deleted",
+            Self::SignedIn => "Signed In",
+            Self::SignedOut => "Signed Out",
+            Self::PasswordReset => "Password Reset",
+            Self::Received => "Received",
+            Self::Adjusted => "Adjusted",
+            Self::RoleChanged => "Role Changed",
+            Self::Deactivated => "Deactivated",
+            Self::Revoked => "Revoked",
+            Self::TwoFactorEnabled => "2FA Enabled",
+            Self::TwoFactorDisabled => "2FA Disabled",
+            Self::Returned => "Returned",
+            Self::Converted => "Converted",
+            Self::Released => "Released",
+            Self::Ordered => "Ordered",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+}
+
+/// One row of the audit log. The states hold only the fields that changed, or the
+/// whole entity when it was created or removed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    /// `None` once the acting handler has been removed.
+    pub actor_name: Option<String>,
+    pub entity_type: AuditEntity,
+    pub entity_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub before_state: Option<Value>,
+    pub after_state: Option<Value>,
+    pub ip_address: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditLogPage {
+    pub items: Vec<AuditEvent>,
+    pub total: i64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+impl AuditLogPage {
+    pub fn page_count(&self) -> u32 {
+        (self.total.max(0) as u64).div_ceil(u64::from(self.page_size.max(1))) as u32
+    }
+}
@@ -0,0 +1,83 @@
+use crate::entities::{RefundMethod, Sale};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Customer {
+    pub id: Uuid,
+    pub system_id: Uuid,
+    pub name: String,
+    /// Normalized by `payloads::normalize_phone`.
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A refund issued against one of the customer's sales.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct CustomerReturn {
+    pub id: Uuid,
+    pub sale_id: Uuid,
+    pub invoice_number: i32,
+    pub refund_amount: Decimal,
+    pub refund_method: RefundMethod,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A serialized unit the customer bought and still has.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct WarrantyUnit {
+    pub tracked_unit_id: Uuid,
+    pub product_name: String,
+    pub serial_numbers: Vec<String>,
+    pub invoice_number: i32,
+    pub sold_at: DateTime<Utc>,
+    /// `None` when the product carries no warranty.
+    pub warranty_until: Option<DateTime<Utc>>,
+}
+
+impl WarrantyUnit {
+    pub fn is_covered(&self, now: DateTime<Utc>) -> bool {
+        self.warranty_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Everything the workspace has on record for one customer, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerHistory {
+    pub customer: Customer,
+    pub sales: Vec<Sale>,
+    pub returns: Vec<CustomerReturn>,
+    pub units: Vec<WarrantyUnit>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_warranty_coverage() {
+        let now = Utc::now();
+        let unit = |warranty_until| WarrantyUnit {
+            tracked_unit_id: Uuid::new_v4(),
+            product_name: "Phone".to_string(),
+            serial_numbers: vec!["SN-1".to_string()],
+            invoice_number: 1,
+            sold_at: now - Duration::days(30),
+            warranty_until,
+        };
+
+        assert!(unit(Some(now + Duration::days(1))).is_covered(now));
+        assert!(!unit(Some(now - Duration::days(1))).is_covered(now)); // Lapsed
+        assert!(!unit(None).is_covered(now)); // No warranty
+    }
+}
@@ -1,3 +1,5 @@
+use crate::entities::Customer;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -12,4 +14,184 @@ pub struct Product {
     pub is_tracked: bool,
     pub added_by: Uuid,
     pub last_edited_by: Option<Uuid>,
+    pub is_archived: bool,
+    /// Months of cover a tracked unit carries from the day it is sold; 0 means none.
+    pub warranty_months: i32,
+}
+
+/// One page of `GetProducts` plus the total number of matching products.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductPage {
+    pub items: Vec<Product>,
+    pub total: i64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+impl ProductPage {
+    pub fn page_count(&self) -> u32 {
+        (self.total.max(0) as u64).div_ceil(u64::from(self.page_size.max(1))) as u32
+    }
+}
+
+/// How `ArchiveProduct` retired a product.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProductRemoval {
+    /// Sales or stock history reference it, so it was hidden but kept.
+    Archived(Product),
+    /// Nothing referenced it, so it was deleted outright.
+    Deleted { product_id: Uuid },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(type_name = "public.tracked_unit_status", rename_all = "PascalCase")
+)]
+pub enum TrackedUnitStatus {
+    InStock,
+    Sold,
+    #[cfg_attr(feature = "ssr", sqlx(rename = "RMA"))]
+    Rma,
+    Reserved,
+    Archived,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct TrackedUnit {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub serial_numbers: Vec<String>,
+    pub supplier_id: Option<Uuid>,
+    pub status: TrackedUnitStatus,
+    pub acquisition_cost: Decimal,
+    pub target_msrp: Decimal,
+    pub added_by: Uuid,
+    pub last_updated_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct UntrackedInventory {
+    pub product_id: Uuid,
+    pub quantity: i32,
+    pub average_acquisition_cost: Decimal,
+    pub base_retail_price: Decimal,
+    pub last_updated_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(
+        type_name = "public.stock_adjustment_reason",
+        rename_all = "snake_case"
+    )
+)]
+pub enum StockAdjustmentReason {
+    Received,
+    Damaged,
+    Lost,
+    Theft,
+    CountCorrection,
+    Other,
+    /// Put back on the shelf by a customer return.
+    Returned,
+}
+
+impl StockAdjustmentReason {
+    /// Reasons a handler may pick when manually adjusting stock.
+    pub const MANUAL: [Self; 5] = [
+        Self::Damaged,
+        Self::Lost,
+        Self::Theft,
+        Self::CountCorrection,
+        Self::Other,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Received => "Received",
+            Self::Damaged => "Damaged",
+            Self::Lost => "Lost",
+            Self::Theft => "Theft",
+            Self::CountCorrection => "Count Correction",
+            Self::Other => "Other",
+            Self::Returned => "Returned",
+        }
+    }
+}
+
+/// A catalog product with what is currently available to sell.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockedProduct {
+    pub product: Product,
+    /// In-stock units for tracked products, bucket quantity for bulk products.
+    pub on_hand: i32,
+    /// Bulk retail price; tracked units carry their own MSRP.
+    pub retail_price: Decimal,
+}
+
+/// What the POS scanner box resolved a query to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LookupResult {
+    /// Exact serial/IMEI match on a single unit.
+    TrackedUnitHit {
+        unit: Box<TrackedUnit>,
+        product: Box<Product>,
+    },
+    /// Exact SKU match (`exact`) or a fuzzy name/category search.
+    ProductHit {
+        products: Vec<StockedProduct>,
+        exact: bool,
+    },
+    /// The query was the phone number of a known customer.
+    CustomerHit {
+        customer: Box<Customer>,
+    },
+    NoMatch,
+}
+
+/// Outcome of a single unit (one serial array) inside a receiving batch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SerialIntakeOutcome {
+    Received {
+        unit_id: Uuid,
+    },
+    /// One or more serials already belong to a unit in this workspace.
+    AlreadyExists {
+        conflicting: Vec<String>,
+    },
+    /// The same serial was scanned more than once in this batch.
+    DuplicateInBatch {
+        conflicting: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SerialIntakeResult {
+    pub serial_numbers: Vec<String>,
+    pub outcome: SerialIntakeOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrackedUnitIntakeReport {
+    pub product_id: Uuid,
+    pub results: Vec<SerialIntakeResult>,
+}
+
+impl TrackedUnitIntakeReport {
+    pub fn received_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, SerialIntakeOutcome::Received { .. }))
+            .count()
+    }
+
+    pub fn rejected_count(&self) -> usize {
+        self.results.len() - self.received_count()
+    }
 }
@@ -1,5 +1,19 @@
+pub mod audit;
+pub mod customers;
 pub mod inventory;
+pub mod purchase_orders;
+pub mod reservations;
+pub mod sales;
+pub mod suppliers;
 pub mod user;
+pub mod workspace;
 
+pub use audit::*;
+pub use customers::*;
 pub use inventory::*;
+pub use purchase_orders::*;
+pub use reservations::*;
+pub use sales::*;
+pub use suppliers::*;
 pub use user::*;
+pub use workspace::*;
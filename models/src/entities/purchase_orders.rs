@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(type_name = "public.purchase_order_status", rename_all = "snake_case")
+)]
+pub enum PurchaseOrderStatus {
+    /// Being put together; nothing has been sent to the supplier.
+    Draft,
+    /// Sent to the supplier and awaiting delivery.
+    Ordered,
+    /// Some lines have arrived, others are still outstanding.
+    PartiallyReceived,
+    /// Every line has arrived in full.
+    Received,
+    /// Closed without (fully) arriving.
+    Cancelled,
+}
+
+impl PurchaseOrderStatus {
+    pub const ALL: [Self; 5] = [
+        Self::Draft,
+        Self::Ordered,
+        Self::PartiallyReceived,
+        Self::Received,
+        Self::Cancelled,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Draft => "Draft",
+            Self::Ordered => "Ordered",
+            Self::PartiallyReceived => "Partially Received",
+            Self::Received => "Received",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Whether deliveries can still be booked against the order.
+    pub fn is_receivable(&self) -> bool {
+        matches!(self, Self::Ordered | Self::PartiallyReceived)
+    }
+
+    /// Whether the order is still open, i.e. neither received in full nor cancelled.
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Draft | Self::Ordered | Self::PartiallyReceived)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct PurchaseOrder {
+    pub id: Uuid,
+    pub system_id: Uuid,
+    /// Sequential per System, like invoice numbers.
+    pub po_number: i32,
+    pub supplier_id: Uuid,
+    pub status: PurchaseOrderStatus,
+    pub notes: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub ordered_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// One ordered product with how much of it has arrived so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct PurchaseOrderLine {
+    pub id: Uuid,
+    pub purchase_order_id: Uuid,
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub is_tracked: bool,
+    pub expected_quantity: i32,
+    pub received_quantity: i32,
+    pub unit_cost: Decimal,
+}
+
+impl PurchaseOrderLine {
+    pub fn outstanding_quantity(&self) -> i32 {
+        (self.expected_quantity - self.received_quantity).max(0)
+    }
+
+    pub fn line_total(&self) -> Decimal {
+        self.unit_cost * Decimal::from(self.expected_quantity)
+    }
+}
+
+/// A purchase order as listed on the receiving screen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseOrderDetail {
+    pub order: PurchaseOrder,
+    pub supplier_name: String,
+    pub lines: Vec<PurchaseOrderLine>,
+}
+
+impl PurchaseOrderDetail {
+    pub fn total_cost(&self) -> Decimal {
+        self.lines.iter().map(PurchaseOrderLine::line_total).sum()
+    }
+
+    pub fn expected_quantity(&self) -> i32 {
+        self.lines.iter().map(|l| l.expected_quantity).sum()
+    }
+
+    pub fn received_quantity(&self) -> i32 {
+        self.lines.iter().map(|l| l.received_quantity).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(expected_quantity: i32, received_quantity: i32, cost_cents: i64) -> PurchaseOrderLine {
+        PurchaseOrderLine {
+            id: Uuid::new_v4(),
+            purchase_order_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            product_name: "USB-C Cable".to_string(),
+            is_tracked: false,
+            expected_quantity,
+            received_quantity,
+            unit_cost: Decimal::new(cost_cents, 2),
+        }
+    }
+
+    #[test]
+    fn test_purchase_order_progress() {
+        let now = Utc::now();
+        let detail = PurchaseOrderDetail {
+            order: PurchaseOrder {
+                id: Uuid::new_v4(),
+                system_id: Uuid::new_v4(),
+                po_number: 1,
+                supplier_id: Uuid::new_v4(),
+                status: PurchaseOrderStatus::PartiallyReceived,
+                notes: None,
+                created_by: Uuid::new_v4(),
+                created_at: now,
+                ordered_at: Some(now),
+                closed_at: None,
+            },
+            supplier_name: "Acme Distribution".to_string(),
+            lines: vec![line(10, 4, 250), line(2, 2, 10000)],
+        };
+
+        assert_eq!(detail.lines[0].outstanding_quantity(), 6);
+        assert_eq!(detail.lines[1].outstanding_quantity(), 0);
+        assert_eq!(detail.expected_quantity(), 12);
+        assert_eq!(detail.received_quantity(), 6);
+        assert_eq!(detail.total_cost(), Decimal::new(22500, 2));
+        assert!(detail.order.status.is_receivable());
+        assert!(!PurchaseOrderStatus::Draft.is_receivable());
+        assert!(!PurchaseOrderStatus::Received.is_open());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::entities::SaleReceipt;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(type_name = "public.reservation_status", rename_all = "snake_case")
+)]
+pub enum ReservationStatus {
+    /// Stock is on hold and cannot be sold at the till.
+    Active,
+    /// Paid off and turned into a sale.
+    Converted,
+    /// Released early by a handler.
+    Cancelled,
+    /// Released by the expiry job once `expires_at` passed.
+    Expired,
+}
+
+impl ReservationStatus {
+    pub const ALL: [Self; 4] = [
+        Self::Active,
+        Self::Converted,
+        Self::Cancelled,
+        Self::Expired,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Active => "Active",
+            Self::Converted => "Converted",
+            Self::Cancelled => "Cancelled",
+            Self::Expired => "Expired",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Reservation {
+    pub id: Uuid,
+    pub system_id: Uuid,
+    pub customer_name: String,
+    pub customer_phone: Option<String>,
+    pub deposit: Decimal,
+    pub expires_at: DateTime<Utc>,
+    pub status: ReservationStatus,
+    pub sale_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct ReservationLine {
+    pub id: Uuid,
+    pub reservation_id: Uuid,
+    pub product_id: Uuid,
+    pub tracked_unit_id: Option<Uuid>,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+}
+
+/// A held line with what the counter needs to recognise it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct ReservedItem {
+    pub id: Uuid,
+    pub reservation_id: Uuid,
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub tracked_unit_id: Option<Uuid>,
+    pub serial_numbers: Option<Vec<String>>,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+}
+
+/// A reservation with its lines, as listed on the reservations page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReservationDetail {
+    pub reservation: Reservation,
+    pub items: Vec<ReservedItem>,
+}
+
+impl ReservationDetail {
+    pub fn total_amount(&self) -> Decimal {
+        self.items
+            .iter()
+            .map(|i| i.unit_price * Decimal::from(i.quantity))
+            .sum()
+    }
+
+    /// What the customer still owes once the deposit is applied.
+    pub fn balance_due(&self) -> Decimal {
+        (self.total_amount() - self.reservation.deposit).max(Decimal::ZERO)
+    }
+}
+
+/// A reservation paid off at the till.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertedReservation {
+    pub reservation: Reservation,
+    pub receipt: SaleReceipt,
+    /// Collected at conversion; the deposit covered the rest.
+    pub balance_due: Decimal,
+}
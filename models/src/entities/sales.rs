@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Sale {
+    pub id: Uuid,
+    pub system_id: Uuid,
+    pub invoice_number: i32,
+    pub total_amount: Decimal,
+    /// Part of the total already paid as a reservation deposit.
+    pub deposit_applied: Decimal,
+    pub sold_by: Uuid,
+    pub customer_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Sale {
+    /// What was taken at the till, after any reservation deposit.
+    pub fn amount_collected(&self) -> Decimal {
+        self.total_amount - self.deposit_applied
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct SaleLine {
+    pub id: Uuid,
+    pub sale_id: Uuid,
+    pub product_id: Uuid,
+    pub tracked_unit_id: Option<Uuid>,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+    pub unit_cost: Decimal,
+}
+
+impl SaleLine {
+    pub fn line_total(&self) -> Decimal {
+        self.unit_price * Decimal::from(self.quantity)
+    }
+}
+
+/// A completed sale as handed back to the POS for printing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaleReceipt {
+    pub sale: Sale,
+    pub lines: Vec<SaleLine>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(type_name = "public.refund_method", rename_all = "snake_case")
+)]
+pub enum RefundMethod {
+    Cash,
+    Card,
+    StoreCredit,
+    Other,
+}
+
+impl RefundMethod {
+    pub const ALL: [Self; 4] = [Self::Cash, Self::Card, Self::StoreCredit, Self::Other];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Cash => "Cash",
+            Self::Card => "Card",
+            Self::StoreCredit => "Store Credit",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// The state goods come back in. Defective tracked units are held in RMA instead of
+/// going back on sale.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "ssr",
+    sqlx(type_name = "public.return_condition", rename_all = "snake_case")
+)]
+pub enum ReturnCondition {
+    Resellable,
+    Defective,
+}
+
+impl ReturnCondition {
+    pub const ALL: [Self; 2] = [Self::Resellable, Self::Defective];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Resellable => "Resellable",
+            Self::Defective => "Defective",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct SaleReturn {
+    pub id: Uuid,
+    pub sale_id: Uuid,
+    pub refund_amount: Decimal,
+    pub refund_method: RefundMethod,
+    pub reason: Option<String>,
+    pub processed_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct SaleReturnLine {
+    pub id: Uuid,
+    pub return_id: Uuid,
+    pub sale_line_id: Uuid,
+    pub quantity: i32,
+    pub condition: ReturnCondition,
+}
+
+/// A processed return as handed back to the till.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnReceipt {
+    pub sale_return: SaleReturn,
+    pub lines: Vec<SaleReturnLine>,
+}
+
+/// A sale line with how much of it has already come back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct ReturnableLine {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub tracked_unit_id: Option<Uuid>,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+    pub returned_quantity: i32,
+}
+
+impl ReturnableLine {
+    pub fn returnable_quantity(&self) -> i32 {
+        (self.quantity - self.returned_quantity).max(0)
+    }
+}
+
+/// A completed sale looked up for a return.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnableSale {
+    pub sale: Sale,
+    pub lines: Vec<ReturnableLine>,
+    /// Sum of every refund already issued against the sale.
+    pub refunded_amount: Decimal,
+}
+
+impl ReturnableSale {
+    /// What can still be refunded at the till. A reservation deposit is refunded outside
+    /// the system, like one on a cancelled reservation.
+    pub fn refundable_amount(&self) -> Decimal {
+        (self.sale.amount_collected() - self.refunded_amount).max(Decimal::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refundable_amount_excludes_deposit() {
+        let returnable = |deposit_cents, refunded_cents| ReturnableSale {
+            sale: Sale {
+                id: Uuid::new_v4(),
+                system_id: Uuid::new_v4(),
+                invoice_number: 7,
+                total_amount: Decimal::new(100000, 2),
+                deposit_applied: Decimal::new(deposit_cents, 2),
+                sold_by: Uuid::new_v4(),
+                customer_id: None,
+                created_at: Utc::now(),
+            },
+            lines: vec![],
+            refunded_amount: Decimal::new(refunded_cents, 2),
+        };
+
+        assert_eq!(
+            returnable(0, 0).refundable_amount(),
+            Decimal::new(100000, 2)
+        );
+        assert_eq!(
+            returnable(20000, 0).refundable_amount(),
+            Decimal::new(80000, 2)
+        );
+        assert_eq!(
+            returnable(20000, 30000).refundable_amount(),
+            Decimal::new(50000, 2)
+        );
+        assert_eq!(returnable(20000, 80000).refundable_amount(), Decimal::ZERO);
+    }
+}
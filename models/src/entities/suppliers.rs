@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Supplier {
+    pub id: Uuid,
+    pub system_id: Uuid,
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What the workspace has bought from one supplier and how the tracked units held up.
+/// Bulk stock is not traced past receiving, so returns and RMA cover tracked units only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct SupplierReport {
+    pub supplier_id: Uuid,
+    pub supplier_name: String,
+    pub tracked_units: i64,
+    pub bulk_units: i64,
+    /// Acquisition cost of every tracked unit plus every bulk delivery.
+    pub total_cost: Decimal,
+    pub units_sold: i64,
+    /// Sold tracked units that customers brought back, in any condition.
+    pub units_returned: i64,
+    /// Tracked units currently held in RMA.
+    pub units_in_rma: i64,
+}
+
+impl SupplierReport {
+    pub fn units_purchased(&self) -> i64 {
+        self.tracked_units + self.bulk_units
+    }
+
+    /// Share of sold tracked units that came back, or `None` before any were sold.
+    pub fn return_rate(&self) -> Option<f64> {
+        (self.units_sold > 0).then(|| self.units_returned as f64 / self.units_sold as f64)
+    }
+
+    /// Share of received tracked units sitting in RMA, or `None` when none were received.
+    pub fn rma_rate(&self) -> Option<f64> {
+        (self.tracked_units > 0).then(|| self.units_in_rma as f64 / self.tracked_units as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supplier_report_rates() {
+        let report = SupplierReport {
+            supplier_id: Uuid::new_v4(),
+            supplier_name: "Acme Distribution".to_string(),
+            tracked_units: 20,
+            bulk_units: 100,
+            total_cost: Decimal::new(1_500_000, 2),
+            units_sold: 8,
+            units_returned: 2,
+            units_in_rma: 1,
+        };
+
+        assert_eq!(report.units_purchased(), 120);
+        assert_eq!(report.return_rate(), Some(0.25));
+        assert_eq!(report.rma_rate(), Some(0.05));
+
+        let untouched = SupplierReport {
+            tracked_units: 0,
+            units_sold: 0,
+            ..report
+        };
+        assert_eq!(untouched.return_rate(), None);
+        assert_eq!(untouched.rma_rate(), None);
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -16,7 +17,7 @@ pub struct User {
     pub preferred_theme: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ssr", derive(sqlx::Type))]
 #[cfg_attr(
     feature = "ssr",
@@ -27,3 +28,113 @@ pub enum HandlerRole {
     SystemManager,
     SystemSalesman,
 }
+
+impl HandlerRole {
+    pub const ALL: [Self; 3] = [Self::SystemAdmin, Self::SystemManager, Self::SystemSalesman];
+
+    /// Roles a workspace's two-factor policy applies to.
+    pub fn is_privileged(&self) -> bool {
+        matches!(self, Self::SystemAdmin | Self::SystemManager)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SystemAdmin => "Admin",
+            Self::SystemManager => "Manager",
+            Self::SystemSalesman => "Salesman",
+        }
+    }
+}
+
+/// A handler as seen from the staff management screen.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct StaffMember {
+    pub id: Uuid,
+    pub email: String,
+    pub user_name: String,
+    pub handler_role: HandlerRole,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct StaffInvite {
+    pub id: Uuid,
+    pub email: String,
+    pub user_name: String,
+    pub handler_role: HandlerRole,
+    pub invited_by: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A freshly created invite. `accept_path` carries the raw token and is only returned once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedInvite {
+    pub invite: StaffInvite,
+    pub accept_path: String,
+}
+
+/// Result of the password step of signing in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LoginOutcome {
+    Authenticated(User),
+    /// Password accepted; redeem `challenge` with a TOTP or recovery code.
+    TwoFactorRequired {
+        challenge: String,
+    },
+    /// Password accepted, but the workspace requires 2FA and none is set up yet.
+    /// `challenge` may only be used to enrol.
+    TwoFactorSetupRequired {
+        challenge: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpEnrolment {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+/// Recovery codes are returned in plain text exactly once, when 2FA is switched on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpActivation {
+    pub recovery_codes: Vec<String>,
+    /// Set when enrolment finished a sign-in that was waiting on it.
+    pub user: Option<User>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub workspace_requires: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+/// One signed-in device of the current handler.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct ActiveSession {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// The session making the request.
+    pub is_current: bool,
+}
+
+/// Live session counts for a workspace.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Unexpired sessions.
+    pub active_sessions: i64,
+    /// Distinct handlers seen in the last [`SessionStats::ONLINE_WINDOW_MINUTES`].
+    pub online_handlers: i64,
+}
+
+impl SessionStats {
+    pub const ONLINE_WINDOW_MINUTES: i32 = 15;
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A System as addressed by its subdomain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Workspace {
+    pub id: Uuid,
+    pub handle: String,
+    pub name: String,
+}
+
+/// What the server resolved the request's host to, plus what the client needs to
+/// build links to other workspaces.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkspaceContext {
+    /// `None` on the bare base domain, or when subdomain routing is off.
+    pub workspace: Option<Workspace>,
+    pub base_url: String,
+    /// Set when workspaces are served from `<handle>.<base_domain>`.
+    pub base_domain: Option<String>,
+}
@@ -0,0 +1,50 @@
+use crate::entities::{AuditAction, AuditEntity};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Upper bound on `AuditLogQuery::page_size`.
+pub const MAX_AUDIT_PAGE_SIZE: u32 = 200;
+
+/// Filters for `GetAuditLog`; every filter is optional. Results are newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditLogQuery {
+    pub entity_type: Option<AuditEntity>,
+    pub entity_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub action: Option<AuditAction>,
+    /// Inclusive lower bound on `occurred_at`.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `occurred_at`.
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub page: u32,
+    pub page_size: u32,
+}
+
+impl Default for AuditLogQuery {
+    fn default() -> Self {
+        Self {
+            entity_type: None,
+            entity_id: None,
+            actor_id: None,
+            action: None,
+            since: None,
+            until: None,
+            page: 0,
+            page_size: 50,
+        }
+    }
+}
+
+impl AuditLogQuery {
+    /// Clamps the page size to `1..=MAX_AUDIT_PAGE_SIZE`.
+    pub fn normalized(mut self) -> Self {
+        self.page_size = self.page_size.clamp(1, MAX_AUDIT_PAGE_SIZE);
+        self
+    }
+
+    pub fn offset(&self) -> i64 {
+        i64::from(self.page) * i64::from(self.page_size)
+    }
+}
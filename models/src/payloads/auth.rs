@@ -151,6 +151,98 @@ impl RegisterWorkspacePayload {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteHandlerPayload {
+    pub email: String,
+    pub user_name: String,
+    pub handler_role: crate::entities::HandlerRole,
+}
+
+impl InviteHandlerPayload {
+    pub fn validate(&self) -> Result<(), crate::auth::AuthError> {
+        RegisterWorkspacePayload::validate_user_name(&self.user_name)?;
+        RegisterWorkspacePayload::validate_email(&self.email)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AcceptInvitePayload {
+    pub token: String,
+    pub password: String,
+    pub confirm_password: String,
+}
+
+impl std::fmt::Debug for AcceptInvitePayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcceptInvitePayload")
+            .field("token", &"***")
+            .field("password", &"***")
+            .field("confirm_password", &"***")
+            .finish()
+    }
+}
+
+impl AcceptInvitePayload {
+    pub fn validate(&self) -> Result<(), crate::auth::AuthError> {
+        if self.token.trim().is_empty() {
+            return Err(crate::auth::AuthError::InvalidInput(
+                "The invite link is incomplete.".to_string(),
+            ));
+        }
+        RegisterWorkspacePayload::validate_password(&self.password)?;
+        RegisterWorkspacePayload::validate_confirm_password(
+            &self.password,
+            &self.confirm_password,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestPasswordResetPayload {
+    pub email: String,
+}
+
+impl RequestPasswordResetPayload {
+    pub fn validate(&self) -> Result<(), crate::auth::AuthError> {
+        RegisterWorkspacePayload::validate_email(&self.email)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ResetPasswordPayload {
+    pub token: String,
+    pub password: String,
+    pub confirm_password: String,
+}
+
+impl std::fmt::Debug for ResetPasswordPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResetPasswordPayload")
+            .field("token", &"***")
+            .field("password", &"***")
+            .field("confirm_password", &"***")
+            .finish()
+    }
+}
+
+impl ResetPasswordPayload {
+    pub fn validate(&self) -> Result<(), crate::auth::AuthError> {
+        if self.token.trim().is_empty() {
+            return Err(crate::auth::AuthError::InvalidInput(
+                "The reset link is incomplete.".to_string(),
+            ));
+        }
+        RegisterWorkspacePayload::validate_password(&self.password)?;
+        RegisterWorkspacePayload::validate_confirm_password(
+            &self.password,
+            &self.confirm_password,
+        )?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,4 +256,30 @@ mod tests {
         assert!(RegisterWorkspacePayload::validate_workspace_handle("admin").is_err()); // Reserved
         assert!(RegisterWorkspacePayload::validate_workspace_handle("api").is_err()); // Reserved
     }
+
+    #[test]
+    fn test_validate_accept_invite() {
+        let accept = |token: &str, password: &str, confirm: &str| AcceptInvitePayload {
+            token: token.to_string(),
+            password: password.to_string(),
+            confirm_password: confirm.to_string(),
+        };
+
+        assert!(
+            accept("abc123", "hunter2hunter2", "hunter2hunter2")
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            accept("", "hunter2hunter2", "hunter2hunter2")
+                .validate()
+                .is_err()
+        ); // Missing token
+        assert!(accept("abc123", "short", "short").validate().is_err()); // Too short
+        assert!(
+            accept("abc123", "hunter2hunter2", "hunter3hunter3")
+                .validate()
+                .is_err()
+        ); // Mismatch
+    }
 }
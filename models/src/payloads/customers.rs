@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Reduces a phone number to its digits, keeping a leading `+`, so "0803 123-4567" and
+/// "08031234567" are the same customer. Returns `None` for anything that is not a plausible
+/// phone number, which is also how the POS scanner tells phones from other input.
+pub fn normalize_phone(input: &str) -> Option<String> {
+    let input = input.trim();
+    if !input
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '(' | ')' | '.'))
+    {
+        return None;
+    }
+
+    let digits: String = input.chars().filter(char::is_ascii_digit).collect();
+    if !(7..=15).contains(&digits.len()) {
+        return None;
+    }
+    if input.starts_with('+') {
+        Some(format!("+{digits}"))
+    } else {
+        Some(digits)
+    }
+}
+
+/// Create or full replacement of a customer's details.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_customer"))]
+pub struct CustomerPayload {
+    #[validate(length(
+        min = 2,
+        max = 255,
+        message = "Customer name must be at least 2 characters long."
+    ))]
+    pub name: String,
+    pub phone: Option<String>,
+    #[validate(email(message = "Enter a valid email address."))]
+    pub email: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl CustomerPayload {
+    /// Trims every field, turns blanks into `None` and normalizes the phone number.
+    pub fn normalized(mut self) -> Self {
+        let clean = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        self.name = self.name.trim().to_string();
+        self.phone = clean(self.phone).map(|p| normalize_phone(&p).unwrap_or(p));
+        self.email = clean(self.email).map(|e| e.to_lowercase());
+        self.notes = clean(self.notes);
+        self
+    }
+}
+
+fn validate_customer(payload: &CustomerPayload) -> Result<(), ValidationError> {
+    if let Some(phone) = payload.phone.as_deref()
+        && normalize_phone(phone).is_none()
+    {
+        return Err(ValidationError::new("invalid_phone")
+            .with_message("Enter a phone number of 7 to 15 digits.".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(
+            normalize_phone("0803 123-4567").as_deref(),
+            Some("08031234567")
+        );
+        assert_eq!(
+            normalize_phone("+234 (803) 123.4567").as_deref(),
+            Some("+2348031234567")
+        );
+        assert_eq!(normalize_phone("12345"), None); // Too short
+        assert_eq!(normalize_phone("IPHONE-15"), None); // Not a phone
+        assert_eq!(normalize_phone("3567891045123456"), None); // Too long
+    }
+
+    #[test]
+    fn test_validate_customer() {
+        let customer = |phone: Option<&str>, email: Option<&str>| {
+            CustomerPayload {
+                name: "Ada Obi".to_string(),
+                phone: phone.map(String::from),
+                email: email.map(String::from),
+                notes: None,
+            }
+            .normalized()
+        };
+
+        assert!(
+            customer(Some("0803 123 4567"), Some("ada@example.com"))
+                .validate()
+                .is_ok()
+        );
+        assert!(customer(Some("  "), Some("")).validate().is_ok()); // Blanks are dropped
+        assert!(customer(Some("call me"), None).validate().is_err());
+        assert!(customer(None, Some("not-an-email")).validate().is_err());
+    }
+}
@@ -1,5 +1,9 @@
+use super::sales::MAX_MONEY_AMOUNT;
+use crate::entities::StockAdjustmentReason;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct AddProductPayload {
@@ -10,4 +14,335 @@ pub struct AddProductPayload {
     pub category: Option<String>,
     #[serde(default)]
     pub is_tracked: bool,
+    /// Months of cover for tracked units sold from now on; 0 means none.
+    #[serde(default)]
+    #[validate(range(
+        min = 0,
+        max = 120,
+        message = "Warranty must be between 0 and 120 months."
+    ))]
+    pub warranty_months: i32,
+}
+
+/// Upper bound on `ProductListQuery::page_size`.
+pub const MAX_PRODUCT_PAGE_SIZE: u32 = 200;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProductSort {
+    #[default]
+    NameAsc,
+    NameDesc,
+    Newest,
+    Oldest,
+    Category,
+}
+
+impl ProductSort {
+    pub const ALL: [Self; 5] = [
+        Self::NameAsc,
+        Self::NameDesc,
+        Self::Newest,
+        Self::Oldest,
+        Self::Category,
+    ];
+
+    /// Stable key matched by the `ORDER BY CASE` branches in `db_ops::list_products`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::NameAsc => "name_asc",
+            Self::NameDesc => "name_desc",
+            Self::Newest => "newest",
+            Self::Oldest => "oldest",
+            Self::Category => "category",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::NameAsc => "Name (A-Z)",
+            Self::NameDesc => "Name (Z-A)",
+            Self::Newest => "Newest First",
+            Self::Oldest => "Oldest First",
+            Self::Category => "Category",
+        }
+    }
+}
+
+/// Filters, sort and page for `GetProducts`. Page numbers start at 0.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProductListQuery {
+    pub search: Option<String>,
+    pub category: Option<String>,
+    /// `Some(true)` for tracked only, `Some(false)` for bulk only.
+    pub is_tracked: Option<bool>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub sort: ProductSort,
+    #[serde(default)]
+    pub page: u32,
+    pub page_size: u32,
+}
+
+impl Default for ProductListQuery {
+    fn default() -> Self {
+        Self {
+            search: None,
+            category: None,
+            is_tracked: None,
+            archived: false,
+            sort: ProductSort::default(),
+            page: 0,
+            page_size: 25,
+        }
+    }
+}
+
+impl ProductListQuery {
+    /// Blank filters become `None` and the page size is clamped to `1..=MAX_PRODUCT_PAGE_SIZE`.
+    pub fn normalized(mut self) -> Self {
+        let clean = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        self.search = clean(self.search);
+        self.category = clean(self.category);
+        self.page_size = self.page_size.clamp(1, MAX_PRODUCT_PAGE_SIZE);
+        self
+    }
+
+    pub fn offset(&self) -> i64 {
+        i64::from(self.page) * i64::from(self.page_size)
+    }
+}
+
+/// Full replacement of a product's catalog fields.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UpdateProductPayload {
+    pub product_id: Uuid,
+    #[validate(length(min = 2, message = "Product name must be at least 2 characters long."))]
+    pub name: String,
+
+    pub sku: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub is_tracked: bool,
+    /// Months of cover for tracked units sold from now on; 0 means none.
+    #[serde(default)]
+    #[validate(range(
+        min = 0,
+        max = 120,
+        message = "Warranty must be between 0 and 120 months."
+    ))]
+    pub warranty_months: i32,
+}
+
+/// A scanned shipment of serialized units for one product.
+/// Each entry of `units` is the full set of serials (IMEI 1, IMEI 2, S/N...) printed on one box.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_tracked_intake"))]
+pub struct ReceiveTrackedUnitsPayload {
+    pub product_id: Uuid,
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "A batch must contain between 1 and 500 units."
+    ))]
+    pub units: Vec<Vec<String>>,
+    pub acquisition_cost: Decimal,
+    pub target_msrp: Decimal,
+    #[serde(default)]
+    pub supplier_id: Option<Uuid>,
+    /// Books the delivery against this purchase order line, whose supplier it takes.
+    #[serde(default)]
+    pub purchase_order_line_id: Option<Uuid>,
+}
+
+impl ReceiveTrackedUnitsPayload {
+    /// Trims every serial and drops blank entries left behind by the scanner.
+    pub fn normalized_units(&self) -> Vec<Vec<String>> {
+        self.units
+            .iter()
+            .map(|unit| {
+                unit.iter()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn validate_tracked_intake(payload: &ReceiveTrackedUnitsPayload) -> Result<(), ValidationError> {
+    if payload.acquisition_cost.is_sign_negative() || payload.target_msrp.is_sign_negative() {
+        return Err(ValidationError::new("negative_price")
+            .with_message("Acquisition cost and MSRP cannot be negative.".into()));
+    }
+    if payload.acquisition_cost > MAX_MONEY_AMOUNT || payload.target_msrp > MAX_MONEY_AMOUNT {
+        return Err(ValidationError::new("price_too_large")
+            .with_message("Acquisition cost and MSRP are too large.".into()));
+    }
+
+    if payload.normalized_units().iter().any(|u| u.is_empty()) {
+        return Err(ValidationError::new("empty_unit")
+            .with_message("Every unit needs at least one serial number.".into()));
+    }
+
+    Ok(())
+}
+
+/// Bulk stock arriving from a supplier; folds `unit_cost` into the weighted average cost.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_bulk_receipt"))]
+pub struct ReceiveBulkStockPayload {
+    pub product_id: Uuid,
+    #[validate(range(min = 1, message = "Received quantity must be at least 1."))]
+    pub quantity: i32,
+    pub unit_cost: Decimal,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub supplier_id: Option<Uuid>,
+    /// Books the delivery against this purchase order line, whose supplier it takes.
+    #[serde(default)]
+    pub purchase_order_line_id: Option<Uuid>,
+}
+
+fn validate_bulk_receipt(payload: &ReceiveBulkStockPayload) -> Result<(), ValidationError> {
+    if payload.unit_cost.is_sign_negative() {
+        return Err(ValidationError::new("negative_price")
+            .with_message("Unit cost cannot be negative.".into()));
+    }
+    if payload.unit_cost > MAX_MONEY_AMOUNT {
+        return Err(
+            ValidationError::new("price_too_large").with_message("Unit cost is too large.".into())
+        );
+    }
+    Ok(())
+}
+
+/// Manual correction of bulk stock. Positive deltas add units, negative deltas remove them.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_bulk_adjustment"))]
+pub struct AdjustBulkStockPayload {
+    pub product_id: Uuid,
+    pub quantity_delta: i32,
+    pub reason: StockAdjustmentReason,
+    pub note: Option<String>,
+}
+
+fn validate_bulk_adjustment(payload: &AdjustBulkStockPayload) -> Result<(), ValidationError> {
+    if payload.quantity_delta == 0 {
+        return Err(ValidationError::new("zero_delta")
+            .with_message("Adjustment quantity cannot be zero.".into()));
+    }
+    if payload.reason == StockAdjustmentReason::Received {
+        return Err(ValidationError::new("invalid_reason")
+            .with_message("Use 'Receive Stock' to record incoming shipments.".into()));
+    }
+    if !StockAdjustmentReason::MANUAL.contains(&payload.reason) {
+        return Err(ValidationError::new("invalid_reason").with_message(
+            "This reason is recorded by the system and cannot be used here.".into(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intake(units: Vec<Vec<&str>>, cost: i64) -> ReceiveTrackedUnitsPayload {
+        ReceiveTrackedUnitsPayload {
+            product_id: Uuid::new_v4(),
+            units: units
+                .into_iter()
+                .map(|u| u.into_iter().map(String::from).collect())
+                .collect(),
+            acquisition_cost: Decimal::new(cost, 2),
+            target_msrp: Decimal::new(99900, 2),
+            supplier_id: None,
+            purchase_order_line_id: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_tracked_intake() {
+        assert!(
+            intake(vec![vec!["356938035643809", "356938035643817"]], 65000)
+                .validate()
+                .is_ok()
+        );
+        assert!(intake(vec![], 65000).validate().is_err()); // Empty batch
+        assert!(intake(vec![vec!["  ", ""]], 65000).validate().is_err()); // Blank serials only
+        assert!(
+            intake(vec![vec!["356938035643809"]], -1)
+                .validate()
+                .is_err()
+        ); // Negative cost
+        assert!(
+            intake(vec![vec!["356938035643809"]], 1_000_000_000_000)
+                .validate()
+                .is_err()
+        ); // Cost over NUMERIC(12, 2)
+    }
+
+    #[test]
+    fn test_validate_bulk_receipt() {
+        let receipt = |quantity, cost_cents| ReceiveBulkStockPayload {
+            product_id: Uuid::new_v4(),
+            quantity,
+            unit_cost: Decimal::new(cost_cents, 2),
+            note: None,
+            supplier_id: None,
+            purchase_order_line_id: None,
+        };
+        assert!(receipt(10, 250).validate().is_ok());
+        assert!(receipt(0, 250).validate().is_err()); // Nothing received
+        assert!(receipt(10, -1).validate().is_err()); // Negative cost
+        assert!(receipt(10, 1_000_000_000_000).validate().is_err()); // Cost over NUMERIC(12, 2)
+    }
+
+    #[test]
+    fn test_product_list_query_normalized() {
+        let query = ProductListQuery {
+            search: Some("  ".to_string()),
+            category: Some(" Cables ".to_string()),
+            page: 3,
+            page_size: 10_000,
+            ..Default::default()
+        }
+        .normalized();
+
+        assert_eq!(query.search, None);
+        assert_eq!(query.category.as_deref(), Some("Cables"));
+        assert_eq!(query.page_size, MAX_PRODUCT_PAGE_SIZE);
+        assert_eq!(query.offset(), 600);
+    }
+
+    #[test]
+    fn test_validate_bulk_adjustment() {
+        let adjust = |quantity_delta, reason| AdjustBulkStockPayload {
+            product_id: Uuid::new_v4(),
+            quantity_delta,
+            reason,
+            note: None,
+        };
+        assert!(
+            adjust(-3, StockAdjustmentReason::Damaged)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            adjust(0, StockAdjustmentReason::Damaged)
+                .validate()
+                .is_err()
+        ); // No-op
+        assert!(
+            adjust(5, StockAdjustmentReason::Received)
+                .validate()
+                .is_err()
+        ); // Receipts have their own action
+        assert!(
+            adjust(5, StockAdjustmentReason::Returned)
+                .validate()
+                .is_err()
+        ); // Returns are booked by ProcessReturn
+    }
 }
@@ -1,5 +1,17 @@
+mod audit;
 mod auth;
+mod customers;
 mod inventory;
+mod purchase_orders;
+mod reservations;
+mod sales;
+mod suppliers;
 
+pub use audit::*;
 pub use auth::*;
+pub use customers::*;
 pub use inventory::*;
+pub use purchase_orders::*;
+pub use reservations::*;
+pub use sales::*;
+pub use suppliers::*;
@@ -0,0 +1,88 @@
+use super::sales::MAX_MONEY_AMOUNT;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// One product to order, at the cost agreed with the supplier.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PurchaseOrderLineInput {
+    pub product_id: Uuid,
+    pub expected_quantity: i32,
+    pub unit_cost: Decimal,
+}
+
+/// A new purchase order, created as a draft.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_purchase_order"))]
+pub struct CreatePurchaseOrderPayload {
+    pub supplier_id: Uuid,
+    pub notes: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "A purchase order needs between 1 and 200 lines."
+    ))]
+    pub lines: Vec<PurchaseOrderLineInput>,
+}
+
+fn validate_purchase_order(payload: &CreatePurchaseOrderPayload) -> Result<(), ValidationError> {
+    let mut seen_products = HashSet::new();
+
+    for line in &payload.lines {
+        if line.expected_quantity < 1 {
+            return Err(ValidationError::new("invalid_quantity")
+                .with_message("Every line needs a quantity of at least 1.".into()));
+        }
+        if line.unit_cost.is_sign_negative() {
+            return Err(ValidationError::new("negative_price")
+                .with_message("Unit cost cannot be negative.".into()));
+        }
+        if line.unit_cost > MAX_MONEY_AMOUNT {
+            return Err(ValidationError::new("price_too_large")
+                .with_message("Unit cost is too large.".into()));
+        }
+        if !seen_products.insert(line.product_id) {
+            return Err(ValidationError::new("duplicate_product")
+                .with_message("Each product can only appear once per order.".into()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_purchase_order() {
+        let product_id = Uuid::new_v4();
+        let line = |product_id, expected_quantity, cost_cents| PurchaseOrderLineInput {
+            product_id,
+            expected_quantity,
+            unit_cost: Decimal::new(cost_cents, 2),
+        };
+        let order = |lines| CreatePurchaseOrderPayload {
+            supplier_id: Uuid::new_v4(),
+            notes: None,
+            lines,
+        };
+
+        assert!(order(vec![line(product_id, 10, 250)]).validate().is_ok());
+        assert!(order(vec![]).validate().is_err()); // No lines
+        assert!(order(vec![line(product_id, 0, 250)]).validate().is_err());
+        assert!(order(vec![line(product_id, 1, -1)]).validate().is_err());
+        assert!(
+            order(vec![line(product_id, 1, 1_000_000_000_000)])
+                .validate()
+                .is_err()
+        ); // Cost over NUMERIC(12, 2)
+        assert!(
+            order(vec![line(product_id, 1, 250), line(product_id, 2, 250)])
+                .validate()
+                .is_err()
+        ); // Same product twice
+    }
+}
@@ -0,0 +1,78 @@
+use super::sales::{SaleLineInput, lines_total, validate_lines};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_reservation"))]
+pub struct CreateReservationPayload {
+    #[validate(length(min = 2, max = 255, message = "Enter the customer's name."))]
+    pub customer_name: String,
+    #[validate(length(max = 50, message = "Phone number is too long."))]
+    pub customer_phone: Option<String>,
+    /// Taken now and applied to the price when the reservation is converted.
+    pub deposit: Decimal,
+    pub expires_at: DateTime<Utc>,
+    #[validate(length(min = 1, message = "Add at least one item to reserve."))]
+    pub lines: Vec<SaleLineInput>,
+}
+
+impl CreateReservationPayload {
+    pub fn total_amount(&self) -> Option<Decimal> {
+        lines_total(&self.lines)
+    }
+}
+
+fn validate_reservation(payload: &CreateReservationPayload) -> Result<(), ValidationError> {
+    validate_lines(&payload.lines)?;
+
+    if payload.deposit.is_sign_negative() {
+        return Err(ValidationError::new("negative_deposit")
+            .with_message("The deposit cannot be negative.".into()));
+    }
+    if Some(payload.deposit) > payload.total_amount() {
+        return Err(ValidationError::new("deposit_exceeds_total")
+            .with_message("The deposit cannot be more than the reserved items cost.".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_validate_reservation() {
+        let line = |tracked_unit_id, quantity| SaleLineInput {
+            product_id: Uuid::new_v4(),
+            tracked_unit_id,
+            quantity,
+            unit_price: Decimal::new(50000, 2),
+        };
+        let payload = |lines, deposit_cents| CreateReservationPayload {
+            customer_name: "Ada Obi".to_string(),
+            customer_phone: None,
+            deposit: Decimal::new(deposit_cents, 2),
+            expires_at: Utc::now(),
+            lines,
+        };
+
+        assert!(
+            payload(vec![line(Some(Uuid::new_v4()), 1)], 10000)
+                .validate()
+                .is_ok()
+        );
+        assert!(payload(vec![line(None, 2)], 100000).validate().is_ok()); // Paid in full
+        assert!(payload(vec![], 0).validate().is_err()); // Nothing held
+        assert!(payload(vec![line(None, 2)], 100001).validate().is_err()); // Deposit over total
+        assert!(payload(vec![line(None, 1)], -1).validate().is_err()); // Negative deposit
+        assert!(
+            payload(vec![line(Some(Uuid::new_v4()), 2)], 0)
+                .validate()
+                .is_err()
+        ); // Tracked quantity > 1
+    }
+}
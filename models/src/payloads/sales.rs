@@ -0,0 +1,229 @@
+use crate::entities::{RefundMethod, ReturnCondition};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// The largest amount a `NUMERIC(12, 2)` money column can store.
+pub const MAX_MONEY_AMOUNT: Decimal = Decimal::from_parts(3_567_587_327, 232, 0, false, 2);
+
+/// One cart row. Tracked lines name the exact unit being sold and always have a quantity of 1.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SaleLineInput {
+    pub product_id: Uuid,
+    pub tracked_unit_id: Option<Uuid>,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_sale"))]
+pub struct CompleteSalePayload {
+    #[validate(length(min = 1, message = "The cart is empty."))]
+    pub lines: Vec<SaleLineInput>,
+    /// Left `None` for an anonymous sale.
+    #[serde(default)]
+    pub customer_id: Option<Uuid>,
+}
+
+impl CompleteSalePayload {
+    pub fn total_amount(&self) -> Option<Decimal> {
+        lines_total(&self.lines)
+    }
+}
+
+/// Sum of every line's price times quantity, or `None` if it overflows.
+pub fn lines_total(lines: &[SaleLineInput]) -> Option<Decimal> {
+    lines.iter().try_fold(Decimal::ZERO, |total, l| {
+        l.unit_price
+            .checked_mul(Decimal::from(l.quantity))
+            .and_then(|line_total| total.checked_add(line_total))
+    })
+}
+
+fn validate_sale(payload: &CompleteSalePayload) -> Result<(), ValidationError> {
+    validate_lines(&payload.lines)
+}
+
+/// Checks shared by every set of cart lines, whether sold now or put on hold.
+pub(crate) fn validate_lines(lines: &[SaleLineInput]) -> Result<(), ValidationError> {
+    let mut seen_units = HashSet::new();
+
+    for line in lines {
+        if line.quantity < 1 {
+            return Err(ValidationError::new("invalid_quantity")
+                .with_message("Every line needs a quantity of at least 1.".into()));
+        }
+        if line.unit_price.is_sign_negative() {
+            return Err(ValidationError::new("negative_price")
+                .with_message("Unit price cannot be negative.".into()));
+        }
+        if line.unit_price > MAX_MONEY_AMOUNT {
+            return Err(ValidationError::new("price_too_large")
+                .with_message("Unit price is too large.".into()));
+        }
+        if let Some(unit_id) = line.tracked_unit_id {
+            if line.quantity != 1 {
+                return Err(ValidationError::new("invalid_quantity")
+                    .with_message("A tracked unit can only be sold once per line.".into()));
+            }
+            if !seen_units.insert(unit_id) {
+                return Err(ValidationError::new("duplicate_unit")
+                    .with_message("The same unit was scanned twice.".into()));
+            }
+        }
+    }
+    if lines_total(lines).is_none_or(|total| total > MAX_MONEY_AMOUNT) {
+        return Err(ValidationError::new("total_too_large")
+            .with_message("The total is more than a single sale can record.".into()));
+    }
+
+    Ok(())
+}
+
+/// Goods coming back from one line of the original sale.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReturnLineInput {
+    pub sale_line_id: Uuid,
+    pub quantity: i32,
+    pub condition: ReturnCondition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_return"))]
+pub struct ProcessReturnPayload {
+    pub sale_id: Uuid,
+    #[validate(length(min = 1, message = "Select at least one item to return."))]
+    pub lines: Vec<ReturnLineInput>,
+    /// May be less than the value of the returned goods, e.g. after a restocking fee.
+    pub refund_amount: Decimal,
+    pub refund_method: RefundMethod,
+    pub reason: Option<String>,
+}
+
+fn validate_return(payload: &ProcessReturnPayload) -> Result<(), ValidationError> {
+    let mut seen_lines = HashSet::new();
+
+    for line in &payload.lines {
+        if line.quantity < 1 {
+            return Err(ValidationError::new("invalid_quantity")
+                .with_message("Every returned line needs a quantity of at least 1.".into()));
+        }
+        if !seen_lines.insert(line.sale_line_id) {
+            return Err(ValidationError::new("duplicate_line")
+                .with_message("The same sale line was listed twice.".into()));
+        }
+    }
+    if payload.refund_amount.is_sign_negative() {
+        return Err(ValidationError::new("negative_refund")
+            .with_message("The refund cannot be negative.".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(tracked_unit_id: Option<Uuid>, quantity: i32, cents: i64) -> SaleLineInput {
+        SaleLineInput {
+            product_id: Uuid::new_v4(),
+            tracked_unit_id,
+            quantity,
+            unit_price: Decimal::new(cents, 2),
+        }
+    }
+
+    #[test]
+    fn test_validate_sale() {
+        let unit = Some(Uuid::new_v4());
+        let sale = |lines| CompleteSalePayload {
+            lines,
+            customer_id: None,
+        };
+
+        assert!(
+            sale(vec![line(unit, 1, 99900), line(None, 3, 1250)])
+                .validate()
+                .is_ok()
+        );
+        assert!(sale(vec![]).validate().is_err()); // Empty cart
+        assert!(sale(vec![line(None, 0, 1250)]).validate().is_err()); // Zero quantity
+        assert!(sale(vec![line(unit, 2, 99900)]).validate().is_err()); // Tracked quantity > 1
+        assert!(
+            sale(vec![line(unit, 1, 99900), line(unit, 1, 99900)])
+                .validate()
+                .is_err()
+        ); // Same unit twice
+        assert!(sale(vec![line(None, 1, -1)]).validate().is_err()); // Negative price
+        assert!(
+            sale(vec![line(None, 1, 999_999_999_999)])
+                .validate()
+                .is_ok()
+        ); // Largest price the column holds
+        assert!(
+            sale(vec![line(None, 1, 1_000_000_000_000)])
+                .validate()
+                .is_err()
+        ); // Price over NUMERIC(12, 2)
+        assert!(
+            sale(vec![line(None, i32::MAX, 999_999_999_999)])
+                .validate()
+                .is_err()
+        ); // Line total over NUMERIC(12, 2)
+        assert!(
+            sale(vec![line(None, 1, 999_999_999_999), line(None, 1, 1)])
+                .validate()
+                .is_err()
+        ); // Sum over NUMERIC(12, 2)
+    }
+
+    #[test]
+    fn test_total_amount() {
+        let sale = CompleteSalePayload {
+            lines: vec![line(None, 3, 1250), line(Some(Uuid::new_v4()), 1, 99900)],
+            customer_id: None,
+        };
+        assert_eq!(sale.total_amount(), Some(Decimal::new(103650, 2)));
+    }
+
+    #[test]
+    fn test_lines_total_overflow() {
+        let huge = SaleLineInput {
+            unit_price: Decimal::MAX,
+            ..line(None, 2, 0)
+        };
+        assert_eq!(lines_total(&[huge]), None);
+        assert_eq!(MAX_MONEY_AMOUNT, Decimal::new(999_999_999_999, 2));
+    }
+
+    #[test]
+    fn test_validate_return() {
+        let line_id = Uuid::new_v4();
+        let line = |sale_line_id, quantity| ReturnLineInput {
+            sale_line_id,
+            quantity,
+            condition: ReturnCondition::Resellable,
+        };
+        let payload = |lines, cents| ProcessReturnPayload {
+            sale_id: Uuid::new_v4(),
+            lines,
+            refund_amount: Decimal::new(cents, 2),
+            refund_method: RefundMethod::Cash,
+            reason: None,
+        };
+
+        assert!(payload(vec![line(line_id, 2)], 2500).validate().is_ok());
+        assert!(payload(vec![line(line_id, 1)], 0).validate().is_ok()); // Exchange, no refund
+        assert!(payload(vec![], 0).validate().is_err()); // Nothing returned
+        assert!(payload(vec![line(line_id, 0)], 0).validate().is_err()); // Zero quantity
+        assert!(
+            payload(vec![line(line_id, 1), line(line_id, 1)], 0)
+                .validate()
+                .is_err()
+        ); // Same line twice
+        assert!(payload(vec![line(line_id, 1)], -1).validate().is_err()); // Negative refund
+    }
+}
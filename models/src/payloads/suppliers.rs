@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Create or full replacement of a supplier's details.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct SupplierPayload {
+    #[validate(length(
+        min = 2,
+        max = 255,
+        message = "Supplier name must be at least 2 characters long."
+    ))]
+    pub name: String,
+    #[validate(length(max = 255, message = "Contact name is too long."))]
+    pub contact_name: Option<String>,
+    #[validate(length(max = 50, message = "Phone number is too long."))]
+    pub phone: Option<String>,
+    #[validate(email(message = "Enter a valid email address."))]
+    pub email: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl SupplierPayload {
+    /// Trims every field and turns blanks into `None`.
+    pub fn normalized(mut self) -> Self {
+        let clean = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        self.name = self.name.trim().to_string();
+        self.contact_name = clean(self.contact_name);
+        self.phone = clean(self.phone);
+        self.email = clean(self.email).map(|e| e.to_lowercase());
+        self.notes = clean(self.notes);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_supplier() {
+        let supplier = |name: &str, email: Option<&str>| {
+            SupplierPayload {
+                name: name.to_string(),
+                contact_name: Some("  ".to_string()),
+                phone: None,
+                email: email.map(String::from),
+                notes: None,
+            }
+            .normalized()
+        };
+
+        let valid = supplier(" Acme Distribution ", Some("Sales@Acme.example"));
+        assert!(valid.validate().is_ok());
+        assert_eq!(valid.name, "Acme Distribution");
+        assert_eq!(valid.contact_name, None); // Blanks are dropped
+        assert_eq!(valid.email.as_deref(), Some("sales@acme.example"));
+
+        assert!(supplier("A", None).validate().is_err()); // Name too short
+        assert!(supplier("Acme", Some("sales")).validate().is_err());
+    }
+}
@@ -29,7 +29,10 @@ pub struct SystemState {
     pub toasts: Vec<Arc<dyn SystemNotification + Send + Sync>>,
     pub modal: Option<Arc<dyn SystemNotification + Send + Sync>>,
     pub auth_state: AuthState,
+    /// Subdomain handle the app was loaded on, resolved by the server.
     pub active_workspace_handle: Option<String>,
+    /// Public base URL, for building links to other workspaces.
+    pub base_url: Option<String>,
 }
 
 impl SystemState {
@@ -4,7 +4,7 @@ use leptos::prelude::*;
 use leptos_meta::{provide_meta_context, MetaTags, Stylesheet, Title};
 use leptos_router::{
     components::{ParentRoute, Route, Router, Routes},
-    StaticSegment,
+    ParamSegment, StaticSegment,
 };
 use models::system_state::SystemState;
 pub fn shell(options: LeptosOptions) -> impl IntoView {
@@ -30,9 +30,22 @@ pub fn App() -> impl IntoView {
     provide_meta_context();
 
     let auth_resource = Resource::new(|| (), |_| async move { actions::validate_session().await });
+    let workspace_resource = Resource::new(
+        || (),
+        |_| async move { actions::get_workspace_context().await },
+    );
     let state = RwSignal::new(SystemState::default());
     provide_context(state);
 
+    Effect::new(move |_| {
+        if let Some(Ok(context)) = workspace_resource.get() {
+            state.update(|s| {
+                s.active_workspace_handle = context.workspace.map(|w| w.handle);
+                s.base_url = Some(context.base_url);
+            });
+        }
+    });
+
     Effect::new(move |_| {
         auth_resource.with(|res| {
             if let Some(outcome) = res {
@@ -67,6 +80,18 @@ pub fn App() -> impl IntoView {
                             <ParentRoute path=StaticSegment("auth") view=AuthLayout>
                                 <Route path=StaticSegment("login") view=LoginPage />
                                 <Route path=StaticSegment("register") view=RegisterPage />
+                                <Route
+                                    path=(StaticSegment("invite"), ParamSegment("token"))
+                                    view=AcceptInvitePage
+                                />
+                                <Route
+                                    path=StaticSegment("forgot-password")
+                                    view=ForgotPasswordPage
+                                />
+                                <Route
+                                    path=(StaticSegment("reset-password"), ParamSegment("token"))
+                                    view=ResetPasswordPage
+                                />
                             </ParentRoute>
                             <ParentRoute
                                 path=StaticSegment("system")
@@ -82,6 +107,13 @@ pub fn App() -> impl IntoView {
                                 <Route path=StaticSegment("catalog") view=CatalogPage />
                                 <Route path=StaticSegment("inventory") view=InventoryPage />
                                 <Route path=StaticSegment("pos") view=PosPage />
+                                <Route path=StaticSegment("returns") view=ReturnsPage />
+                                <Route path=StaticSegment("reservations") view=ReservationsPage />
+                                <Route path=StaticSegment("customers") view=CustomersPage />
+                                <Route path=StaticSegment("suppliers") view=SuppliersPage />
+                                <Route path=StaticSegment("staff") view=StaffPage />
+                                <Route path=StaticSegment("security") view=SecurityPage />
+                                <Route path=StaticSegment("audit") view=AuditLogPage />
                             </ParentRoute>
                         </Routes>
                     </main>
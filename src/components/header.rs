@@ -1,3 +1,4 @@
+use actions::Permission;
 use leptos::prelude::*;
 use models::system_state::{AuthState, SystemState};
 
@@ -18,7 +19,14 @@ pub fn Header() -> impl IntoView {
 
                 // Middle: Main Navigation
                 {move || match state.get().auth_state {
-                    AuthState::Authenticated(_) => {
+                    AuthState::Authenticated(user) => {
+                        let can_manage_staff = Permission::ManageStaff
+                            .is_granted_to(user.handler_role);
+                        let can_refund = Permission::Refund.is_granted_to(user.handler_role);
+                        let can_receive_stock = Permission::ReceiveStock
+                            .is_granted_to(user.handler_role);
+                        let can_view_audit_log = Permission::ViewAuditLog
+                            .is_granted_to(user.handler_role);
                         view! {
                             <nav class="header-nav flex gap-6 text-sm font-medium">
                                 <a
@@ -48,6 +56,26 @@ pub fn Header() -> impl IntoView {
                                     </svg>
                                     "POS"
                                 </a>
+                                <a
+                                    href="/system/reservations"
+                                    class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
+                                >
+                                    "Reservations"
+                                </a>
+                                <a
+                                    href="/system/customers"
+                                    class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
+                                >
+                                    "Customers"
+                                </a>
+                                <Show when=move || can_refund>
+                                    <a
+                                        href="/system/returns"
+                                        class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
+                                    >
+                                        "Returns"
+                                    </a>
+                                </Show>
                                 <a
                                     href="/system/inventory"
                                     class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
@@ -60,6 +88,36 @@ pub fn Header() -> impl IntoView {
                                 >
                                     "Catalog"
                                 </a>
+                                <Show when=move || can_receive_stock>
+                                    <a
+                                        href="/system/suppliers"
+                                        class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
+                                    >
+                                        "Suppliers"
+                                    </a>
+                                </Show>
+                                <Show when=move || can_manage_staff>
+                                    <a
+                                        href="/system/staff"
+                                        class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
+                                    >
+                                        "Staff"
+                                    </a>
+                                </Show>
+                                <Show when=move || can_view_audit_log>
+                                    <a
+                                        href="/system/audit"
+                                        class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
+                                    >
+                                        "Audit Log"
+                                    </a>
+                                </Show>
+                                <a
+                                    href="/system/security"
+                                    class="text-[var(--text-secondary)] hover:text-[var(--text-primary)] transition-colors"
+                                >
+                                    "Security"
+                                </a>
                             </nav>
                         }
                             .into_any()
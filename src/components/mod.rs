@@ -4,6 +4,7 @@ pub mod header;
 pub mod loading;
 pub mod notifier;
 pub mod themed_input;
+pub mod totp_setup;
 
 pub use alert_modal::*;
 pub use guard::*;
@@ -11,3 +12,4 @@ pub use header::Header;
 pub use loading::*;
 pub use notifier::*;
 pub use themed_input::*;
+pub use totp_setup::*;
@@ -0,0 +1,113 @@
+use actions::two_factor::{begin_totp_enrolment, ConfirmTotpEnrolment};
+use leptos::prelude::*;
+use models::entities::TotpActivation;
+use models::system_state::SystemState;
+use std::sync::Arc;
+
+/// Walks a handler through adding an authenticator app: shows a fresh secret,
+/// confirms a code from the app, then shows the one-time recovery codes.
+///
+/// `challenge` is set when enrolment is forced during sign-in.
+#[component]
+pub fn TotpSetup(
+    #[prop(optional)] challenge: Option<String>,
+    on_done: Callback<TotpActivation>,
+) -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+
+    let challenge = StoredValue::new(challenge);
+    let enrolment = Resource::new(
+        || (),
+        move |_| async move { begin_totp_enrolment(challenge.get_value()).await },
+    );
+
+    let (code, set_code) = signal(String::new());
+    let confirm_action = ServerAction::<ConfirmTotpEnrolment>::new();
+    let confirm_value = confirm_action.value();
+
+    let on_confirm = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        confirm_action.dispatch(ConfirmTotpEnrolment {
+            code: code.get(),
+            challenge: challenge.get_value(),
+        });
+    };
+
+    Effect::new(move |_| {
+        if let Some(Err(e)) = confirm_value.get() {
+            state.update(|s| s.add_toast(Arc::new(e)));
+        }
+    });
+
+    view! {
+        <div class="totp-setup">
+            {move || {
+                confirm_value
+                    .get()
+                    .and_then(Result::ok)
+                    .map(|activation| {
+                        let codes = activation.recovery_codes.clone();
+                        view! {
+                            <p>
+                                "Two-factor authentication is on. Store these recovery codes somewhere safe; each one signs you in once if you lose your device. They will not be shown again."
+                            </p>
+                            <ul class="totp-recovery-codes">
+                                {codes
+                                    .into_iter()
+                                    .map(|c| view! { <li>{c}</li> })
+                                    .collect_view()}
+                            </ul>
+                            <button
+                                type="button"
+                                class="btn btn-primary btn-full"
+                                on:click=move |_| on_done.run(activation.clone())
+                            >
+                                "I have saved my codes"
+                            </button>
+                        }
+                    })
+            }}
+            <Show when=move || confirm_value.with(|v| !matches!(v, Some(Ok(_))))>
+                <Suspense fallback=|| view! { <p>"Generating secret..."</p> }>
+                    {move || Suspend::new(async move {
+                        match enrolment.await {
+                            Ok(enrolment) => {
+                                view! {
+                                    <p>
+                                        "Add this key to your authenticator app, or open the link on your phone, then enter the 6-digit code it shows."
+                                    </p>
+                                    <code class="totp-secret">{enrolment.secret.clone()}</code>
+                                    <a class="totp-uri" href=enrolment.otpauth_uri.clone()>
+                                        "Open in authenticator app"
+                                    </a>
+                                    <form class="totp-confirm" on:submit=on_confirm>
+                                        <input
+                                            type="text"
+                                            class="input-field"
+                                            inputmode="numeric"
+                                            autocomplete="one-time-code"
+                                            placeholder="123456"
+                                            required
+                                            on:input=move |ev| set_code.set(event_target_value(&ev))
+                                            prop:value=code
+                                        />
+                                        <button
+                                            type="submit"
+                                            class="btn btn-primary"
+                                            disabled=move || confirm_action.pending().get()
+                                        >
+                                            "Confirm"
+                                        </button>
+                                    </form>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                        }
+                    })}
+                </Suspense>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,101 @@
+use actions::staff::AcceptInvite;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use models::AcceptInvitePayload;
+use std::sync::Arc;
+
+/// Landing page for a staff invite link: the invitee picks a password and is signed in.
+#[component]
+pub fn AcceptInvitePage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let navigate = leptos_router::hooks::use_navigate();
+    let params = use_params_map();
+
+    let (password, set_password) = signal(String::new());
+    let (confirm_password, set_confirm_password) = signal(String::new());
+
+    let accept_action = ServerAction::<AcceptInvite>::new();
+    let accept_loading = accept_action.pending();
+    let accept_value = accept_action.value();
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        accept_action.dispatch(AcceptInvite {
+            payload: AcceptInvitePayload {
+                token: params.read().get("token").unwrap_or_default(),
+                password: password.get(),
+                confirm_password: confirm_password.get(),
+            },
+        });
+    };
+
+    Effect::new(move |_| match accept_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(user)) => {
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Welcome Aboard",
+                    format!("You have joined {}.", user.system_name),
+                    NotificationLevel::Success,
+                )));
+                s.set_user(user);
+            });
+            navigate("/system/dashboard", Default::default());
+        }
+        None => {}
+    });
+
+    view! {
+        <main class="auth-page">
+            <div class="grain-bg"></div>
+            <div class="gradient-orb auth-orb orb-1"></div>
+            <div class="gradient-orb auth-orb orb-2"></div>
+
+            <div class="auth-container">
+                <div class="glass-card-premium auth-card">
+                    <header class="auth-header scale-in">
+                        <h1 class="gradient-text glow-text">"Join"</h1>
+                        <p>"Choose a password to activate your Sharp System account"</p>
+                    </header>
+
+                    <form class="auth-form fade-in" on:submit=on_submit>
+                        <div class="form-group">
+                            <label for="password">"Security // New Password"</label>
+                            <input
+                                type="password"
+                                id="password"
+                                placeholder="••••••••"
+                                on:input=move |ev| set_password.set(event_target_value(&ev))
+                                prop:value=password
+                                required
+                            />
+                        </div>
+
+                        <div class="form-group">
+                            <label for="confirm_password">"Security // Confirm Password"</label>
+                            <input
+                                type="password"
+                                id="confirm_password"
+                                placeholder="••••••••"
+                                on:input=move |ev| set_confirm_password.set(event_target_value(&ev))
+                                prop:value=confirm_password
+                                required
+                            />
+                        </div>
+
+                        <button
+                            type="submit"
+                            class="btn btn-primary btn-full glow-primary"
+                            disabled=accept_loading
+                        >
+                            {move || if accept_loading.get() { "ACTIVATING..." } else { "ACCEPT INVITE" }}
+                        </button>
+                    </form>
+                </div>
+            </div>
+        </main>
+    }
+}
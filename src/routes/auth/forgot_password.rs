@@ -0,0 +1,89 @@
+use actions::auth::RequestPasswordReset;
+use leptos::prelude::*;
+use leptos_router::components::A;
+use models::system_state::SystemState;
+use models::RequestPasswordResetPayload;
+use std::sync::Arc;
+
+#[component]
+pub fn ForgotPasswordPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+
+    let (email, set_email) = signal(String::new());
+
+    let request_action = ServerAction::<RequestPasswordReset>::new();
+    let request_loading = request_action.pending();
+    let request_value = request_action.value();
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        request_action.dispatch(RequestPasswordReset {
+            payload: RequestPasswordResetPayload { email: email.get() },
+        });
+    };
+
+    Effect::new(move |_| {
+        if let Some(Err(e)) = request_value.get() {
+            state.update(|s| s.add_toast(Arc::new(e)));
+        }
+    });
+
+    view! {
+        <main class="auth-page">
+            <div class="grain-bg"></div>
+            <div class="gradient-orb auth-orb orb-1"></div>
+            <div class="gradient-orb auth-orb orb-2"></div>
+
+            <div class="auth-container">
+                <div class="glass-card-premium auth-card">
+                    <header class="auth-header scale-in">
+                        <h1 class="gradient-text glow-text">"Reset"</h1>
+                        <p>"We will email you a link to choose a new password"</p>
+                    </header>
+
+                    <Show
+                        when=move || matches!(request_value.get(), Some(Ok(())))
+                        fallback=move || {
+                            view! {
+                                <form class="auth-form fade-in" on:submit=on_submit>
+                                    <div class="form-group">
+                                        <label for="email">"Identity // Email Address"</label>
+                                        <input
+                                            type="email"
+                                            id="email"
+                                            placeholder="you@example.com"
+                                            on:input=move |ev| set_email.set(event_target_value(&ev))
+                                            prop:value=email
+                                            required
+                                        />
+                                    </div>
+
+                                    <button
+                                        type="submit"
+                                        class="btn btn-primary btn-full glow-primary"
+                                        disabled=request_loading
+                                    >
+                                        {move || {
+                                            if request_loading.get() { "SENDING..." } else { "SEND RESET LINK" }
+                                        }}
+                                    </button>
+                                </form>
+                            }
+                        }
+                    >
+                        <p class="fade-in">
+                            "If an account exists for that address, a reset link is on its way. It expires in one hour."
+                        </p>
+                    </Show>
+
+                    <footer class="auth-footer scale-in">
+                        <A href="/auth/login" attr:class="back-to-home">
+                            "← Back to Sign In"
+                        </A>
+                    </footer>
+                </div>
+            </div>
+        </main>
+    }
+}
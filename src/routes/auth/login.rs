@@ -1,11 +1,22 @@
+use crate::components::TotpSetup;
 use actions::auth::AuthenticateUser;
+use actions::two_factor::VerifyTwoFactor;
 use leptos::prelude::*;
 use leptos_router::components::A;
+use models::entities::{LoginOutcome, User};
 use models::shared::notifications::{Notification, NotificationLevel};
 use models::system_state::SystemState;
 use models::AuthenticateUserPayload;
 use std::sync::Arc;
 
+/// Where the sign-in form is after the password step.
+#[derive(Clone, PartialEq)]
+enum LoginStep {
+    Password,
+    Verify(String),
+    Enrol(String),
+}
+
 /// Login page component
 #[component]
 pub fn LoginPage() -> impl IntoView {
@@ -16,6 +27,8 @@ pub fn LoginPage() -> impl IntoView {
     // Form field signals
     let (email, set_email) = signal(String::new());
     let (password, set_password) = signal(String::new());
+    let (code, set_code) = signal(String::new());
+    let (step, set_step) = signal(LoginStep::Password);
 
     // Server action
     let login_action = ServerAction::<AuthenticateUser>::new();
@@ -33,28 +46,50 @@ pub fn LoginPage() -> impl IntoView {
         });
     };
 
-    // Watch for server errors
-    Effect::new(move |_| {
-        if let Some(Err(e)) = login_value.get() {
-            state.update(|s| {
-                s.add_toast(Arc::new(e));
+    let verify_action = ServerAction::<VerifyTwoFactor>::new();
+    let verify_loading = verify_action.pending();
+    let verify_value = verify_action.value();
+
+    let on_verify = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        if let LoginStep::Verify(challenge) = step.get() {
+            verify_action.dispatch(VerifyTwoFactor {
+                challenge,
+                code: code.get(),
             });
         }
+    };
+
+    let sign_in = Callback::new(move |user: User| {
+        state.update(|s| {
+            s.set_user(user);
+            s.add_toast(Arc::new(Notification::new(
+                "Access Authorized",
+                "Welcome back to the grid.",
+                NotificationLevel::Success,
+            )));
+        });
+        navigate("/system/dashboard", Default::default());
     });
 
-    // Watch for success
-    Effect::new(move |_| {
-        if let Some(Ok(user)) = login_value.get() {
-            state.update(|s| {
-                s.set_user(user);
-                s.add_toast(Arc::new(Notification::new(
-                    "Access Authorized",
-                    "Welcome back to the grid.",
-                    NotificationLevel::Success,
-                )));
-            });
-            navigate("/system/dashboard", Default::default());
+    // Watch for the password step
+    Effect::new(move |_| match login_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(LoginOutcome::Authenticated(user))) => sign_in.run(user),
+        Some(Ok(LoginOutcome::TwoFactorRequired { challenge })) => {
+            set_step.set(LoginStep::Verify(challenge))
         }
+        Some(Ok(LoginOutcome::TwoFactorSetupRequired { challenge })) => {
+            set_step.set(LoginStep::Enrol(challenge))
+        }
+        None => {}
+    });
+
+    // Watch for the code step
+    Effect::new(move |_| match verify_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(user)) => sign_in.run(user),
+        None => {}
     });
 
     view! {
@@ -72,43 +107,100 @@ pub fn LoginPage() -> impl IntoView {
                         <p>"Sign in to your Sharp System terminal"</p>
                     </header>
 
-                    <form class="auth-form fade-in" on:submit=on_submit>
-                        <div class="form-group">
-                            <label for="email">"Identity // Email Address"</label>
-                            <input
-                                type="email"
-                                id="email"
-                                name="email"
-                                placeholder="you@example.com"
-                                on:input=move |ev| set_email.set(event_target_value(&ev))
-                                prop:value=email
-                                required
-                            />
-                        </div>
-
-                        <div class="form-group">
-                            <label for="password">"Security // Private Key"</label>
-                            <input
-                                type="password"
-                                id="password"
-                                name="password"
-                                placeholder="••••••••"
-                                on:input=move |ev| set_password.set(event_target_value(&ev))
-                                prop:value=password
-                                required
-                            />
-                        </div>
-
-                        <button
-                            type="submit"
-                            class="btn btn-primary btn-full glow-primary"
-                            disabled=login_loading
-                        >
-                            {move || if login_loading.get() { "AUTHORIZING..." } else { "SIGN IN" }}
-                        </button>
-                    </form>
+                    {move || match step.get() {
+                        LoginStep::Password => {
+                            view! {
+                                        <form class="auth-form fade-in" on:submit=on_submit>
+                                            <div class="form-group">
+                                                <label for="email">"Identity // Email Address"</label>
+                                                <input
+                                                    type="email"
+                                                    id="email"
+                                                    name="email"
+                                                    placeholder="you@example.com"
+                                                    on:input=move |ev| set_email.set(event_target_value(&ev))
+                                                    prop:value=email
+                                                    required
+                                                />
+                                            </div>
+
+                                            <div class="form-group">
+                                                <label for="password">"Security // Private Key"</label>
+                                                <input
+                                                    type="password"
+                                                    id="password"
+                                                    name="password"
+                                                    placeholder="••••••••"
+                                                    on:input=move |ev| set_password.set(event_target_value(&ev))
+                                                    prop:value=password
+                                                    required
+                                                />
+                                            </div>
+
+                                            <button
+                                                type="submit"
+                                                class="btn btn-primary btn-full glow-primary"
+                                                disabled=login_loading
+                                            >
+                                                {move || if login_loading.get() { "AUTHORIZING..." } else { "SIGN IN" }}
+                                            </button>
+                                        </form>
+                            }
+                                .into_any()
+                        }
+                        LoginStep::Verify(_) => {
+                            view! {
+                                <form class="auth-form fade-in" on:submit=on_verify>
+                                    <div class="form-group">
+                                        <label for="code">"Second Factor // Authenticator Code"</label>
+                                        <input
+                                            type="text"
+                                            id="code"
+                                            name="code"
+                                            inputmode="numeric"
+                                            autocomplete="one-time-code"
+                                            placeholder="123456 or a recovery code"
+                                            on:input=move |ev| set_code.set(event_target_value(&ev))
+                                            prop:value=code
+                                            required
+                                        />
+                                    </div>
+
+                                    <button
+                                        type="submit"
+                                        class="btn btn-primary btn-full glow-primary"
+                                        disabled=verify_loading
+                                    >
+                                        {move || if verify_loading.get() { "VERIFYING..." } else { "VERIFY" }}
+                                    </button>
+                                </form>
+                            }
+                                .into_any()
+                        }
+                        LoginStep::Enrol(challenge) => {
+                            view! {
+                                <div class="auth-form fade-in">
+                                    <p>
+                                        "Your workspace requires two-factor authentication for your role. Set it up to finish signing in."
+                                    </p>
+                                    <TotpSetup
+                                        challenge=challenge
+                                        on_done=Callback::new(move |activation: models::entities::TotpActivation| {
+                                            if let Some(user) = activation.user {
+                                                sign_in.run(user);
+                                            }
+                                        })
+                                    />
+                                </div>
+                            }
+                                .into_any()
+                        }
+                    }}
 
                     <footer class="auth-footer scale-in">
+                        <p>
+                            <A href="/auth/forgot-password">"Forgot your password?"</A>
+                        </p>
                         <p>
                             "New to the system? " <A href="/auth/register">"Initialize Account"</A>
                         </p>
@@ -1,10 +1,16 @@
 // Auth routes - Public authentication pages
 // Login, Register, Forgot Password, etc.
 
+mod accept_invite;
 mod auth;
+mod forgot_password;
 mod login;
 mod register;
+mod reset_password;
 
+pub use accept_invite::AcceptInvitePage;
 pub use auth::AuthLayout;
+pub use forgot_password::ForgotPasswordPage;
 pub use login::LoginPage;
 pub use register::RegisterPage;
+pub use reset_password::ResetPasswordPage;
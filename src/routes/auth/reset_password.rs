@@ -0,0 +1,100 @@
+use actions::auth::ResetPassword;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use models::ResetPasswordPayload;
+use std::sync::Arc;
+
+/// Landing page for an emailed reset link.
+#[component]
+pub fn ResetPasswordPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let navigate = leptos_router::hooks::use_navigate();
+    let params = use_params_map();
+
+    let (password, set_password) = signal(String::new());
+    let (confirm_password, set_confirm_password) = signal(String::new());
+
+    let reset_action = ServerAction::<ResetPassword>::new();
+    let reset_loading = reset_action.pending();
+    let reset_value = reset_action.value();
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        reset_action.dispatch(ResetPassword {
+            payload: ResetPasswordPayload {
+                token: params.read().get("token").unwrap_or_default(),
+                password: password.get(),
+                confirm_password: confirm_password.get(),
+            },
+        });
+    };
+
+    Effect::new(move |_| match reset_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(())) => {
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Password Updated",
+                    "Sign in with your new password. Other devices have been signed out.",
+                    NotificationLevel::Success,
+                )))
+            });
+            navigate("/auth/login", Default::default());
+        }
+        None => {}
+    });
+
+    view! {
+        <main class="auth-page">
+            <div class="grain-bg"></div>
+            <div class="gradient-orb auth-orb orb-1"></div>
+            <div class="gradient-orb auth-orb orb-2"></div>
+
+            <div class="auth-container">
+                <div class="glass-card-premium auth-card">
+                    <header class="auth-header scale-in">
+                        <h1 class="gradient-text glow-text">"New Password"</h1>
+                        <p>"Choose a new password for your account"</p>
+                    </header>
+
+                    <form class="auth-form fade-in" on:submit=on_submit>
+                        <div class="form-group">
+                            <label for="password">"Security // New Password"</label>
+                            <input
+                                type="password"
+                                id="password"
+                                placeholder="••••••••"
+                                on:input=move |ev| set_password.set(event_target_value(&ev))
+                                prop:value=password
+                                required
+                            />
+                        </div>
+
+                        <div class="form-group">
+                            <label for="confirm_password">"Security // Confirm Password"</label>
+                            <input
+                                type="password"
+                                id="confirm_password"
+                                placeholder="••••••••"
+                                on:input=move |ev| set_confirm_password.set(event_target_value(&ev))
+                                prop:value=confirm_password
+                                required
+                            />
+                        </div>
+
+                        <button
+                            type="submit"
+                            class="btn btn-primary btn-full glow-primary"
+                            disabled=reset_loading
+                        >
+                            {move || if reset_loading.get() { "UPDATING..." } else { "SET PASSWORD" }}
+                        </button>
+                    </form>
+                </div>
+            </div>
+        </main>
+    }
+}
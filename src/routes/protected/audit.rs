@@ -0,0 +1,202 @@
+use actions::get_audit_log;
+use leptos::prelude::*;
+use models::entities::{AuditAction, AuditEntity, AuditEvent};
+use models::payloads::AuditLogQuery;
+use serde_json::Value;
+
+/// `(field, before, after)` for every field recorded on either side of the event.
+fn change_rows(event: &AuditEvent) -> Vec<(String, String, String)> {
+    let show = |v: Option<&Value>| match v {
+        None | Some(Value::Null) => "—".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    let before = event.before_state.as_ref().and_then(|v| v.as_object());
+    let after = event.after_state.as_ref().and_then(|v| v.as_object());
+
+    let mut keys: Vec<&String> = before
+        .into_iter()
+        .chain(after)
+        .flat_map(|fields| fields.keys())
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|key| {
+            (
+                key.clone(),
+                show(before.and_then(|f| f.get(key))),
+                show(after.and_then(|f| f.get(key))),
+            )
+        })
+        .collect()
+}
+
+#[component]
+pub fn AuditLogPage() -> impl IntoView {
+    let query = RwSignal::new(AuditLogQuery::default());
+    let events = Resource::new(
+        move || query.get(),
+        |query| async move { get_audit_log(query).await },
+    );
+
+    // Any filter change starts again from the first page
+    let set_filter = move |f: Box<dyn Fn(&mut AuditLogQuery)>| {
+        query.update(|q| {
+            f(q);
+            q.page = 0;
+        });
+    };
+
+    view! {
+        <div class="system-page-container">
+            <header class="system-page-header">
+                <div>
+                    <h1>"Audit Log"</h1>
+                    <p>"Who changed what in this workspace, newest first."</p>
+                </div>
+            </header>
+
+            <div class="catalog-filters">
+                <select
+                    class="input-field"
+                    on:change=move |ev| {
+                        let entity = event_target_value(&ev)
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| AuditEntity::ALL.get(i).copied());
+                        set_filter(Box::new(move |q| q.entity_type = entity));
+                    }
+                >
+                    <option value="">"All Records"</option>
+                    {AuditEntity::ALL
+                        .iter()
+                        .enumerate()
+                        .map(|(i, e)| view! { <option value=i.to_string()>{e.label()}</option> })
+                        .collect_view()}
+                </select>
+                <select
+                    class="input-field"
+                    on:change=move |ev| {
+                        let action = event_target_value(&ev)
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| AuditAction::ALL.get(i).copied());
+                        set_filter(Box::new(move |q| q.action = action));
+                    }
+                >
+                    <option value="">"All Actions"</option>
+                    {AuditAction::ALL
+                        .iter()
+                        .enumerate()
+                        .map(|(i, a)| view! { <option value=i.to_string()>{a.label()}</option> })
+                        .collect_view()}
+                </select>
+            </div>
+
+            <div class="system-content-area">
+                <Suspense fallback=|| view! { <p class="text-secondary">"Loading audit log..."</p> }>
+                    {move || Suspend::new(async move {
+                        match events.await {
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                            Ok(page) if page.items.is_empty() => {
+                                view! { <p class="text-secondary">"No events match these filters."</p> }
+                                    .into_any()
+                            }
+                            Ok(page) => {
+                                let page_count = page.page_count();
+                                let current = page.page;
+                                view! {
+                                    <table class="data-table">
+                                        <thead>
+                                            <tr>
+                                                <th>"When"</th>
+                                                <th>"Who"</th>
+                                                <th>"Record"</th>
+                                                <th>"Action"</th>
+                                                <th>"Changes"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {page
+                                                .items
+                                                .into_iter()
+                                                .map(|event| {
+                                                    let changes = change_rows(&event);
+                                                    view! {
+                                                        <tr>
+                                                            <td>{event.occurred_at.format("%Y-%m-%d %H:%M:%S").to_string()}</td>
+                                                            <td>
+                                                                {event.actor_name.unwrap_or_else(|| "—".to_string())}
+                                                                <div class="text-secondary text-sm">
+                                                                    {event.ip_address.unwrap_or_default()}
+                                                                </div>
+                                                            </td>
+                                                            <td>
+                                                                {event.entity_type.label()}
+                                                                <div class="text-secondary text-sm font-mono">
+                                                                    {event.entity_id.map(|id| id.to_string()).unwrap_or_default()}
+                                                                </div>
+                                                            </td>
+                                                            <td>
+                                                                <span class="badge">{event.action.label()}</span>
+                                                            </td>
+                                                            <td class="audit-changes">
+                                                                {changes
+                                                                    .into_iter()
+                                                                    .map(|(field, before, after)| {
+                                                                        view! {
+                                                                            <div>
+                                                                                <span class="font-mono">{field}</span>
+                                                                                ": "
+                                                                                <span class="text-secondary">{before}</span>
+                                                                                " → "
+                                                                                <span>{after}</span>
+                                                                            </div>
+                                                                        }
+                                                                    })
+                                                                    .collect_view()}
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tbody>
+                                    </table>
+                                    <div class="pagination">
+                                        <span class="text-secondary text-sm">
+                                            {format!(
+                                                "{} event(s) · Page {} of {}",
+                                                page.total,
+                                                current + 1,
+                                                page_count,
+                                            )}
+                                        </span>
+                                        <div class="flex gap-2">
+                                            <button
+                                                class="btn btn-ghost"
+                                                disabled=current == 0
+                                                on:click=move |_| query.update(|q| q.page = q.page.saturating_sub(1))
+                                            >
+                                                "Previous"
+                                            </button>
+                                            <button
+                                                class="btn btn-ghost"
+                                                disabled=current + 1 >= page_count
+                                                on:click=move |_| query.update(|q| q.page += 1)
+                                            >
+                                                "Next"
+                                            </button>
+                                        </div>
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
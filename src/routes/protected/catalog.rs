@@ -1,11 +1,64 @@
 use super::components::AddProductForm;
+use actions::{get_products, ArchiveProduct, Permission};
 use leptos::prelude::*;
+use models::entities::{Product, ProductRemoval};
+use models::payloads::{ProductListQuery, ProductSort};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::{AuthState, SystemState};
+use std::sync::Arc;
 
 #[component]
 pub fn CatalogPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
     let is_adding = RwSignal::new(false);
-    let product_id = RwSignal::new(None);
-    let products = Resource::new(move || product_id.get(), |id: Option<String>| async move {});
+    let can_manage = move || {
+        state.with(|s| match &s.auth_state {
+            AuthState::Authenticated(user) => {
+                Permission::ManageCatalog.is_granted_to(user.handler_role)
+            }
+            _ => false,
+        })
+    };
+    let query = RwSignal::new(ProductListQuery::default());
+    let refresh = RwSignal::new(0u32);
+    let products = Resource::new(
+        move || (query.get(), refresh.get()),
+        |(query, _)| async move { get_products(query).await },
+    );
+    let archive_action = ServerAction::<ArchiveProduct>::new();
+    let archive_value = archive_action.value();
+
+    // Any filter change starts again from the first page
+    let set_filter = move |f: Box<dyn Fn(&mut ProductListQuery)>| {
+        query.update(|q| {
+            f(q);
+            q.page = 0;
+        });
+    };
+
+    Effect::new(move |_| match archive_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(removal)) => {
+            let message = match removal {
+                ProductRemoval::Archived(product) => format!(
+                    "'{}' has sales or stock history and was archived.",
+                    product.name
+                ),
+                ProductRemoval::Deleted { .. } => "The product was deleted.".to_string(),
+            };
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Product Retired",
+                    message,
+                    NotificationLevel::Success,
+                )))
+            });
+            refresh.update(|r| *r += 1);
+        }
+        None => {}
+    });
+
     view! {
         <div class="system-page-container">
             <header class="system-page-header">
@@ -13,12 +66,14 @@ pub fn CatalogPage() -> impl IntoView {
                     <h1>"Product Catalog"</h1>
                     <p>"Manage your menu of products and decide what items to track."</p>
                 </div>
-                <button
-                    class="btn btn-primary shadow-sm hover:-translate-y-1 transition-all"
-                    on:click=move |_| is_adding.set(!is_adding.get())
-                >
-                    {move || if is_adding.get() { "Cancel" } else { "+ Add Product" }}
-                </button>
+                <Show when=can_manage>
+                    <button
+                        class="btn btn-primary shadow-sm hover:-translate-y-1 transition-all"
+                        on:click=move |_| is_adding.set(!is_adding.get())
+                    >
+                        {move || if is_adding.get() { "Cancel" } else { "+ Add Product" }}
+                    </button>
+                </Show>
             </header>
 
             // AI Info Box explaining Tracked vs Untracked
@@ -54,32 +109,184 @@ pub fn CatalogPage() -> impl IntoView {
             </div>
 
             <Show when=move || is_adding.get()>
-                <AddProductForm />
+                <AddProductForm on_added=move |_: Product| refresh.update(|r| *r += 1) />
             </Show>
 
-            // Future Data Table
+            <div class="catalog-filters">
+                <input
+                    type="search"
+                    class="input-field"
+                    placeholder="Search name or SKU..."
+                    on:input=move |ev| {
+                        let value = event_target_value(&ev);
+                        set_filter(Box::new(move |q| q.search = Some(value.clone())));
+                    }
+                />
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder="Category"
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        set_filter(Box::new(move |q| q.category = Some(value.clone())));
+                    }
+                />
+                <select
+                    class="input-field"
+                    on:change=move |ev| {
+                        let is_tracked = match event_target_value(&ev).as_str() {
+                            "tracked" => Some(true),
+                            "bulk" => Some(false),
+                            _ => None,
+                        };
+                        set_filter(Box::new(move |q| q.is_tracked = is_tracked));
+                    }
+                >
+                    <option value="all">"All Types"</option>
+                    <option value="tracked">"Tracked"</option>
+                    <option value="bulk">"Bulk"</option>
+                </select>
+                <select
+                    class="input-field"
+                    on:change=move |ev| {
+                        let sort = event_target_value(&ev)
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| ProductSort::ALL.get(i).copied())
+                            .unwrap_or_default();
+                        set_filter(Box::new(move |q| q.sort = sort));
+                    }
+                >
+                    {ProductSort::ALL
+                        .iter()
+                        .enumerate()
+                        .map(|(i, sort)| view! { <option value=i.to_string()>{sort.label()}</option> })
+                        .collect_view()}
+                </select>
+                <label class="catalog-archived-toggle">
+                    <input
+                        type="checkbox"
+                        on:change=move |ev| {
+                            let archived = event_target_checked(&ev);
+                            set_filter(Box::new(move |q| q.archived = archived));
+                        }
+                    />
+                    "Show archived"
+                </label>
+            </div>
+
             <div class="system-content-area">
-                <div class="empty-state">
-                    <svg
-                        xmlns="http://www.w3.org/2000/svg"
-                        width="48"
-                        height="48"
-                        viewBox="0 0 24 24"
-                        fill="none"
-                        stroke="currentColor"
-                        stroke-width="1"
-                        stroke-linecap="round"
-                        stroke-linejoin="round"
-                    >
-                        <path d="M21 16V8a2 2 0 0 0-1-1.73l-7-4a2 2 0 0 0-2 0l-7 4A2 2 0 0 0 3 8v8a2 2 0 0 0 1 1.73l7 4a2 2 0 0 0 2 0l7-4A2 2 0 0 0 21 16z"></path>
-                        <polyline points="3.27 6.96 12 12.01 20.73 6.96"></polyline>
-                        <line x1="12" y1="22.08" x2="12" y2="12"></line>
-                    </svg>
-                    <h3>"No Products Found"</h3>
-                    <p>
-                        "Your catalog is empty. Click '+ Add Product' above to define your first menu item before adding physical stock."
-                    </p>
-                </div>
+                <Suspense fallback=|| view! { <p class="text-secondary">"Loading catalog..."</p> }>
+                    {move || Suspend::new(async move {
+                        match products.await {
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                            Ok(page) if page.items.is_empty() => {
+                                view! {
+                                    <div class="empty-state">
+                                        <svg
+                                            xmlns="http://www.w3.org/2000/svg"
+                                            width="48"
+                                            height="48"
+                                            viewBox="0 0 24 24"
+                                            fill="none"
+                                            stroke="currentColor"
+                                            stroke-width="1"
+                                            stroke-linecap="round"
+                                            stroke-linejoin="round"
+                                        >
+                                            <path d="M21 16V8a2 2 0 0 0-1-1.73l-7-4a2 2 0 0 0-2 0l-7 4A2 2 0 0 0 3 8v8a2 2 0 0 0 1 1.73l7 4a2 2 0 0 0 2 0l7-4A2 2 0 0 0 21 16z"></path>
+                                            <polyline points="3.27 6.96 12 12.01 20.73 6.96"></polyline>
+                                            <line x1="12" y1="22.08" x2="12" y2="12"></line>
+                                        </svg>
+                                        <h3>"No Products Found"</h3>
+                                        <p>
+                                            "Nothing matches these filters. Click '+ Add Product' above to define a new menu item before adding physical stock."
+                                        </p>
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Ok(page) => {
+                                let page_count = page.page_count();
+                                let current = page.page;
+                                view! {
+                                    <table class="data-table">
+                                        <thead>
+                                            <tr>
+                                                <th>"Name"</th>
+                                                <th>"SKU"</th>
+                                                <th>"Category"</th>
+                                                <th>"Type"</th>
+                                                <th></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {page
+                                                .items
+                                                .into_iter()
+                                                .map(|product| {
+                                                    let id = product.id;
+                                                    view! {
+                                                        <tr>
+                                                            <td>{product.name}</td>
+                                                            <td class="font-mono">{product.sku.unwrap_or_default()}</td>
+                                                            <td>{product.category.unwrap_or_default()}</td>
+                                                            <td>
+                                                                <span class="badge">
+                                                                    {if product.is_tracked { "Tracked" } else { "Bulk" }}
+                                                                </span>
+                                                            </td>
+                                                            <td class="text-right">
+                                                                <Show when=move || can_manage() && !product.is_archived>
+                                                                    <button
+                                                                        class="btn btn-ghost"
+                                                                        disabled=move || archive_action.pending().get()
+                                                                        on:click=move |_| {
+                                                                            archive_action.dispatch(ArchiveProduct { product_id: id });
+                                                                        }
+                                                                    >
+                                                                        "Archive"
+                                                                    </button>
+                                                                </Show>
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tbody>
+                                    </table>
+                                    <div class="pagination">
+                                        <span class="text-secondary text-sm">
+                                            {format!(
+                                                "{} product(s) · Page {} of {}",
+                                                page.total,
+                                                current + 1,
+                                                page_count,
+                                            )}
+                                        </span>
+                                        <div class="flex gap-2">
+                                            <button
+                                                class="btn btn-ghost"
+                                                disabled=current == 0
+                                                on:click=move |_| query.update(|q| q.page = q.page.saturating_sub(1))
+                                            >
+                                                "Previous"
+                                            </button>
+                                            <button
+                                                class="btn btn-ghost"
+                                                disabled=current + 1 >= page_count
+                                                on:click=move |_| query.update(|q| q.page += 1)
+                                            >
+                                                "Next"
+                                            </button>
+                                        </div>
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
             </div>
         </div>
     }
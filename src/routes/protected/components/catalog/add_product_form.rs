@@ -1,11 +1,16 @@
 use actions::AddProduct;
 use leptos::prelude::*;
+use models::entities::Product;
 use models::shared::notifications::{Notification, NotificationLevel};
 use models::system_state::SystemState;
 use std::sync::Arc;
 
 #[component]
-pub fn AddProductForm() -> impl IntoView {
+pub fn AddProductForm(
+    /// Fired after the product is saved so the caller can refresh its listing.
+    #[prop(optional, into)]
+    on_added: Option<Callback<Product>>,
+) -> impl IntoView {
     let state =
         use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
     let add_product_action = ServerAction::<AddProduct>::new();
@@ -25,6 +30,9 @@ pub fn AddProductForm() -> impl IntoView {
     Effect::new(move |_| {
         if let Some(Ok(product)) = submit_value.get() {
             is_adding.set(false);
+            if let Some(on_added) = on_added {
+                on_added.run(product.clone());
+            }
             state.update(|s| {
                 s.add_toast(Arc::new(Notification::new(
                     "Product Added",
@@ -66,6 +74,20 @@ pub fn AddProductForm() -> impl IntoView {
                         <input type="text" name="sku" class="input-field" placeholder=" " />
                         <label class="input-label">"Internal SKU (Optional)"</label>
                     </div>
+
+                    // Warranty Input
+                    <div class="input-group">
+                        <input
+                            type="number"
+                            name="warranty_months"
+                            class="input-field"
+                            min="0"
+                            max="120"
+                            value="0"
+                            required
+                        />
+                        <label class="input-label">"Warranty (Months, 0 for None)"</label>
+                    </div>
                 </div>
 
                 // THE MASSIVE TOGGLE SWITCH
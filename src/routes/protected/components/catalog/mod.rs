@@ -1,3 +1,3 @@
 mod add_product_form;
 
-pub use add_product_form::AddProductForm;
\ No newline at end of file
+pub use add_product_form::AddProductForm;
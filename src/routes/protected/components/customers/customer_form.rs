@@ -0,0 +1,69 @@
+use leptos::prelude::*;
+use models::payloads::CustomerPayload;
+
+/// Name, phone, email and notes, used both to add a customer and to edit one.
+#[component]
+pub fn CustomerForm(
+    /// Starting values; blank for a new customer.
+    initial: CustomerPayload,
+    #[prop(into)] submit_label: String,
+    #[prop(into)] pending: Signal<bool>,
+    on_submit: Callback<CustomerPayload>,
+) -> impl IntoView {
+    let (name, set_name) = signal(initial.name);
+    let (phone, set_phone) = signal(initial.phone.unwrap_or_default());
+    let (email, set_email) = signal(initial.email.unwrap_or_default());
+    let (notes, set_notes) = signal(initial.notes.unwrap_or_default());
+
+    let submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        // Blank fields are dropped server-side by `CustomerPayload::normalized`
+        on_submit.run(CustomerPayload {
+            name: name.get(),
+            phone: Some(phone.get()),
+            email: Some(email.get()),
+            notes: Some(notes.get()),
+        });
+    };
+
+    view! {
+        <form class="customer-form" on:submit=submit>
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder="Full name"
+                    required
+                    on:input=move |ev| set_name.set(event_target_value(&ev))
+                    prop:value=name
+                />
+                <input
+                    type="tel"
+                    class="input-field"
+                    placeholder="Phone"
+                    on:input=move |ev| set_phone.set(event_target_value(&ev))
+                    prop:value=phone
+                />
+                <input
+                    type="email"
+                    class="input-field"
+                    placeholder="Email"
+                    on:input=move |ev| set_email.set(event_target_value(&ev))
+                    prop:value=email
+                />
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder="Notes"
+                    on:input=move |ev| set_notes.set(event_target_value(&ev))
+                    prop:value=notes
+                />
+            </div>
+            <div class="flex justify-end mt-4">
+                <button type="submit" class="btn btn-primary" disabled=pending>
+                    {submit_label}
+                </button>
+            </div>
+        </form>
+    }
+}
@@ -0,0 +1,3 @@
+mod customer_form;
+
+pub use customer_form::CustomerForm;
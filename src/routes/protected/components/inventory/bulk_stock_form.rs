@@ -0,0 +1,246 @@
+use super::{PurchaseOrderLineSelect, SupplierSelect};
+use actions::{get_products, AdjustBulkStock, ReceiveBulkStock};
+use leptos::prelude::*;
+use models::entities::{PurchaseOrderLine, StockAdjustmentReason};
+use models::payloads::{
+    AdjustBulkStockPayload, ProductListQuery, ReceiveBulkStockPayload, MAX_PRODUCT_PAGE_SIZE,
+};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[component]
+pub fn BulkStockForm() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let products = Resource::new(
+        || (),
+        |_| async move {
+            get_products(ProductListQuery {
+                is_tracked: Some(false),
+                page_size: MAX_PRODUCT_PAGE_SIZE,
+                ..Default::default()
+            })
+            .await
+        },
+    );
+    let receive_action = ServerAction::<ReceiveBulkStock>::new();
+    let adjust_action = ServerAction::<AdjustBulkStock>::new();
+
+    let is_receiving = RwSignal::new(true);
+    let (product_id, set_product_id) = signal(String::new());
+    let (quantity, set_quantity) = signal(String::new());
+    let (unit_cost, set_unit_cost) = signal(String::new());
+    let (reason, set_reason) = signal(0usize);
+    let (note, set_note) = signal(String::new());
+    let supplier_id = RwSignal::new(None::<Uuid>);
+    let po_line = RwSignal::new(None::<PurchaseOrderLine>);
+
+    // Start from the cost agreed on the purchase order
+    Effect::new(move |_| {
+        if let Some(line) = po_line.get() {
+            set_unit_cost.set(line.unit_cost.to_string());
+        }
+    });
+
+    let warn = move |message: &str| {
+        state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                "Input Error",
+                message,
+                NotificationLevel::Warning,
+            )));
+        });
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let (Ok(product_id), Ok(qty)) = (
+            Uuid::parse_str(&product_id.get()),
+            quantity.get().trim().parse::<i32>(),
+        ) else {
+            warn("Select a product and enter a whole quantity.");
+            return;
+        };
+        let note = Some(note.get()).filter(|n| !n.trim().is_empty());
+
+        if is_receiving.get() {
+            let Ok(unit_cost) = Decimal::from_str(unit_cost.get().trim()) else {
+                warn("Enter a valid unit cost.");
+                return;
+            };
+            receive_action.dispatch(ReceiveBulkStock {
+                payload: ReceiveBulkStockPayload {
+                    product_id,
+                    quantity: qty,
+                    unit_cost,
+                    note,
+                    // An order line brings its own supplier
+                    supplier_id: supplier_id.get().filter(|_| po_line.with(Option::is_none)),
+                    purchase_order_line_id: po_line.with(|l| l.as_ref().map(|l| l.id)),
+                },
+            });
+        } else {
+            adjust_action.dispatch(AdjustBulkStock {
+                payload: AdjustBulkStockPayload {
+                    product_id,
+                    quantity_delta: qty,
+                    reason: StockAdjustmentReason::MANUAL[reason.get()],
+                    note,
+                },
+            });
+        }
+    };
+
+    // Both actions report the resulting stock level the same way
+    for value in [receive_action.value(), adjust_action.value()] {
+        Effect::new(move |_| match value.get() {
+            Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+            Some(Ok(level)) => {
+                set_quantity.set(String::new());
+                set_note.set(String::new());
+                state.update(|s| {
+                    s.add_toast(Arc::new(Notification::new(
+                        "Stock Updated",
+                        format!(
+                            "{} on hand at an average cost of {}.",
+                            level.quantity, level.average_acquisition_cost
+                        ),
+                        NotificationLevel::Success,
+                    )));
+                });
+            }
+            None => {}
+        });
+    }
+
+    let is_pending = move || receive_action.pending().get() || adjust_action.pending().get();
+
+    view! {
+        <form class="catalog-add-form" on:submit=on_submit>
+            <div class="form-header flex justify-between items-center">
+                <h3>
+                    {move || if is_receiving.get() { "Receive Bulk Stock" } else { "Adjust Bulk Stock" }}
+                </h3>
+                <button
+                    type="button"
+                    class="btn btn-ghost"
+                    on:click=move |_| is_receiving.update(|r| *r = !*r)
+                >
+                    {move || if is_receiving.get() { "Switch to Adjustment" } else { "Switch to Receiving" }}
+                </button>
+            </div>
+
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+                <div class="input-group">
+                    <select
+                        class="input-field"
+                        required
+                        on:change=move |ev| set_product_id.set(event_target_value(&ev))
+                    >
+                        <option value="">"Select a bulk product..."</option>
+                        <Suspense>
+                            {move || {
+                                products
+                                    .get()
+                                    .and_then(Result::ok)
+                                    .map(|page| page.items)
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|p| view! { <option value=p.id.to_string()>{p.name}</option> })
+                                    .collect_view()
+                            }}
+                        </Suspense>
+                    </select>
+                </div>
+                <div class="input-group">
+                    <input
+                        type="number"
+                        step="1"
+                        class="input-field"
+                        placeholder=" "
+                        required
+                        prop:value=quantity
+                        on:input=move |ev| set_quantity.set(event_target_value(&ev))
+                    />
+                    <label class="input-label">
+                        {move || {
+                            if is_receiving.get() {
+                                "Quantity Received"
+                            } else {
+                                "Quantity Change (e.g. -3)"
+                            }
+                        }}
+                    </label>
+                </div>
+                <Show
+                    when=move || is_receiving.get()
+                    fallback=move || {
+                        view! {
+                            <div class="input-group">
+                                <select
+                                    class="input-field"
+                                    on:change=move |ev| {
+                                        set_reason.set(event_target_value(&ev).parse().unwrap_or(0))
+                                    }
+                                >
+                                    {StockAdjustmentReason::MANUAL
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(i, r)| view! { <option value=i.to_string()>{r.label()}</option> })
+                                        .collect_view()}
+                                </select>
+                            </div>
+                        }
+                    }
+                >
+                    <div class="input-group">
+                        <input
+                            type="text"
+                            inputmode="decimal"
+                            class="input-field"
+                            placeholder=" "
+                            required
+                            prop:value=unit_cost
+                            readonly=move || po_line.with(Option::is_some)
+                            on:input=move |ev| set_unit_cost.set(event_target_value(&ev))
+                        />
+                        <label class="input-label">"Unit Cost"</label>
+                    </div>
+                </Show>
+            </div>
+
+            <Show when=move || is_receiving.get()>
+                <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                    <PurchaseOrderLineSelect
+                        product_id
+                        line=po_line
+                        refresh=receive_action.version()
+                    />
+                    <Show when=move || po_line.with(Option::is_none)>
+                        <SupplierSelect supplier_id />
+                    </Show>
+                </div>
+            </Show>
+
+            <div class="input-group">
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder=" "
+                    prop:value=note
+                    on:input=move |ev| set_note.set(event_target_value(&ev))
+                />
+                <label class="input-label">"Note (Optional)"</label>
+            </div>
+
+            <div class="flex justify-end">
+                <input type="submit" class="btn btn-primary" value="Save" disabled=is_pending />
+            </div>
+        </form>
+    }
+}
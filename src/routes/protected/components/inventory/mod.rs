@@ -0,0 +1,11 @@
+mod bulk_stock_form;
+mod purchase_order_line_select;
+mod purchase_orders_panel;
+mod receive_tracked_form;
+mod supplier_select;
+
+pub use bulk_stock_form::BulkStockForm;
+use purchase_order_line_select::PurchaseOrderLineSelect;
+pub use purchase_orders_panel::PurchaseOrdersPanel;
+pub use receive_tracked_form::ReceiveTrackedForm;
+use supplier_select::SupplierSelect;
@@ -0,0 +1,110 @@
+use actions::get_purchase_orders;
+use leptos::prelude::*;
+use models::entities::PurchaseOrderLine;
+use uuid::Uuid;
+
+/// Picks the purchase order line a delivery of `product_id` arrives against, and shows how
+/// much of it is still expected. Only orders open for receiving are offered.
+#[component]
+pub fn PurchaseOrderLineSelect(
+    #[prop(into)] product_id: Signal<String>,
+    line: RwSignal<Option<PurchaseOrderLine>>,
+    /// Bumped after every delivery so the outstanding quantities stay current.
+    #[prop(into)]
+    refresh: Signal<usize>,
+) -> impl IntoView {
+    let orders = Resource::new(
+        move || refresh.get(),
+        |_| async move { get_purchase_orders(true).await },
+    );
+
+    // (PO number, supplier, line) for every receivable line of the chosen product
+    let candidates = move || {
+        let product_id = Uuid::parse_str(&product_id.get()).ok();
+        orders
+            .get()
+            .and_then(Result::ok)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|detail| detail.order.status.is_receivable())
+            .flat_map(|detail| {
+                let po_number = detail.order.po_number;
+                let supplier_name = detail.supplier_name;
+                detail
+                    .lines
+                    .into_iter()
+                    .filter(move |l| Some(l.product_id) == product_id)
+                    .filter(|l| l.outstanding_quantity() > 0)
+                    .map(move |l| (po_number, supplier_name.clone(), l))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // A new product or a refreshed order invalidates the current pick
+    Effect::new(move |_| {
+        let current = candidates();
+        line.update(|selected| {
+            *selected = selected.take().and_then(|s| {
+                current
+                    .into_iter()
+                    .map(|(_, _, l)| l)
+                    .find(|l| l.id == s.id)
+            });
+        });
+    });
+
+    view! {
+        <div class="input-group">
+            <select
+                class="input-field"
+                on:change=move |ev| {
+                    let id = Uuid::parse_str(&event_target_value(&ev)).ok();
+                    line.set(
+                        candidates().into_iter().map(|(_, _, l)| l).find(|l| Some(l.id) == id),
+                    );
+                }
+            >
+                <option value="">"No purchase order"</option>
+                <Suspense>
+                    {move || {
+                        candidates()
+                            .into_iter()
+                            .map(|(po_number, supplier_name, l)| {
+                                let id = l.id;
+                                view! {
+                                    <option
+                                        value=id.to_string()
+                                        selected=move || line.with(|s| s.as_ref().map(|s| s.id) == Some(id))
+                                    >
+                                        {format!(
+                                            "PO #{} · {} · {} outstanding",
+                                            po_number,
+                                            supplier_name,
+                                            l.outstanding_quantity(),
+                                        )}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </Suspense>
+            </select>
+            {move || {
+                line.get()
+                    .map(|l| {
+                        view! {
+                            <span class="text-secondary text-sm">
+                                {format!(
+                                    "Expected {}, received {}, outstanding {} at {} each.",
+                                    l.expected_quantity,
+                                    l.received_quantity,
+                                    l.outstanding_quantity(),
+                                    l.unit_cost,
+                                )}
+                            </span>
+                        }
+                    })
+            }}
+        </div>
+    }
+}
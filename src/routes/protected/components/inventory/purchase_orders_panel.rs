@@ -0,0 +1,348 @@
+use super::SupplierSelect;
+use actions::{
+    get_products, get_purchase_orders, CancelPurchaseOrder, CreatePurchaseOrder,
+    MarkPurchaseOrderOrdered,
+};
+use leptos::prelude::*;
+use models::entities::{PurchaseOrderDetail, PurchaseOrderStatus};
+use models::payloads::{
+    CreatePurchaseOrderPayload, ProductListQuery, PurchaseOrderLineInput, MAX_PRODUCT_PAGE_SIZE,
+};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A line being put together in the new order form.
+#[derive(Debug, Clone, PartialEq)]
+struct DraftLine {
+    product_name: String,
+    input: PurchaseOrderLineInput,
+}
+
+/// Drafting purchase orders and following what has arrived against the open ones.
+#[component]
+pub fn PurchaseOrdersPanel() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let products = Resource::new(
+        || (),
+        |_| async move {
+            get_products(ProductListQuery {
+                page_size: MAX_PRODUCT_PAGE_SIZE,
+                ..Default::default()
+            })
+            .await
+        },
+    );
+    let create_action = ServerAction::<CreatePurchaseOrder>::new();
+    let order_action = ServerAction::<MarkPurchaseOrderOrdered>::new();
+    let cancel_action = ServerAction::<CancelPurchaseOrder>::new();
+    let orders = Resource::new(
+        move || {
+            (
+                create_action.version().get(),
+                order_action.version().get(),
+                cancel_action.version().get(),
+            )
+        },
+        |_| async move { get_purchase_orders(true).await },
+    );
+
+    let supplier_id = RwSignal::new(None::<Uuid>);
+    let lines = RwSignal::new(Vec::<DraftLine>::new());
+    let (product_id, set_product_id) = signal(String::new());
+    let (quantity, set_quantity) = signal(String::new());
+    let (unit_cost, set_unit_cost) = signal(String::new());
+    let (notes, set_notes) = signal(String::new());
+
+    let warn = move |message: &str| {
+        state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                "Input Error",
+                message,
+                NotificationLevel::Warning,
+            )));
+        });
+    };
+
+    let on_add_line = move |_| {
+        let parsed = (
+            Uuid::parse_str(&product_id.get()),
+            quantity.get().trim().parse::<i32>(),
+            Decimal::from_str(unit_cost.get().trim()),
+        );
+        let (Ok(product_id), Ok(expected_quantity), Ok(unit_cost)) = parsed else {
+            warn("Select a product and enter a whole quantity and a valid unit cost.");
+            return;
+        };
+        let product_name = products
+            .get()
+            .and_then(Result::ok)
+            .and_then(|page| page.items.into_iter().find(|p| p.id == product_id))
+            .map(|p| p.name)
+            .unwrap_or_default();
+        lines.update(|lines| {
+            lines.retain(|l| l.input.product_id != product_id);
+            lines.push(DraftLine {
+                product_name,
+                input: PurchaseOrderLineInput {
+                    product_id,
+                    expected_quantity,
+                    unit_cost,
+                },
+            });
+        });
+        set_quantity.set(String::new());
+        set_unit_cost.set(String::new());
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let Some(supplier_id) = supplier_id.get() else {
+            warn("Choose the supplier the order goes to.");
+            return;
+        };
+        create_action.dispatch(CreatePurchaseOrder {
+            payload: CreatePurchaseOrderPayload {
+                supplier_id,
+                notes: Some(notes.get()),
+                lines: lines.with(|lines| lines.iter().map(|l| l.input.clone()).collect()),
+            },
+        });
+    };
+
+    Effect::new(move |_| match create_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(order)) => {
+            lines.set(Vec::new());
+            set_notes.set(String::new());
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Purchase Order Drafted",
+                    format!(
+                        "PO #{} is saved as a draft. Mark it ordered once it is sent.",
+                        order.po_number
+                    ),
+                    NotificationLevel::Success,
+                )))
+            });
+        }
+        None => {}
+    });
+
+    for value in [order_action.value(), cancel_action.value()] {
+        Effect::new(move |_| match value.get() {
+            Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+            Some(Ok(order)) => state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Purchase Order Updated",
+                    format!("PO #{} is now {}.", order.po_number, order.status.label()),
+                    NotificationLevel::Success,
+                )))
+            }),
+            None => {}
+        });
+    }
+
+    let busy = move || order_action.pending().get() || cancel_action.pending().get();
+
+    let order_card = move |detail: PurchaseOrderDetail| {
+        let id = detail.order.id;
+        let status = detail.order.status;
+        let summary = format!(
+            "{} of {} received · {}",
+            detail.received_quantity(),
+            detail.expected_quantity(),
+            detail.total_cost()
+        );
+        view! {
+            <div class="catalog-add-form">
+                <div class="form-header flex justify-between items-center">
+                    <h3>{format!("PO #{} · {}", detail.order.po_number, detail.supplier_name)}</h3>
+                    <span class="badge">{status.label()}</span>
+                </div>
+                <p class="text-secondary text-sm">{summary}</p>
+                <table class="data-table">
+                    <thead>
+                        <tr>
+                            <th>"Product"</th>
+                            <th>"Expected"</th>
+                            <th>"Received"</th>
+                            <th>"Outstanding"</th>
+                            <th>"Unit Cost"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {detail
+                            .lines
+                            .into_iter()
+                            .map(|l| {
+                                view! {
+                                    <tr>
+                                        <td>{l.product_name.clone()}</td>
+                                        <td>{l.expected_quantity}</td>
+                                        <td>{l.received_quantity}</td>
+                                        <td>{l.outstanding_quantity()}</td>
+                                        <td>{l.unit_cost.to_string()}</td>
+                                    </tr>
+                                }
+                            })
+                            .collect_view()}
+                    </tbody>
+                </table>
+                <div class="flex justify-end gap-2">
+                    <Show when=move || status == PurchaseOrderStatus::Draft>
+                        <button
+                            class="btn btn-primary"
+                            disabled=busy
+                            on:click=move |_| {
+                                order_action.dispatch(MarkPurchaseOrderOrdered { purchase_order_id: id });
+                            }
+                        >
+                            "Mark Ordered"
+                        </button>
+                    </Show>
+                    <button
+                        class="btn btn-ghost"
+                        disabled=busy
+                        on:click=move |_| {
+                            cancel_action.dispatch(CancelPurchaseOrder { purchase_order_id: id });
+                        }
+                    >
+                        "Cancel Order"
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <form class="catalog-add-form" on:submit=on_submit>
+            <div class="form-header">
+                <h3>"New Purchase Order"</h3>
+            </div>
+
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <SupplierSelect supplier_id />
+                <div class="input-group">
+                    <input
+                        type="text"
+                        class="input-field"
+                        placeholder=" "
+                        prop:value=notes
+                        on:input=move |ev| set_notes.set(event_target_value(&ev))
+                    />
+                    <label class="input-label">"Notes (Optional)"</label>
+                </div>
+            </div>
+
+            <div class="grid grid-cols-1 md:grid-cols-4 gap-4">
+                <div class="input-group">
+                    <select
+                        class="input-field"
+                        on:change=move |ev| set_product_id.set(event_target_value(&ev))
+                    >
+                        <option value="">"Select a product..."</option>
+                        <Suspense>
+                            {move || {
+                                products
+                                    .get()
+                                    .and_then(Result::ok)
+                                    .map(|page| page.items)
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .filter(|p| !p.is_archived)
+                                    .map(|p| view! { <option value=p.id.to_string()>{p.name}</option> })
+                                    .collect_view()
+                            }}
+                        </Suspense>
+                    </select>
+                </div>
+                <div class="input-group">
+                    <input
+                        type="number"
+                        min="1"
+                        step="1"
+                        class="input-field"
+                        placeholder=" "
+                        prop:value=quantity
+                        on:input=move |ev| set_quantity.set(event_target_value(&ev))
+                    />
+                    <label class="input-label">"Quantity"</label>
+                </div>
+                <div class="input-group">
+                    <input
+                        type="text"
+                        inputmode="decimal"
+                        class="input-field"
+                        placeholder=" "
+                        prop:value=unit_cost
+                        on:input=move |ev| set_unit_cost.set(event_target_value(&ev))
+                    />
+                    <label class="input-label">"Unit Cost"</label>
+                </div>
+                <button type="button" class="btn btn-ghost" on:click=on_add_line>
+                    "Add Line"
+                </button>
+            </div>
+
+            <Show when=move || lines.with(|l| !l.is_empty())>
+                <ul class="intake-report">
+                    <For
+                        each=move || lines.get()
+                        key=|l| l.input.product_id
+                        let:line
+                    >
+                        <li>
+                            <span>
+                                {format!(
+                                    "{} × {} at {}",
+                                    line.input.expected_quantity,
+                                    line.product_name,
+                                    line.input.unit_cost,
+                                )}
+                            </span>
+                            <button
+                                type="button"
+                                class="btn btn-ghost"
+                                on:click=move |_| {
+                                    lines.update(|lines| {
+                                        lines.retain(|l| l.input.product_id != line.input.product_id)
+                                    })
+                                }
+                            >
+                                "×"
+                            </button>
+                        </li>
+                    </For>
+                </ul>
+            </Show>
+
+            <div class="flex justify-end">
+                <input
+                    type="submit"
+                    class="btn btn-primary"
+                    value="Save Draft"
+                    disabled=move || create_action.pending().get() || lines.with(Vec::is_empty)
+                />
+            </div>
+        </form>
+
+        <Suspense fallback=|| {
+            view! { <p class="text-secondary">"Loading purchase orders..."</p> }
+        }>
+            {move || Suspend::new(async move {
+                match orders.await {
+                    Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                    Ok(list) if list.is_empty() => {
+                        view! { <p class="text-secondary">"No open purchase orders."</p> }.into_any()
+                    }
+                    Ok(list) => list.into_iter().map(order_card).collect_view().into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
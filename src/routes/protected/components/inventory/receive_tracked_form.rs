@@ -0,0 +1,250 @@
+use super::{PurchaseOrderLineSelect, SupplierSelect};
+use actions::{get_products, ReceiveTrackedUnits};
+use leptos::prelude::*;
+use models::entities::{PurchaseOrderLine, SerialIntakeOutcome};
+use models::payloads::{ProductListQuery, ReceiveTrackedUnitsPayload, MAX_PRODUCT_PAGE_SIZE};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Splits the scanner textarea into units: one box per line, serials separated by commas or spaces.
+fn parse_scanned_units(raw: &str) -> Vec<Vec<String>> {
+    raw.lines()
+        .map(|line| {
+            line.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .filter(|unit| !unit.is_empty())
+        .collect()
+}
+
+#[component]
+pub fn ReceiveTrackedForm() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let products = Resource::new(
+        || (),
+        |_| async move {
+            get_products(ProductListQuery {
+                is_tracked: Some(true),
+                page_size: MAX_PRODUCT_PAGE_SIZE,
+                ..Default::default()
+            })
+            .await
+        },
+    );
+    let receive_action = ServerAction::<ReceiveTrackedUnits>::new();
+    let receive_value = receive_action.value();
+
+    let (product_id, set_product_id) = signal(String::new());
+    let (scanned, set_scanned) = signal(String::new());
+    let (cost, set_cost) = signal(String::new());
+    let (msrp, set_msrp) = signal(String::new());
+    let supplier_id = RwSignal::new(None::<Uuid>);
+    let po_line = RwSignal::new(None::<PurchaseOrderLine>);
+
+    // Start from the cost agreed on the purchase order
+    Effect::new(move |_| {
+        if let Some(line) = po_line.get() {
+            set_cost.set(line.unit_cost.to_string());
+        }
+    });
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let parsed = (
+            Uuid::parse_str(&product_id.get()),
+            Decimal::from_str(cost.get().trim()),
+            Decimal::from_str(msrp.get().trim()),
+        );
+        let (Ok(product_id), Ok(acquisition_cost), Ok(target_msrp)) = parsed else {
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Input Error",
+                    "Select a product and enter a valid cost and MSRP.",
+                    NotificationLevel::Warning,
+                )));
+            });
+            return;
+        };
+
+        receive_action.dispatch(ReceiveTrackedUnits {
+            payload: ReceiveTrackedUnitsPayload {
+                product_id,
+                units: parse_scanned_units(&scanned.get()),
+                acquisition_cost,
+                target_msrp,
+                // An order line brings its own supplier
+                supplier_id: supplier_id.get().filter(|_| po_line.with(Option::is_none)),
+                purchase_order_line_id: po_line.with(|l| l.as_ref().map(|l| l.id)),
+            },
+        });
+    };
+
+    // Watch for server errors and display them as toasts
+    Effect::new(move |_| {
+        if let Some(Err(e)) = receive_value.get() {
+            state.update(|s| {
+                s.add_toast(Arc::new(e));
+            });
+        }
+    });
+
+    // Summarize the batch and clear the scanner for the next box
+    Effect::new(move |_| {
+        if let Some(Ok(report)) = receive_value.get() {
+            let level = if report.rejected_count() == 0 {
+                NotificationLevel::Success
+            } else {
+                NotificationLevel::Warning
+            };
+            if report.rejected_count() == 0 {
+                set_scanned.set(String::new());
+            }
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Shipment Received",
+                    format!(
+                        "{} unit(s) received, {} rejected.",
+                        report.received_count(),
+                        report.rejected_count()
+                    ),
+                    level,
+                )));
+            });
+        }
+    });
+
+    view! {
+        <form class="catalog-add-form" on:submit=on_submit>
+            <div class="form-header">
+                <h3>"Receive Tracked Units"</h3>
+            </div>
+
+            <div class="grid grid-cols-1 md:grid-cols-4 gap-4">
+                <div class="input-group">
+                    <select
+                        class="input-field"
+                        required
+                        on:change=move |ev| set_product_id.set(event_target_value(&ev))
+                    >
+                        <option value="">"Select a tracked product..."</option>
+                        <Suspense>
+                            {move || {
+                                products
+                                    .get()
+                                    .and_then(Result::ok)
+                                    .map(|page| page.items)
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|p| view! { <option value=p.id.to_string()>{p.name}</option> })
+                                    .collect_view()
+                            }}
+                        </Suspense>
+                    </select>
+                </div>
+                <div class="input-group">
+                    <input
+                        type="text"
+                        inputmode="decimal"
+                        class="input-field"
+                        placeholder=" "
+                        required
+                        prop:value=cost
+                        readonly=move || po_line.with(Option::is_some)
+                        on:input=move |ev| set_cost.set(event_target_value(&ev))
+                    />
+                    <label class="input-label">"Acquisition Cost (per unit)"</label>
+                </div>
+                <div class="input-group">
+                    <input
+                        type="text"
+                        inputmode="decimal"
+                        class="input-field"
+                        placeholder=" "
+                        required
+                        prop:value=msrp
+                        on:input=move |ev| set_msrp.set(event_target_value(&ev))
+                    />
+                    <label class="input-label">"Target MSRP"</label>
+                </div>
+                <Show when=move || po_line.with(Option::is_none)>
+                    <SupplierSelect supplier_id />
+                </Show>
+            </div>
+
+            <PurchaseOrderLineSelect
+                product_id
+                line=po_line
+                refresh=receive_action.version()
+            />
+
+            <textarea
+                class="input-field scanner-textarea"
+                rows="8"
+                placeholder="One box per line. Separate multiple IMEIs on the same box with a comma."
+                prop:value=scanned
+                on:input=move |ev| set_scanned.set(event_target_value(&ev))
+            ></textarea>
+
+            <div class="flex justify-between items-center">
+                <span class="text-secondary text-sm">
+                    {move || format!("{} unit(s) scanned", parse_scanned_units(&scanned.get()).len())}
+                </span>
+                <input
+                    type="submit"
+                    class="btn btn-primary"
+                    value="Receive Batch"
+                    disabled=move || receive_action.pending().get()
+                />
+            </div>
+
+            {move || {
+                receive_value
+                    .get()
+                    .and_then(Result::ok)
+                    .map(|report| {
+                        view! {
+                            <ul class="intake-report">
+                                {report
+                                    .results
+                                    .into_iter()
+                                    .map(|r| {
+                                        let (class, note) = match r.outcome {
+                                            SerialIntakeOutcome::Received { .. } => {
+                                                ("received", "Received".to_string())
+                                            }
+                                            SerialIntakeOutcome::AlreadyExists { conflicting } => {
+                                                (
+                                                    "rejected",
+                                                    format!("Already in stock: {}", conflicting.join(", ")),
+                                                )
+                                            }
+                                            SerialIntakeOutcome::DuplicateInBatch { conflicting } => {
+                                                (
+                                                    "rejected",
+                                                    format!("Scanned twice: {}", conflicting.join(", ")),
+                                                )
+                                            }
+                                        };
+                                        view! {
+                                            <li class=class>
+                                                <span class="font-mono">{r.serial_numbers.join(" / ")}</span>
+                                                <span>{note}</span>
+                                            </li>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </ul>
+                        }
+                    })
+            }}
+        </form>
+    }
+}
@@ -0,0 +1,41 @@
+use actions::get_suppliers;
+use leptos::prelude::*;
+use uuid::Uuid;
+
+/// Optional supplier picker for the receiving forms.
+#[component]
+pub fn SupplierSelect(supplier_id: RwSignal<Option<Uuid>>) -> impl IntoView {
+    let suppliers = Resource::new(|| (), |_| async move { get_suppliers().await });
+
+    view! {
+        <div class="input-group">
+            <select
+                class="input-field"
+                on:change=move |ev| supplier_id.set(Uuid::parse_str(&event_target_value(&ev)).ok())
+            >
+                <option value="">"No supplier"</option>
+                <Suspense>
+                    {move || {
+                        suppliers
+                            .get()
+                            .and_then(Result::ok)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|s| {
+                                let id = s.id;
+                                view! {
+                                    <option
+                                        value=id.to_string()
+                                        selected=move || supplier_id.get() == Some(id)
+                                    >
+                                        {s.name}
+                                    </option>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </Suspense>
+            </select>
+        </div>
+    }
+}
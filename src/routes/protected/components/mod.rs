@@ -1,3 +1,11 @@
 mod catalog;
+mod customers;
+mod inventory;
+mod pos;
+mod suppliers;
 
 pub use catalog::AddProductForm;
+pub use customers::CustomerForm;
+pub use inventory::{BulkStockForm, PurchaseOrdersPanel, ReceiveTrackedForm};
+pub use pos::{CartLine, PosCart};
+pub use suppliers::SupplierForm;
@@ -0,0 +1,250 @@
+use super::HoldForm;
+use actions::CompleteSale;
+use leptos::prelude::*;
+use models::entities::{Customer, TrackedUnit};
+use models::payloads::{CompleteSalePayload, SaleLineInput};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A row in the POS cart before checkout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CartLine {
+    pub key: Uuid,
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub tracked_unit_id: Option<Uuid>,
+    pub serial_numbers: Vec<String>,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+}
+
+impl CartLine {
+    pub fn bulk(product_id: Uuid, product_name: String, unit_price: Decimal) -> Self {
+        Self {
+            key: Uuid::new_v4(),
+            product_id,
+            product_name,
+            tracked_unit_id: None,
+            serial_numbers: Vec::new(),
+            quantity: 1,
+            unit_price,
+        }
+    }
+
+    pub fn tracked(unit: &TrackedUnit, product_name: String) -> Self {
+        Self {
+            key: Uuid::new_v4(),
+            product_id: unit.product_id,
+            product_name,
+            tracked_unit_id: Some(unit.id),
+            serial_numbers: unit.serial_numbers.clone(),
+            quantity: 1,
+            unit_price: unit.target_msrp,
+        }
+    }
+
+    pub fn line_total(&self) -> Decimal {
+        self.unit_price * Decimal::from(self.quantity)
+    }
+
+    pub(super) fn to_input(&self) -> SaleLineInput {
+        SaleLineInput {
+            product_id: self.product_id,
+            tracked_unit_id: self.tracked_unit_id,
+            quantity: self.quantity,
+            unit_price: self.unit_price,
+        }
+    }
+}
+
+pub(super) fn money(amount: Decimal) -> String {
+    format!("${:.2}", amount)
+}
+
+#[component]
+pub fn PosCart(
+    cart: RwSignal<Vec<CartLine>>,
+    customer: RwSignal<Option<Customer>>,
+) -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let sale_action = ServerAction::<CompleteSale>::new();
+    let sale_value = sale_action.value();
+
+    let total = move || cart.with(|lines| lines.iter().map(CartLine::line_total).sum::<Decimal>());
+
+    let on_checkout = move |_| {
+        let lines = cart.with(|lines| lines.iter().map(CartLine::to_input).collect());
+        let customer_id = customer.with(|c| c.as_ref().map(|c| c.id));
+        sale_action.dispatch(CompleteSale {
+            payload: CompleteSalePayload { lines, customer_id },
+        });
+    };
+
+    // Watch for server errors and display them as toasts
+    Effect::new(move |_| {
+        if let Some(Err(e)) = sale_value.get() {
+            state.update(|s| {
+                s.add_toast(Arc::new(e));
+            });
+        }
+    });
+
+    // Clear the cart once the sale is committed
+    Effect::new(move |_| {
+        if let Some(Ok(receipt)) = sale_value.get() {
+            cart.set(Vec::new());
+            customer.set(None);
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Sale Completed",
+                    format!(
+                        "Invoice #{} closed for {}.",
+                        receipt.sale.invoice_number,
+                        money(receipt.sale.total_amount)
+                    ),
+                    NotificationLevel::Success,
+                )));
+            });
+        }
+    });
+
+    let update_line = move |key: Uuid, f: Box<dyn Fn(&mut CartLine)>| {
+        cart.update(|lines| {
+            if let Some(line) = lines.iter_mut().find(|l| l.key == key) {
+                f(line);
+            }
+        });
+    };
+
+    view! {
+        <div class="pos-cart-pane">
+            <div class="cart-header">
+                <h2 class="text-lg font-bold text-primary">"Current Sale"</h2>
+                <p class="text-secondary text-sm">
+                    {move || match sale_value.get() {
+                        Some(Ok(receipt)) => {
+                            format!("Last Invoice #{}", receipt.sale.invoice_number)
+                        }
+                        _ => "New Invoice".to_string(),
+                    }}
+                </p>
+                {move || {
+                    customer
+                        .get()
+                        .map(|c| {
+                            view! {
+                                <div class="cart-customer">
+                                    <span class="text-sm">
+                                        <strong>{c.name}</strong>
+                                        " "
+                                        <span class="font-mono text-secondary">
+                                            {c.phone.unwrap_or_default()}
+                                        </span>
+                                    </span>
+                                    <button
+                                        class="btn btn-ghost"
+                                        title="Detach customer"
+                                        on:click=move |_| customer.set(None)
+                                    >
+                                        "×"
+                                    </button>
+                                </div>
+                            }
+                        })
+                }}
+            </div>
+
+            <Show
+                when=move || cart.with(|lines| !lines.is_empty())
+                fallback=|| {
+                    view! {
+                        <div class="cart-items empty-state">
+                            <svg
+                                xmlns="http://www.w3.org/2000/svg"
+                                width="40"
+                                height="40"
+                                viewBox="0 0 24 24"
+                                fill="none"
+                                stroke="currentColor"
+                                stroke-width="1.5"
+                                stroke-linecap="round"
+                                stroke-linejoin="round"
+                            >
+                                <circle cx="9" cy="21" r="1"></circle>
+                                <circle cx="20" cy="21" r="1"></circle>
+                                <path d="M1 1h4l2.68 13.39a2 2 0 0 0 2 1.61h9.72a2 2 0 0 0 2-1.61L23 6H6"></path>
+                            </svg>
+                            <h3>"Cart is Empty"</h3>
+                            <p>"Scan an item to add it to the cart."</p>
+                        </div>
+                    }
+                }
+            >
+                <ul class="cart-items">
+                    <For each=move || cart.get() key=|line| line.key let:line>
+                        <li class="cart-line">
+                            <div class="cart-line-info">
+                                <strong>{line.product_name.clone()}</strong>
+                                <span class="font-mono text-secondary text-sm">
+                                    {line.serial_numbers.join(" / ")}
+                                </span>
+                            </div>
+                            <input
+                                type="number"
+                                min="1"
+                                class="input-field cart-qty"
+                                disabled=line.tracked_unit_id.is_some()
+                                prop:value=line.quantity.to_string()
+                                on:change=move |ev| {
+                                    let qty = event_target_value(&ev).parse().unwrap_or(1).max(1);
+                                    update_line(line.key, Box::new(move |l| l.quantity = qty));
+                                }
+                            />
+                            <input
+                                type="text"
+                                inputmode="decimal"
+                                class="input-field cart-price"
+                                prop:value=format!("{:.2}", line.unit_price)
+                                on:change=move |ev| {
+                                    if let Ok(price) = Decimal::from_str(event_target_value(&ev).trim()) {
+                                        update_line(line.key, Box::new(move |l| l.unit_price = price));
+                                    }
+                                }
+                            />
+                            <button
+                                class="btn btn-ghost"
+                                on:click=move |_| cart.update(|lines| lines.retain(|l| l.key != line.key))
+                            >
+                                "×"
+                            </button>
+                        </li>
+                    </For>
+                </ul>
+            </Show>
+
+            <div class="cart-footer">
+                <div class="totals-row">
+                    <span class="text-secondary">"Subtotal"</span>
+                    <span class="text-primary">{move || money(total())}</span>
+                </div>
+                <div class="totals-row grand-total">
+                    <span class="text-primary">"Total"</span>
+                    <span class="amount">{move || money(total())}</span>
+                </div>
+                <button
+                    class="btn btn-primary w-full text-lg font-bold py-4"
+                    disabled=move || sale_action.pending().get() || cart.with(|lines| lines.is_empty())
+                    on:click=on_checkout
+                >
+                    "Complete Sale"
+                </button>
+                <HoldForm cart />
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,145 @@
+use super::cart::{money, CartLine};
+use actions::CreateReservation;
+use chrono::{Days, NaiveDate, Utc};
+use leptos::prelude::*;
+use models::payloads::CreateReservationPayload;
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// How long a hold lasts unless the handler picks another date.
+const DEFAULT_HOLD_DAYS: u64 = 7;
+
+/// Puts the whole cart on hold for a named customer instead of selling it.
+#[component]
+pub fn HoldForm(cart: RwSignal<Vec<CartLine>>) -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let default_expiry = Utc::now()
+        .date_naive()
+        .checked_add_days(Days::new(DEFAULT_HOLD_DAYS))
+        .map(|d| d.to_string())
+        .unwrap_or_default();
+
+    let (customer_name, set_customer_name) = signal(String::new());
+    let (customer_phone, set_customer_phone) = signal(String::new());
+    let (deposit, set_deposit) = signal(String::new());
+    let (expires_on, set_expires_on) = signal(default_expiry.clone());
+    let hold_action = ServerAction::<CreateReservation>::new();
+    let hold_value = hold_action.value();
+
+    let warn = move |message: &str| {
+        state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                "Input Error",
+                message,
+                NotificationLevel::Warning,
+            )))
+        });
+    };
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let deposit = match deposit.get().trim() {
+            "" => Decimal::ZERO,
+            amount => match Decimal::from_str(amount) {
+                Ok(amount) => amount,
+                Err(_) => return warn("Enter a valid deposit."),
+            },
+        };
+        // The hold runs to the end of the chosen day
+        let Some(expires_at) = NaiveDate::from_str(&expires_on.get())
+            .ok()
+            .and_then(|d| d.and_hms_opt(23, 59, 59))
+            .map(|d| d.and_utc())
+        else {
+            return warn("Pick the date the hold runs until.");
+        };
+
+        let lines = cart.with(|lines| lines.iter().map(CartLine::to_input).collect());
+        hold_action.dispatch(CreateReservation {
+            payload: CreateReservationPayload {
+                customer_name: customer_name.get(),
+                customer_phone: Some(customer_phone.get()).filter(|p| !p.trim().is_empty()),
+                deposit,
+                expires_at,
+                lines,
+            },
+        });
+    };
+
+    Effect::new(move |_| match hold_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(detail)) => {
+            cart.set(Vec::new());
+            set_customer_name.set(String::new());
+            set_customer_phone.set(String::new());
+            set_deposit.set(String::new());
+            set_expires_on.set(default_expiry.clone());
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Reservation Created",
+                    format!(
+                        "Held for {} until {} with a {} deposit.",
+                        detail.reservation.customer_name,
+                        detail.reservation.expires_at.format("%Y-%m-%d"),
+                        money(detail.reservation.deposit)
+                    ),
+                    NotificationLevel::Success,
+                )))
+            });
+        }
+        None => {}
+    });
+
+    view! {
+        <details class="cart-hold">
+            <summary>"Hold for Customer"</summary>
+            <form on:submit=on_submit>
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder="Customer name"
+                    on:input=move |ev| set_customer_name.set(event_target_value(&ev))
+                    prop:value=customer_name
+                />
+                <input
+                    type="tel"
+                    class="input-field"
+                    placeholder="Phone (optional)"
+                    on:input=move |ev| set_customer_phone.set(event_target_value(&ev))
+                    prop:value=customer_phone
+                />
+                <input
+                    type="text"
+                    inputmode="decimal"
+                    class="input-field"
+                    placeholder="Deposit"
+                    on:input=move |ev| set_deposit.set(event_target_value(&ev))
+                    prop:value=deposit
+                />
+                <label class="text-secondary text-sm">
+                    "Hold until"
+                    <input
+                        type="date"
+                        class="input-field"
+                        on:input=move |ev| set_expires_on.set(event_target_value(&ev))
+                        prop:value=expires_on
+                    />
+                </label>
+                <button
+                    type="submit"
+                    class="btn btn-ghost w-full"
+                    disabled=move || {
+                        hold_action.pending().get() || cart.with(|lines| lines.is_empty())
+                    }
+                >
+                    "Reserve Items"
+                </button>
+            </form>
+        </details>
+    }
+}
@@ -0,0 +1,5 @@
+mod cart;
+mod hold_form;
+
+pub use cart::{CartLine, PosCart};
+pub use hold_form::HoldForm;
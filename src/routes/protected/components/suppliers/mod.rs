@@ -0,0 +1,3 @@
+mod supplier_form;
+
+pub use supplier_form::SupplierForm;
@@ -0,0 +1,348 @@
+use super::components::CustomerForm;
+use actions::{
+    get_customer_history, get_customers, AddCustomer, DeleteCustomer, Permission, UpdateCustomer,
+};
+use chrono::Utc;
+use leptos::prelude::*;
+use models::entities::{Customer, CustomerHistory};
+use models::payloads::CustomerPayload;
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::{AuthState, SystemState};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[component]
+pub fn CustomersPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let can_delete = move || {
+        state.with(|s| match &s.auth_state {
+            AuthState::Authenticated(user) => {
+                Permission::DeleteCustomers.is_granted_to(user.handler_role)
+            }
+            _ => false,
+        })
+    };
+    let (search, set_search) = signal(String::new());
+    let selected = RwSignal::new(None::<Uuid>);
+    let add_action = ServerAction::<AddCustomer>::new();
+    let update_action = ServerAction::<UpdateCustomer>::new();
+    let delete_action = ServerAction::<DeleteCustomer>::new();
+
+    let customers = Resource::new(
+        move || {
+            (
+                search.get(),
+                add_action.version().get(),
+                update_action.version().get(),
+                delete_action.version().get(),
+            )
+        },
+        |(search, _, _, _)| async move { get_customers(Some(search)).await },
+    );
+    let history = Resource::new(
+        move || (selected.get(), update_action.version().get()),
+        |(selected, _)| async move {
+            match selected {
+                Some(customer_id) => get_customer_history(customer_id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+
+    let notify = move |title: &str, message: String| {
+        state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                title,
+                message,
+                NotificationLevel::Success,
+            )))
+        });
+    };
+
+    Effect::new(move |_| match add_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(customer)) => {
+            notify("Customer Added", format!("'{}' is on file.", customer.name));
+            selected.set(Some(customer.id));
+        }
+        None => {}
+    });
+
+    Effect::new(move |_| match update_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(customer)) => {
+            notify("Customer Updated", format!("Saved '{}'.", customer.name));
+        }
+        None => {}
+    });
+
+    Effect::new(move |_| match delete_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(())) => {
+            selected.set(None);
+            notify(
+                "Customer Deleted",
+                "Their past sales are kept without a customer.".to_string(),
+            );
+        }
+        None => {}
+    });
+
+    let row = move |customer: Customer| {
+        let id = customer.id;
+        view! {
+            <tr
+                class="cursor-pointer"
+                class:selected=move || selected.get() == Some(id)
+                on:click=move |_| selected.set(Some(id))
+            >
+                <td>{customer.name}</td>
+                <td class="font-mono">{customer.phone.unwrap_or_default()}</td>
+                <td>{customer.email.unwrap_or_default()}</td>
+                <td class="text-secondary text-sm">{customer.notes.unwrap_or_default()}</td>
+            </tr>
+        }
+    };
+
+    let history_panel = move |history: CustomerHistory| {
+        let customer = history.customer;
+        let customer_id = customer.id;
+        let now = Utc::now();
+        let initial = CustomerPayload {
+            name: customer.name.clone(),
+            phone: customer.phone.clone(),
+            email: customer.email.clone(),
+            notes: customer.notes.clone(),
+        };
+
+        view! {
+            <section class="system-content-area mt-4">
+                <div class="flex justify-between items-center">
+                    <h2 class="text-lg font-bold text-primary">{customer.name}</h2>
+                    <Show when=can_delete>
+                        <button
+                            class="btn btn-ghost"
+                            disabled=move || delete_action.pending().get()
+                            on:click=move |_| {
+                                delete_action.dispatch(DeleteCustomer { customer_id });
+                            }
+                        >
+                            "Delete Customer"
+                        </button>
+                    </Show>
+                </div>
+
+                <CustomerForm
+                    initial
+                    submit_label="Save Changes"
+                    pending=update_action.pending()
+                    on_submit=Callback::new(move |payload| {
+                        update_action.dispatch(UpdateCustomer { customer_id, payload });
+                    })
+                />
+
+                <h3 class="mt-4 font-bold">"Sales"</h3>
+                {if history.sales.is_empty() {
+                    view! { <p class="text-secondary">"No sales recorded."</p> }.into_any()
+                } else {
+                    view! {
+                        <table class="data-table">
+                            <thead>
+                                <tr>
+                                    <th>"Invoice"</th>
+                                    <th>"Date"</th>
+                                    <th>"Total"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {history
+                                    .sales
+                                    .into_iter()
+                                    .map(|sale| {
+                                        view! {
+                                            <tr>
+                                                <td class="font-mono">
+                                                    {format!("#{}", sale.invoice_number)}
+                                                </td>
+                                                <td>{sale.created_at.format("%Y-%m-%d").to_string()}</td>
+                                                <td>{sale.total_amount.to_string()}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }
+                        .into_any()
+                }}
+
+                <h3 class="mt-4 font-bold">"Returns"</h3>
+                {if history.returns.is_empty() {
+                    view! { <p class="text-secondary">"No returns recorded."</p> }.into_any()
+                } else {
+                    view! {
+                        <table class="data-table">
+                            <thead>
+                                <tr>
+                                    <th>"Invoice"</th>
+                                    <th>"Date"</th>
+                                    <th>"Refund"</th>
+                                    <th>"Method"</th>
+                                    <th>"Reason"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {history
+                                    .returns
+                                    .into_iter()
+                                    .map(|r| {
+                                        view! {
+                                            <tr>
+                                                <td class="font-mono">
+                                                    {format!("#{}", r.invoice_number)}
+                                                </td>
+                                                <td>{r.created_at.format("%Y-%m-%d").to_string()}</td>
+                                                <td>{r.refund_amount.to_string()}</td>
+                                                <td>{r.refund_method.label()}</td>
+                                                <td>{r.reason.unwrap_or_default()}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }
+                        .into_any()
+                }}
+
+                <h3 class="mt-4 font-bold">"Serialized Units"</h3>
+                {if history.units.is_empty() {
+                    view! { <p class="text-secondary">"No serialized units sold."</p> }.into_any()
+                } else {
+                    view! {
+                        <table class="data-table">
+                            <thead>
+                                <tr>
+                                    <th>"Product"</th>
+                                    <th>"Serials"</th>
+                                    <th>"Invoice"</th>
+                                    <th>"Warranty"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {history
+                                    .units
+                                    .into_iter()
+                                    .map(|unit| {
+                                        let warranty = match unit.warranty_until {
+                                            None => "None".to_string(),
+                                            Some(until) if unit.is_covered(now) => {
+                                                format!("Covered until {}", until.format("%Y-%m-%d"))
+                                            }
+                                            Some(until) => {
+                                                format!("Expired {}", until.format("%Y-%m-%d"))
+                                            }
+                                        };
+                                        view! {
+                                            <tr>
+                                                <td>{unit.product_name}</td>
+                                                <td class="font-mono text-sm">
+                                                    {unit.serial_numbers.join(" / ")}
+                                                </td>
+                                                <td class="font-mono">
+                                                    {format!("#{}", unit.invoice_number)}
+                                                </td>
+                                                <td>
+                                                    <span class="badge">{warranty}</span>
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }
+                        .into_any()
+                }}
+            </section>
+        }
+    };
+
+    view! {
+        <div class="system-page-container">
+            <header class="system-page-header">
+                <div>
+                    <h1>"Customers"</h1>
+                    <p>
+                        "Customer records, their purchases and the warranty on units they bought. Type a customer's phone into the POS scanner to attach them to a sale."
+                    </p>
+                </div>
+            </header>
+
+            <details class="system-content-area mb-4">
+                <summary class="cursor-pointer font-bold">"Add Customer"</summary>
+                <CustomerForm
+                    initial=CustomerPayload::default()
+                    submit_label="Save Customer"
+                    pending=add_action.pending()
+                    on_submit=Callback::new(move |payload| {
+                        add_action.dispatch(AddCustomer { payload });
+                    })
+                />
+            </details>
+
+            <div class="catalog-filters">
+                <input
+                    type="search"
+                    class="input-field"
+                    placeholder="Search by name, phone or email..."
+                    on:input=move |ev| set_search.set(event_target_value(&ev))
+                    prop:value=search
+                />
+            </div>
+
+            <div class="system-content-area">
+                <Suspense fallback=|| {
+                    view! { <p class="text-secondary">"Loading customers..."</p> }
+                }>
+                    {move || Suspend::new(async move {
+                        match customers.await {
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                            Ok(list) if list.is_empty() => {
+                                view! { <p class="text-secondary">"No customers found."</p> }
+                                    .into_any()
+                            }
+                            Ok(list) => {
+                                view! {
+                                    <table class="data-table">
+                                        <thead>
+                                            <tr>
+                                                <th>"Name"</th>
+                                                <th>"Phone"</th>
+                                                <th>"Email"</th>
+                                                <th>"Notes"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>{list.into_iter().map(row).collect_view()}</tbody>
+                                    </table>
+                                }
+                                    .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+
+            <Suspense fallback=|| ()>
+                {move || Suspend::new(async move {
+                    match history.await {
+                        Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                        Ok(None) => ().into_any(),
+                        Ok(Some(history)) => history_panel(history).into_any(),
+                    }
+                })}
+            </Suspense>
+        </div>
+    }
+}
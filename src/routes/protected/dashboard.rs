@@ -1,11 +1,12 @@
 use crate::components::InlineLoader;
-use actions::check_system_health;
+use actions::{check_system_health, get_session_stats};
 use leptos::prelude::*;
 
 /// Dashboard page component (protected)
 #[component]
 pub fn DashboardPage() -> impl IntoView {
     let status = Resource::new(|| {}, async |_| check_system_health().await);
+    let session_stats = Resource::new(|| {}, async |_| get_session_stats().await);
 
     view! {
         <div class="dashboard-container">
@@ -62,7 +63,28 @@ pub fn DashboardPage() -> impl IntoView {
                 <div class="card glass-card p-6 border border-white/5 flex flex-col justify-between">
                     <div>
                         <h3 class="text-lg font-bold text-white mb-2">"Active Sessions"</h3>
-                        <p class="text-sm text-gray-400">"1,204 online now"</p>
+                        <Suspense fallback=|| view! { <p class="text-sm text-gray-400">"Counting..."</p> }>
+                            {move || Suspend::new(async move {
+                                match session_stats.await {
+                                    Ok(stats) => {
+                                        view! {
+                                            <p class="text-sm text-gray-400">
+                                                {format!(
+                                                    "{} online now // {} signed in",
+                                                    stats.online_handlers,
+                                                    stats.active_sessions,
+                                                )}
+                                            </p>
+                                        }
+                                            .into_any()
+                                    }
+                                    Err(e) => {
+                                        view! { <p class="text-sm text-red-400">{e.to_string()}</p> }
+                                            .into_any()
+                                    }
+                                }
+                            })}
+                        </Suspense>
                     </div>
                     <div class="mt-4 pt-4 border-t border-[var(--border-default)]">
                         <span class="inline-flex items-center gap-1.5 px-2.5 py-1 rounded-full text-xs font-medium bg-green-500/10 text-green-400">
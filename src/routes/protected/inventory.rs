@@ -1,7 +1,17 @@
+use super::components::{BulkStockForm, PurchaseOrdersPanel, ReceiveTrackedForm};
 use leptos::prelude::*;
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InventoryTab {
+    Tracked,
+    Bulk,
+    PurchaseOrders,
+}
+
 #[component]
 pub fn InventoryPage() -> impl IntoView {
+    let tab = RwSignal::new(InventoryTab::Tracked);
+
     view! {
         <div class="system-page-container">
             <header class="system-page-header">
@@ -12,8 +22,27 @@ pub fn InventoryPage() -> impl IntoView {
             </header>
 
             <div class="tabs-header">
-                <button class="tab-btn active">"Tracked Units (Rapid Scan)"</button>
-                <button class="tab-btn">"Untracked (Bulk Stock)"</button>
+                <button
+                    class="tab-btn"
+                    class:active=move || tab.get() == InventoryTab::Tracked
+                    on:click=move |_| tab.set(InventoryTab::Tracked)
+                >
+                    "Tracked Units (Rapid Scan)"
+                </button>
+                <button
+                    class="tab-btn"
+                    class:active=move || tab.get() == InventoryTab::Bulk
+                    on:click=move |_| tab.set(InventoryTab::Bulk)
+                >
+                    "Untracked (Bulk Stock)"
+                </button>
+                <button
+                    class="tab-btn"
+                    class:active=move || tab.get() == InventoryTab::PurchaseOrders
+                    on:click=move |_| tab.set(InventoryTab::PurchaseOrders)
+                >
+                    "Purchase Orders"
+                </button>
             </div>
 
             // AI Info Box explaining HOW to use the scanner
@@ -38,40 +67,26 @@ pub fn InventoryPage() -> impl IntoView {
                 <div>
                     <h4>"AI Assistant: How do I receive a shipment?"</h4>
                     <p>
-                        "1. First, select the Product (e.g., iPhone 15) from the dropdown above."
-                        <br /> "2. Click inside the scanner box below."<br />
+                        "1. First, select the Product (e.g., iPhone 15) from the dropdown below."
+                        <br /> "2. Click inside the scanner box and scan one box per line."<br />
                         "3. Start scanning the barcodes on the boxes. Our system will instantly capture all IMEIs and save 50 units in under a second."
+                        <br />
+                        "4. If the delivery was ordered, pick its purchase order line to see what is still expected. The order closes itself once everything has arrived."
                     </p>
                 </div>
             </div>
 
-            <div class="system-content-area border-dashed">
-                // Future Scanning Engine UI goes here
-                <div class="empty-state">
-                    <svg
-                        xmlns="http://www.w3.org/2000/svg"
-                        width="48"
-                        height="48"
-                        viewBox="0 0 24 24"
-                        fill="none"
-                        stroke="currentColor"
-                        stroke-width="1.5"
-                        stroke-linecap="round"
-                        stroke-linejoin="round"
-                        class="mb-4 opacity-50"
-                    >
-                        <path d="M3 7V5a2 2 0 0 1 2-2h2"></path>
-                        <path d="M17 3h2a2 2 0 0 1 2 2v2"></path>
-                        <path d="M21 17v2a2 2 0 0 1-2 2h-2"></path>
-                        <path d="M7 21H5a2 2 0 0 1-2-2v-2"></path>
-                        <path d="M8 7v10"></path>
-                        <path d="M12 7v10"></path>
-                        <path d="M16 7v10"></path>
-                    </svg>
-                    <h3>"Ready to Scan"</h3>
-                    <p>"Waiting for barcode input..."</p>
-                </div>
-            </div>
+            <Show when=move || tab.get() == InventoryTab::Tracked>
+                <ReceiveTrackedForm />
+            </Show>
+
+            <Show when=move || tab.get() == InventoryTab::Bulk>
+                <BulkStockForm />
+            </Show>
+
+            <Show when=move || tab.get() == InventoryTab::PurchaseOrders>
+                <PurchaseOrdersPanel />
+            </Show>
         </div>
     }
 }
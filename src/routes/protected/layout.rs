@@ -14,29 +14,22 @@ pub fn LayoutPage() -> impl IntoView {
                 navigate("/auth/login", Default::default());
             }
             AuthState::Authenticated(user) => {
-                // If there's a subdomain in the URL, verify it belongs to this user!
-                if let Some(active_handle) = &current_state.active_workspace_handle {
+                // The server turns away foreign sessions on page loads; this catches a
+                // sign-in on another workspace's subdomain without a reload.
+                if let (Some(active_handle), Some(base_url)) = (
+                    &current_state.active_workspace_handle,
+                    &current_state.base_url,
+                ) {
                     if active_handle != &user.workspace_handle {
-                        // The user tried to access a workspace they don't own!
-                        // Redirect them to the absolute base domain safely.
-                        // In local dev, we check window location to strip the subdomain.
                         #[cfg(feature = "hydrate")]
                         if let Some(window) = web_sys::window() {
-                            let hostname = window.location().hostname().unwrap_or_default();
-
-                            // If we are on `fake.localhost`, redirect to `localhost`
-                            if hostname.ends_with(".localhost") {
-                                let _ = window
-                                    .location()
-                                    .set_href("http://localhost:3000/auth/login");
-                            }
-                            // If we are on `fake.sharpsystem.app`, redirect to `sharpsystem.app`
-                            else if hostname.ends_with(".sharpsystem.app") {
-                                let _ = window
-                                    .location()
-                                    .set_href("https://sharpsystem.app/auth/login");
-                            }
+                            let _ = window.location().set_href(&format!(
+                                "{}/system/dashboard",
+                                actions::workspace_url(base_url, &user.workspace_handle)
+                            ));
                         }
+                        #[cfg(not(feature = "hydrate"))]
+                        let _ = base_url;
                     }
                 }
             }
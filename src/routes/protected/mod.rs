@@ -1,16 +1,30 @@
 // Protected routes - Requires authentication
 // Dashboard, Settings, etc.
 
+mod audit;
 mod catalog;
 mod components;
+mod customers;
 mod dashboard;
 mod inventory;
 mod layout;
 mod pos;
+mod reservations;
+mod returns;
+mod security;
+mod staff;
+mod suppliers;
 
+pub use audit::AuditLogPage;
 pub use catalog::CatalogPage;
 pub(super) use components::*;
+pub use customers::CustomersPage;
 pub use dashboard::DashboardPage;
 pub use inventory::InventoryPage;
 pub use layout::LayoutPage;
 pub use pos::PosPage;
+pub use reservations::ReservationsPage;
+pub use returns::ReturnsPage;
+pub use security::SecurityPage;
+pub use staff::StaffPage;
+pub use suppliers::SuppliersPage;
@@ -1,50 +1,115 @@
+use super::components::{CartLine, PosCart};
+use actions::UniversalLookup;
 use leptos::prelude::*;
+use models::entities::{Customer, LookupResult, StockedProduct, TrackedUnitStatus};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use std::sync::Arc;
 
 #[component]
 pub fn PosPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let cart = RwSignal::new(Vec::<CartLine>::new());
+    let customer = RwSignal::new(None::<Customer>);
+    let results = RwSignal::new(Vec::<StockedProduct>::new());
+    let (query, set_query) = signal(String::new());
+    let lookup_action = ServerAction::<UniversalLookup>::new();
+    let lookup_value = lookup_action.value();
+
+    let notify = move |title: &str, message: String, level: NotificationLevel| {
+        state.update(|s| s.add_toast(Arc::new(Notification::new(title, message, level))));
+    };
+
+    // Bulk products go straight into the cart; tracked ones need a specific unit scanned
+    let add_product = move |item: &StockedProduct| {
+        if item.product.is_tracked {
+            notify(
+                "Scan Required",
+                format!(
+                    "Scan the serial of the '{}' unit being sold.",
+                    item.product.name
+                ),
+                NotificationLevel::Info,
+            );
+        } else if item.on_hand < 1 {
+            notify(
+                "Out of Stock",
+                format!("'{}' has no stock on hand.", item.product.name),
+                NotificationLevel::Warning,
+            );
+        } else {
+            let line = CartLine::bulk(
+                item.product.id,
+                item.product.name.clone(),
+                item.retail_price,
+            );
+            cart.update(|lines| lines.push(line));
+        }
+    };
+
+    let on_scan = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        lookup_action.dispatch(UniversalLookup { query: query.get() });
+    };
+
+    Effect::new(move |_| match lookup_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(LookupResult::TrackedUnitHit { unit, product })) => {
+            set_query.set(String::new());
+            let in_cart = cart
+                .with_untracked(|lines| lines.iter().any(|l| l.tracked_unit_id == Some(unit.id)));
+            if in_cart {
+                notify(
+                    "Already Scanned",
+                    "This unit is already in the cart.".to_string(),
+                    NotificationLevel::Warning,
+                );
+            } else if unit.status != TrackedUnitStatus::InStock {
+                notify(
+                    "Unavailable",
+                    format!(
+                        "This '{}' is not in stock ({:?}).",
+                        product.name, unit.status
+                    ),
+                    NotificationLevel::Warning,
+                );
+            } else {
+                cart.update(|lines| lines.push(CartLine::tracked(&unit, product.name)));
+            }
+        }
+        Some(Ok(LookupResult::ProductHit { products, exact })) => {
+            if exact && products.len() == 1 && !products[0].product.is_tracked {
+                set_query.set(String::new());
+                add_product(&products[0]);
+            } else {
+                results.set(products);
+            }
+        }
+        Some(Ok(LookupResult::CustomerHit { customer: hit })) => {
+            set_query.set(String::new());
+            notify(
+                "Customer Attached",
+                format!("This sale will be recorded against {}.", hit.name),
+                NotificationLevel::Info,
+            );
+            customer.set(Some(*hit));
+        }
+        Some(Ok(LookupResult::NoMatch)) => {
+            results.set(Vec::new());
+            notify(
+                "No Match",
+                format!("Nothing matches '{}'.", query.get_untracked()),
+                NotificationLevel::Info,
+            );
+        }
+        None => {}
+    });
+
     view! {
         <div class="pos-layout">
             // Left Pane: The Active Invoice
-            <div class="pos-cart-pane">
-                <div class="cart-header">
-                    <h2 class="text-lg font-bold text-primary">"Current Sale"</h2>
-                    <p class="text-secondary text-sm">"Invoice #REQ-001"</p>
-                </div>
-
-                <div class="cart-items empty-state">
-                    <svg
-                        xmlns="http://www.w3.org/2000/svg"
-                        width="40"
-                        height="40"
-                        viewBox="0 0 24 24"
-                        fill="none"
-                        stroke="currentColor"
-                        stroke-width="1.5"
-                        stroke-linecap="round"
-                        stroke-linejoin="round"
-                    >
-                        <circle cx="9" cy="21" r="1"></circle>
-                        <circle cx="20" cy="21" r="1"></circle>
-                        <path d="M1 1h4l2.68 13.39a2 2 0 0 0 2 1.61h9.72a2 2 0 0 0 2-1.61L23 6H6"></path>
-                    </svg>
-                    <h3>"Cart is Empty"</h3>
-                    <p>"Scan an item to add it to the cart."</p>
-                </div>
-
-                <div class="cart-footer">
-                    <div class="totals-row">
-                        <span class="text-secondary">"Subtotal"</span>
-                        <span class="text-primary">"$0.00"</span>
-                    </div>
-                    <div class="totals-row grand-total">
-                        <span class="text-primary">"Total"</span>
-                        <span class="amount">"$0.00"</span>
-                    </div>
-                    <button class="btn btn-primary w-full text-lg font-bold py-4">
-                        "Complete Sale"
-                    </button>
-                </div>
-            </div>
+            <PosCart cart customer />
 
             // Right Pane: Universal Search & Catalog Grid
             <div class="pos-scanner-pane">
@@ -75,6 +140,8 @@ pub fn PosPage() -> impl IntoView {
                             " -> We instantly find the specific Tracked phone and add it to the receipt."
                             <br /> "• " <strong>"Type 'Cable'"</strong>
                             " -> We find all matching Untracked items so you can specify a quantity."
+                            <br /> "• " <strong>"Type a phone number"</strong>
+                            " -> We attach the matching customer to the sale."
                         </p>
                     </div>
                 </div>
@@ -96,33 +163,66 @@ pub fn PosPage() -> impl IntoView {
                             <line x1="21" y1="21" x2="16.65" y2="16.65"></line>
                         </svg>
                     </div>
-                    <input
-                        type="text"
-                        placeholder="Scan Barcode, IMEI, or Search Product..."
-                        autofocus
-                    />
+                    <form on:submit=on_scan>
+                        <input
+                            type="text"
+                            placeholder="Scan Barcode, IMEI, Customer Phone, or Search Product..."
+                            autofocus
+                            prop:value=query
+                            on:input=move |ev| set_query.set(event_target_value(&ev))
+                        />
+                    </form>
                 </div>
 
-                <div class="system-content-area border-dashed">
-                    <div class="empty-state">
-                        <svg
-                            xmlns="http://www.w3.org/2000/svg"
-                            width="48"
-                            height="48"
-                            viewBox="0 0 24 24"
-                            fill="none"
-                            stroke="currentColor"
-                            stroke-width="1.5"
-                            stroke-linecap="round"
-                            stroke-linejoin="round"
-                            class="mb-4 opacity-50"
-                        >
-                            <path d="M4 7V4h16v3M9 20h6M12 4v16" />
-                        </svg>
-                        <h3>"Scanner Active"</h3>
-                        <p>"The system is listening. Waiting for input..."</p>
+                <Show
+                    when=move || results.with(|r| !r.is_empty())
+                    fallback=|| {
+                        view! {
+                            <div class="system-content-area border-dashed">
+                                <div class="empty-state">
+                                    <svg
+                                        xmlns="http://www.w3.org/2000/svg"
+                                        width="48"
+                                        height="48"
+                                        viewBox="0 0 24 24"
+                                        fill="none"
+                                        stroke="currentColor"
+                                        stroke-width="1.5"
+                                        stroke-linecap="round"
+                                        stroke-linejoin="round"
+                                        class="mb-4 opacity-50"
+                                    >
+                                        <path d="M4 7V4h16v3M9 20h6M12 4v16" />
+                                    </svg>
+                                    <h3>"Scanner Active"</h3>
+                                    <p>"The system is listening. Waiting for input..."</p>
+                                </div>
+                            </div>
+                        }
+                    }
+                >
+                    <div class="system-content-area">
+                        <div class="pos-product-grid">
+                            <For each=move || results.get() key=|item| item.product.id let:item>
+                                <button
+                                    class="pos-product-tile"
+                                    on:click={
+                                        let item = item.clone();
+                                        move |_| add_product(&item)
+                                    }
+                                >
+                                    <strong>{item.product.name.clone()}</strong>
+                                    <span class="text-secondary text-sm">
+                                        {item.product.category.clone().unwrap_or_default()}
+                                    </span>
+                                    <span class="font-mono text-sm">
+                                        {format!("{} on hand", item.on_hand)}
+                                    </span>
+                                </button>
+                            </For>
+                        </div>
                     </div>
-                </div>
+                </Show>
             </div>
         </div>
     }
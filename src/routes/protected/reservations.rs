@@ -0,0 +1,198 @@
+use actions::{get_reservations, CancelReservation, ConvertReservation};
+use leptos::prelude::*;
+use models::entities::{ReservationDetail, ReservationStatus};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use std::sync::Arc;
+
+#[component]
+pub fn ReservationsPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let status = RwSignal::new(Some(ReservationStatus::Active));
+    let convert_action = ServerAction::<ConvertReservation>::new();
+    let cancel_action = ServerAction::<CancelReservation>::new();
+    let reservations = Resource::new(
+        move || {
+            (
+                status.get(),
+                convert_action.version().get(),
+                cancel_action.version().get(),
+            )
+        },
+        |(status, _, _)| async move { get_reservations(status).await },
+    );
+
+    Effect::new(move |_| match convert_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(converted)) => state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                "Reservation Sold",
+                format!(
+                    "Invoice #{} opened. Collect {} after the {} deposit.",
+                    converted.receipt.sale.invoice_number,
+                    converted.balance_due,
+                    converted.reservation.deposit
+                ),
+                NotificationLevel::Success,
+            )))
+        }),
+        None => {}
+    });
+
+    Effect::new(move |_| match cancel_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(reservation)) => state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                "Reservation Cancelled",
+                format!(
+                    "Stock held for {} is back on sale. Refund the {} deposit if owed.",
+                    reservation.customer_name, reservation.deposit
+                ),
+                NotificationLevel::Info,
+            )))
+        }),
+        None => {}
+    });
+
+    let busy = move || convert_action.pending().get() || cancel_action.pending().get();
+
+    let row = move |detail: ReservationDetail| {
+        let id = detail.reservation.id;
+        let is_active = detail.reservation.status == ReservationStatus::Active;
+        let total = detail.total_amount();
+        let balance = detail.balance_due();
+        let reservation = detail.reservation;
+        view! {
+            <tr>
+                <td>
+                    {reservation.customer_name}
+                    <div class="text-secondary text-sm">
+                        {reservation.customer_phone.unwrap_or_default()}
+                    </div>
+                </td>
+                <td>
+                    {detail
+                        .items
+                        .into_iter()
+                        .map(|item| {
+                            view! {
+                                <div>
+                                    {format!("{} × {}", item.quantity, item.product_name)}
+                                    <span class="text-secondary text-sm font-mono">
+                                        {item
+                                            .serial_numbers
+                                            .map(|s| format!(" {}", s.join(" / ")))
+                                            .unwrap_or_default()}
+                                    </span>
+                                </div>
+                            }
+                        })
+                        .collect_view()}
+                </td>
+                <td>{total.to_string()}</td>
+                <td>{reservation.deposit.to_string()}</td>
+                <td>{balance.to_string()}</td>
+                <td>{reservation.expires_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                <td>
+                    <span class="badge">{reservation.status.label()}</span>
+                </td>
+                <td>
+                    <Show when=move || is_active>
+                        <div class="flex gap-2">
+                            <button
+                                class="btn btn-primary"
+                                disabled=busy
+                                on:click=move |_| {
+                                    convert_action.dispatch(ConvertReservation { reservation_id: id });
+                                }
+                            >
+                                "Sell"
+                            </button>
+                            <button
+                                class="btn btn-ghost"
+                                disabled=busy
+                                on:click=move |_| {
+                                    cancel_action.dispatch(CancelReservation { reservation_id: id });
+                                }
+                            >
+                                "Release"
+                            </button>
+                        </div>
+                    </Show>
+                </td>
+            </tr>
+        }
+    };
+
+    view! {
+        <div class="system-page-container">
+            <header class="system-page-header">
+                <div>
+                    <h1>"Reservations"</h1>
+                    <p>
+                        "Stock held for customers. Held items cannot be sold at the till until they are released."
+                    </p>
+                </div>
+            </header>
+
+            <div class="catalog-filters">
+                <select
+                    class="input-field"
+                    on:change=move |ev| {
+                        status
+                            .set(
+                                event_target_value(&ev)
+                                    .parse::<usize>()
+                                    .ok()
+                                    .and_then(|i| ReservationStatus::ALL.get(i).copied()),
+                            );
+                    }
+                >
+                    {ReservationStatus::ALL
+                        .iter()
+                        .enumerate()
+                        .map(|(i, s)| view! { <option value=i.to_string()>{s.label()}</option> })
+                        .collect_view()}
+                    <option value="">"All Reservations"</option>
+                </select>
+            </div>
+
+            <div class="system-content-area">
+                <Suspense fallback=|| {
+                    view! { <p class="text-secondary">"Loading reservations..."</p> }
+                }>
+                    {move || Suspend::new(async move {
+                        match reservations.await {
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                            Ok(list) if list.is_empty() => {
+                                view! { <p class="text-secondary">"No reservations to show."</p> }
+                                    .into_any()
+                            }
+                            Ok(list) => {
+                                view! {
+                                    <table class="data-table">
+                                        <thead>
+                                            <tr>
+                                                <th>"Customer"</th>
+                                                <th>"Items"</th>
+                                                <th>"Total"</th>
+                                                <th>"Deposit"</th>
+                                                <th>"Balance"</th>
+                                                <th>"Expires"</th>
+                                                <th>"Status"</th>
+                                                <th></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>{list.into_iter().map(row).collect_view()}</tbody>
+                                    </table>
+                                }
+                                    .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
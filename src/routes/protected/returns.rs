@@ -0,0 +1,311 @@
+use actions::{get_returnable_sale, ProcessReturn};
+use leptos::prelude::*;
+use models::entities::{RefundMethod, ReturnCondition, ReturnableSale};
+use models::payloads::{ProcessReturnPayload, ReturnLineInput};
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[component]
+pub fn ReturnsPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let (invoice_input, set_invoice_input) = signal(String::new());
+    let invoice = RwSignal::new(None::<i32>);
+    let refresh = RwSignal::new(0u32);
+    let sale = Resource::new(
+        move || (invoice.get(), refresh.get()),
+        |(invoice, _)| async move {
+            match invoice {
+                Some(number) => get_returnable_sale(number).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+    let return_action = ServerAction::<ProcessReturn>::new();
+    let return_value = return_action.value();
+
+    let on_find = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        match invoice_input
+            .get()
+            .trim()
+            .trim_start_matches('#')
+            .parse::<i32>()
+        {
+            Ok(number) => invoice.set(Some(number)),
+            Err(_) => state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Input Error",
+                    "Enter the invoice number printed on the receipt.",
+                    NotificationLevel::Warning,
+                )))
+            }),
+        }
+    };
+
+    Effect::new(move |_| match return_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(receipt)) => {
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Return Processed",
+                    format!(
+                        "Refunded {} by {}.",
+                        receipt.sale_return.refund_amount,
+                        receipt.sale_return.refund_method.label()
+                    ),
+                    NotificationLevel::Success,
+                )))
+            });
+            refresh.update(|r| *r += 1);
+        }
+        None => {}
+    });
+
+    view! {
+        <div class="system-page-container">
+            <header class="system-page-header">
+                <div>
+                    <h1>"Returns"</h1>
+                    <p>"Take goods back against a completed sale and record the refund."</p>
+                </div>
+            </header>
+
+            <form class="catalog-filters" on:submit=on_find>
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder="Invoice number"
+                    on:input=move |ev| set_invoice_input.set(event_target_value(&ev))
+                    prop:value=invoice_input
+                />
+                <button type="submit" class="btn btn-primary">
+                    "Find Sale"
+                </button>
+            </form>
+
+            <div class="system-content-area">
+                <Suspense fallback=|| view! { <p class="text-secondary">"Loading sale..."</p> }>
+                    {move || Suspend::new(async move {
+                        match sale.await {
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                            Ok(None) => {
+                                view! {
+                                    <p class="text-secondary">
+                                        "Look up a sale by its invoice number to start a return."
+                                    </p>
+                                }
+                                    .into_any()
+                            }
+                            Ok(Some(sale)) => view! { <ReturnForm sale return_action /> }.into_any(),
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ReturnForm(sale: ReturnableSale, return_action: ServerAction<ProcessReturn>) -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let sale_id = sale.sale.id;
+    let deposit_applied = sale.sale.deposit_applied;
+    let prices: HashMap<Uuid, Decimal> = sale.lines.iter().map(|l| (l.id, l.unit_price)).collect();
+    let selected = RwSignal::new(HashMap::<Uuid, (i32, ReturnCondition)>::new());
+    let (refund, set_refund) = signal(String::new());
+    let (method, set_method) = signal(0usize);
+    let (reason, set_reason) = signal(String::new());
+
+    let returned_value = Memo::new(move |_| {
+        selected.with(|lines| {
+            lines
+                .iter()
+                .map(|(id, (qty, _))| {
+                    prices.get(id).copied().unwrap_or_default() * Decimal::from(*qty)
+                })
+                .sum::<Decimal>()
+        })
+    });
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let lines: Vec<ReturnLineInput> = selected.with(|lines| {
+            lines
+                .iter()
+                .filter(|(_, (qty, _))| *qty > 0)
+                .map(|(id, (qty, condition))| ReturnLineInput {
+                    sale_line_id: *id,
+                    quantity: *qty,
+                    condition: *condition,
+                })
+                .collect()
+        });
+        // A blank refund means the full value of what came back
+        let refund_amount = match refund.get().trim() {
+            "" => returned_value.get(),
+            amount => match Decimal::from_str(amount) {
+                Ok(amount) => amount,
+                Err(_) => {
+                    state.update(|s| {
+                        s.add_toast(Arc::new(Notification::new(
+                            "Input Error",
+                            "Enter a valid refund amount.",
+                            NotificationLevel::Warning,
+                        )))
+                    });
+                    return;
+                }
+            },
+        };
+
+        return_action.dispatch(ProcessReturn {
+            payload: ProcessReturnPayload {
+                sale_id,
+                lines,
+                refund_amount,
+                refund_method: RefundMethod::ALL[method.get()],
+                reason: Some(reason.get()).filter(|r| !r.trim().is_empty()),
+            },
+        });
+    };
+
+    view! {
+        <form on:submit=on_submit>
+            <p class="text-secondary">
+                {format!(
+                    "Sale #{} on {} · Total {} · Already refunded {} · Refundable {}",
+                    sale.sale.invoice_number,
+                    sale.sale.created_at.format("%Y-%m-%d %H:%M"),
+                    sale.sale.total_amount,
+                    sale.refunded_amount,
+                    sale.refundable_amount(),
+                )}
+            </p>
+            <Show when=move || !deposit_applied.is_zero()>
+                <p class="text-secondary text-sm">
+                    {format!(
+                        "{deposit_applied} was paid as a reservation deposit and is refunded outside the till."
+                    )}
+                </p>
+            </Show>
+            <table class="data-table">
+                <thead>
+                    <tr>
+                        <th>"Item"</th>
+                        <th>"Sold"</th>
+                        <th>"Unit Price"</th>
+                        <th>"Return"</th>
+                        <th>"Condition"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {sale
+                        .lines
+                        .into_iter()
+                        .map(|line| {
+                            let id = line.id;
+                            let returnable = line.returnable_quantity();
+                            let set_line = move |qty: Option<i32>, condition: Option<ReturnCondition>| {
+                                selected.update(|lines| {
+                                    let line = lines.entry(id).or_insert((0, ReturnCondition::Resellable));
+                                    line.0 = qty.unwrap_or(line.0);
+                                    line.1 = condition.unwrap_or(line.1);
+                                });
+                            };
+                            view! {
+                                <tr>
+                                    <td>{line.product_name}</td>
+                                    <td>
+                                        {format!("{} ({} returned)", line.quantity, line.returned_quantity)}
+                                    </td>
+                                    <td>{line.unit_price.to_string()}</td>
+                                    <td>
+                                        <input
+                                            type="number"
+                                            class="input-field"
+                                            min="0"
+                                            max=returnable.to_string()
+                                            value="0"
+                                            disabled=returnable == 0
+                                            on:input=move |ev| {
+                                                let qty = event_target_value(&ev)
+                                                    .parse::<i32>()
+                                                    .unwrap_or_default()
+                                                    .clamp(0, returnable);
+                                                set_line(Some(qty), None);
+                                            }
+                                        />
+                                    </td>
+                                    <td>
+                                        <select
+                                            class="input-field"
+                                            disabled=returnable == 0
+                                            on:change=move |ev| {
+                                                let condition = event_target_value(&ev)
+                                                    .parse::<usize>()
+                                                    .ok()
+                                                    .and_then(|i| ReturnCondition::ALL.get(i).copied())
+                                                    .unwrap_or(ReturnCondition::Resellable);
+                                                set_line(None, Some(condition));
+                                            }
+                                        >
+                                            {ReturnCondition::ALL
+                                                .iter()
+                                                .enumerate()
+                                                .map(|(i, c)| view! { <option value=i.to_string()>{c.label()}</option> })
+                                                .collect_view()}
+                                        </select>
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+
+            <div class="catalog-filters">
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder=move || format!("Refund (full: {})", returned_value.get())
+                    on:input=move |ev| set_refund.set(event_target_value(&ev))
+                    prop:value=refund
+                />
+                <select
+                    class="input-field"
+                    on:change=move |ev| {
+                        set_method.set(event_target_value(&ev).parse::<usize>().unwrap_or_default())
+                    }
+                >
+                    {RefundMethod::ALL
+                        .iter()
+                        .enumerate()
+                        .map(|(i, m)| view! { <option value=i.to_string()>{m.label()}</option> })
+                        .collect_view()}
+                </select>
+                <input
+                    type="text"
+                    class="input-field"
+                    placeholder="Reason (optional)"
+                    on:input=move |ev| set_reason.set(event_target_value(&ev))
+                    prop:value=reason
+                />
+                <button
+                    type="submit"
+                    class="btn btn-primary"
+                    disabled=move || return_action.pending().get() || returned_value.get().is_zero()
+                >
+                    "Process Return"
+                </button>
+            </div>
+        </form>
+    }
+}
@@ -0,0 +1,298 @@
+use crate::components::TotpSetup;
+use actions::sessions::{list_my_sessions, RevokeOtherSessions, RevokeSession};
+use actions::two_factor::{get_two_factor_status, DisableTotp, SetWorkspaceTwoFactorRequirement};
+use actions::Permission;
+use leptos::prelude::*;
+use models::entities::TotpActivation;
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::{AuthState, SystemState};
+use std::sync::Arc;
+
+/// Two-factor settings for the signed-in handler, plus the workspace policy for admins.
+#[component]
+pub fn SecurityPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let refresh = RwSignal::new(0u32);
+    let status = Resource::new(
+        move || refresh.get(),
+        |_| async move { get_two_factor_status().await },
+    );
+
+    let can_manage_workspace = move || match state.get().auth_state {
+        AuthState::Authenticated(user) => {
+            Permission::ManageWorkspace.is_granted_to(user.handler_role)
+        }
+        _ => false,
+    };
+
+    let (enrolling, set_enrolling) = signal(false);
+    let (code, set_code) = signal(String::new());
+
+    let disable_action = ServerAction::<DisableTotp>::new();
+    let disable_value = disable_action.value();
+    let policy_action = ServerAction::<SetWorkspaceTwoFactorRequirement>::new();
+    let policy_value = policy_action.value();
+
+    let on_enrolled = Callback::new(move |_: TotpActivation| {
+        set_enrolling.set(false);
+        refresh.update(|r| *r += 1);
+    });
+
+    let on_disable = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        disable_action.dispatch(DisableTotp { code: code.get() });
+    };
+
+    Effect::new(move |_| match disable_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(_)) => {
+            set_code.set(String::new());
+            refresh.update(|r| *r += 1);
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Two-Factor Disabled",
+                    "Your account no longer asks for an authenticator code.",
+                    NotificationLevel::Info,
+                )))
+            });
+        }
+        None => {}
+    });
+
+    Effect::new(move |_| match policy_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(_)) => refresh.update(|r| *r += 1),
+        None => {}
+    });
+
+    view! {
+        <div class="system-page-container">
+            <header class="system-page-header">
+                <div>
+                    <h1>"Security"</h1>
+                    <p>"Protect your account with an authenticator app and review where you are signed in."</p>
+                </div>
+            </header>
+
+            <Suspense fallback=|| view! { <p>"Loading..."</p> }>
+                {move || Suspend::new(async move {
+                    match status.await {
+                        Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                        Ok(status) => {
+                            let required = status.workspace_requires;
+                            view! {
+                                <section class="catalog-add-form">
+                                    <div class="form-header">
+                                        <h3>"Two-Factor Authentication"</h3>
+                                    </div>
+                                    {if status.enabled {
+                                        view! {
+                                            <p>
+                                                "Enabled. "
+                                                {status.recovery_codes_remaining}
+                                                " recovery codes remaining."
+                                            </p>
+                                            <Show
+                                                when=move || !required
+                                                fallback=|| {
+                                                    view! {
+                                                        <p class="text-secondary">
+                                                            "Your workspace requires two-factor authentication, so it cannot be turned off."
+                                                        </p>
+                                                    }
+                                                }
+                                            >
+                                                <form class="totp-setup" on:submit=on_disable>
+                                                    <div class="totp-confirm">
+                                                        <input
+                                                            type="text"
+                                                            class="input-field"
+                                                            autocomplete="one-time-code"
+                                                            placeholder="Code or recovery code"
+                                                            required
+                                                            on:input=move |ev| set_code.set(event_target_value(&ev))
+                                                            prop:value=code
+                                                        />
+                                                        <button
+                                                            type="submit"
+                                                            class="btn btn-secondary"
+                                                            disabled=move || disable_action.pending().get()
+                                                        >
+                                                            "Disable"
+                                                        </button>
+                                                    </div>
+                                                </form>
+                                            </Show>
+                                        }
+                                            .into_any()
+                                    } else {
+                                        view! {
+                                            <Show
+                                                when=move || enrolling.get()
+                                                fallback=move || {
+                                                    view! {
+                                                        <p>"Not enabled."</p>
+                                                        <button
+                                                            type="button"
+                                                            class="btn btn-primary"
+                                                            on:click=move |_| set_enrolling.set(true)
+                                                        >
+                                                            "Set Up Authenticator"
+                                                        </button>
+                                                    }
+                                                }
+                                            >
+                                                <TotpSetup on_done=on_enrolled />
+                                            </Show>
+                                        }
+                                            .into_any()
+                                    }}
+                                </section>
+
+                                <Show when=can_manage_workspace>
+                                    <section class="catalog-add-form">
+                                        <div class="form-header">
+                                            <h3>"Workspace Policy"</h3>
+                                        </div>
+                                        <label class="flex items-center gap-2">
+                                            <input
+                                                type="checkbox"
+                                                prop:checked=required
+                                                disabled=move || policy_action.pending().get()
+                                                on:change=move |ev| {
+                                                    policy_action
+                                                        .dispatch(SetWorkspaceTwoFactorRequirement {
+                                                            required: event_target_checked(&ev),
+                                                        });
+                                                }
+                                            />
+                                            "Require two-factor authentication for admins and managers"
+                                        </label>
+                                    </section>
+                                </Show>
+                            }
+                                .into_any()
+                        }
+                    }
+                })}
+            </Suspense>
+
+            <SessionList />
+        </div>
+    }
+}
+
+/// The handler's signed-in devices, each revocable except the one in use.
+#[component]
+fn SessionList() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let refresh = RwSignal::new(0u32);
+    let sessions = Resource::new(
+        move || refresh.get(),
+        |_| async move { list_my_sessions().await },
+    );
+
+    let revoke_action = ServerAction::<RevokeSession>::new();
+    let revoke_value = revoke_action.value();
+    let revoke_others_action = ServerAction::<RevokeOtherSessions>::new();
+    let revoke_others_value = revoke_others_action.value();
+
+    Effect::new(move |_| {
+        for outcome in [
+            revoke_value.get(),
+            revoke_others_value.get().map(|r| r.map(|_| ())),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            match outcome {
+                Err(e) => state.update(|s| s.add_toast(Arc::new(e))),
+                Ok(()) => refresh.update(|r| *r += 1),
+            }
+        }
+    });
+
+    view! {
+        <section class="catalog-add-form">
+            <div class="form-header flex justify-between items-center">
+                <h3>"Signed-In Devices"</h3>
+                <button
+                    type="button"
+                    class="btn btn-ghost"
+                    disabled=move || revoke_others_action.pending().get()
+                    on:click=move |_| {
+                        revoke_others_action.dispatch(RevokeOtherSessions {});
+                    }
+                >
+                    "Sign Out Everywhere Else"
+                </button>
+            </div>
+            <Suspense fallback=|| view! { <p class="text-secondary">"Loading sessions..."</p> }>
+                {move || Suspend::new(async move {
+                    match sessions.await {
+                        Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                        Ok(sessions) => {
+                            view! {
+                                <table class="data-table">
+                                    <thead>
+                                        <tr>
+                                            <th>"Device"</th>
+                                            <th>"IP Address"</th>
+                                            <th>"Signed In"</th>
+                                            <th>"Last Seen"</th>
+                                            <th></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {sessions
+                                            .into_iter()
+                                            .map(|session| {
+                                                let id = session.id;
+                                                let is_current = session.is_current;
+                                                view! {
+                                                    <tr>
+                                                        <td>
+                                                            {session
+                                                                .user_agent
+                                                                .unwrap_or_else(|| "Unknown device".to_string())}
+                                                        </td>
+                                                        <td class="font-mono">
+                                                            {session.ip_address.unwrap_or_default()}
+                                                        </td>
+                                                        <td>{session.created_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                                        <td>
+                                                            {session.last_seen_at.format("%Y-%m-%d %H:%M").to_string()}
+                                                        </td>
+                                                        <td class="text-right">
+                                                            <Show
+                                                                when=move || !is_current
+                                                                fallback=|| view! { <span class="badge">"This device"</span> }
+                                                            >
+                                                                <button
+                                                                    class="btn btn-ghost"
+                                                                    disabled=move || revoke_action.pending().get()
+                                                                    on:click=move |_| {
+                                                                        revoke_action.dispatch(RevokeSession { session_id: id });
+                                                                    }
+                                                                >
+                                                                    "Revoke"
+                                                                </button>
+                                                            </Show>
+                                                        </td>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </tbody>
+                                </table>
+                            }
+                                .into_any()
+                        }
+                    }
+                })}
+            </Suspense>
+        </section>
+    }
+}
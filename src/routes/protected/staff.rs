@@ -0,0 +1,275 @@
+use actions::{
+    list_handlers, ChangeHandlerRole, DeactivateHandler, InviteHandler, RevokeHandlerSessions,
+};
+use leptos::prelude::*;
+use models::entities::HandlerRole;
+use models::payloads::InviteHandlerPayload;
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::SystemState;
+use std::sync::Arc;
+
+fn parse_role(value: &str) -> HandlerRole {
+    value
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| HandlerRole::ALL.get(i).copied())
+        .unwrap_or(HandlerRole::SystemSalesman)
+}
+
+fn role_index(role: HandlerRole) -> String {
+    HandlerRole::ALL
+        .iter()
+        .position(|r| *r == role)
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[component]
+pub fn StaffPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let refresh = RwSignal::new(0u32);
+    let handlers = Resource::new(
+        move || refresh.get(),
+        |_| async move { list_handlers().await },
+    );
+
+    let (email, set_email) = signal(String::new());
+    let (user_name, set_user_name) = signal(String::new());
+    let (role, set_role) = signal(HandlerRole::SystemSalesman);
+
+    let invite_action = ServerAction::<InviteHandler>::new();
+    let invite_value = invite_action.value();
+    let role_action = ServerAction::<ChangeHandlerRole>::new();
+    let role_value = role_action.value();
+    let deactivate_action = ServerAction::<DeactivateHandler>::new();
+    let deactivate_value = deactivate_action.value();
+    let revoke_action = ServerAction::<RevokeHandlerSessions>::new();
+    let revoke_value = revoke_action.value();
+
+    let on_invite = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        invite_action.dispatch(InviteHandler {
+            payload: InviteHandlerPayload {
+                email: email.get(),
+                user_name: user_name.get(),
+                handler_role: role.get(),
+            },
+        });
+    };
+
+    Effect::new(move |_| {
+        if let Some(Ok(issued)) = invite_value.get() {
+            set_email.set(String::new());
+            set_user_name.set(String::new());
+            state.update(|s| {
+                s.add_toast(Arc::new(Notification::new(
+                    "Invite Created",
+                    format!("Share the invite link with {}.", issued.invite.email),
+                    NotificationLevel::Success,
+                )))
+            });
+        }
+    });
+
+    Effect::new(move |_| match revoke_value.get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(revoked)) => state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                "Signed Out",
+                format!("Ended {revoked} session(s)."),
+                NotificationLevel::Success,
+            )))
+        }),
+        None => {}
+    });
+
+    // Errors from any staff action surface as toasts; successes reload the list
+    Effect::new(move |_| {
+        for outcome in [
+            invite_value.get().map(|r| r.map(|_| ())),
+            role_value.get().map(|r| r.map(|_| ())),
+            deactivate_value.get().map(|r| r.map(|_| ())),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            match outcome {
+                Err(e) => state.update(|s| s.add_toast(Arc::new(e))),
+                Ok(()) => refresh.update(|r| *r += 1),
+            }
+        }
+    });
+
+    view! {
+        <div class="system-page-container">
+            <header class="system-page-header">
+                <div>
+                    <h1>"Staff"</h1>
+                    <p>"Invite managers and salespeople, adjust their roles, or revoke access."</p>
+                </div>
+            </header>
+
+            <form class="catalog-add-form" on:submit=on_invite>
+                <div class="form-header">
+                    <h3>"Invite a Handler"</h3>
+                </div>
+                <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+                    <div class="input-group">
+                        <input
+                            type="text"
+                            class="input-field"
+                            placeholder=" "
+                            required
+                            on:input=move |ev| set_user_name.set(event_target_value(&ev))
+                            prop:value=user_name
+                        />
+                        <label class="input-label">"Name"</label>
+                    </div>
+                    <div class="input-group">
+                        <input
+                            type="email"
+                            class="input-field"
+                            placeholder=" "
+                            required
+                            on:input=move |ev| set_email.set(event_target_value(&ev))
+                            prop:value=email
+                        />
+                        <label class="input-label">"Email"</label>
+                    </div>
+                    <select
+                        class="input-field"
+                        on:change=move |ev| set_role.set(parse_role(&event_target_value(&ev)))
+                        prop:value=move || role_index(role.get())
+                    >
+                        {HandlerRole::ALL
+                            .iter()
+                            .enumerate()
+                            .map(|(i, r)| view! { <option value=i.to_string()>{r.label()}</option> })
+                            .collect_view()}
+                    </select>
+                </div>
+                <div class="flex justify-end mt-4 pt-4 border-t border-[var(--border-default)]">
+                    <button
+                        type="submit"
+                        class="btn btn-primary"
+                        disabled=move || invite_action.pending().get()
+                    >
+                        "Create Invite"
+                    </button>
+                </div>
+                {move || {
+                    invite_value
+                        .get()
+                        .and_then(Result::ok)
+                        .map(|issued| {
+                            view! {
+                                <p class="text-sm text-secondary">
+                                    "Invite link for " {issued.invite.email} ": "
+                                    <code class="font-mono">{issued.accept_path}</code>
+                                </p>
+                            }
+                        })
+                }}
+            </form>
+
+            <div class="system-content-area">
+                <Suspense fallback=|| view! { <p class="text-secondary">"Loading staff..."</p> }>
+                    {move || Suspend::new(async move {
+                        match handlers.await {
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                            Ok(members) => {
+                                view! {
+                                    <table class="data-table">
+                                        <thead>
+                                            <tr>
+                                                <th>"Name"</th>
+                                                <th>"Email"</th>
+                                                <th>"Role"</th>
+                                                <th>"Status"</th>
+                                                <th></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {members
+                                                .into_iter()
+                                                .map(|member| {
+                                                    let id = member.id;
+                                                    let is_active = member.is_active;
+                                                    view! {
+                                                        <tr>
+                                                            <td>{member.user_name}</td>
+                                                            <td>{member.email}</td>
+                                                            <td>
+                                                                <select
+                                                                    class="input-field"
+                                                                    disabled=!is_active
+                                                                    prop:value=role_index(member.handler_role)
+                                                                    on:change=move |ev| {
+                                                                        role_action
+                                                                            .dispatch(ChangeHandlerRole {
+                                                                                handler_id: id,
+                                                                                handler_role: parse_role(&event_target_value(&ev)),
+                                                                            });
+                                                                    }
+                                                                >
+                                                                    {HandlerRole::ALL
+                                                                        .iter()
+                                                                        .enumerate()
+                                                                        .map(|(i, r)| {
+                                                                            view! {
+                                                                                <option value=i.to_string()>{r.label()}</option>
+                                                                            }
+                                                                        })
+                                                                        .collect_view()}
+                                                                </select>
+                                                            </td>
+                                                            <td>
+                                                                <span class="badge">
+                                                                    {if is_active { "Active" } else { "Deactivated" }}
+                                                                </span>
+                                                            </td>
+                                                            <td class="text-right">
+                                                                <Show when=move || is_active>
+                                                                    <button
+                                                                        class="btn btn-ghost"
+                                                                        disabled=move || revoke_action.pending().get()
+                                                                        on:click=move |_| {
+                                                                            revoke_action
+                                                                                .dispatch(RevokeHandlerSessions {
+                                                                                    handler_id: id,
+                                                                                });
+                                                                        }
+                                                                    >
+                                                                        "Sign Out"
+                                                                    </button>
+                                                                    <button
+                                                                        class="btn btn-ghost"
+                                                                        disabled=move || deactivate_action.pending().get()
+                                                                        on:click=move |_| {
+                                                                            deactivate_action
+                                                                                .dispatch(DeactivateHandler {
+                                                                                    handler_id: id,
+                                                                                });
+                                                                        }
+                                                                    >
+                                                                        "Deactivate"
+                                                                    </button>
+                                                                </Show>
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tbody>
+                                    </table>
+                                }
+                                    .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
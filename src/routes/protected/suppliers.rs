@@ -0,0 +1,259 @@
+use super::components::SupplierForm;
+use actions::{
+    get_supplier_reports, get_suppliers, AddSupplier, DeleteSupplier, Permission, UpdateSupplier,
+};
+use leptos::prelude::*;
+use models::entities::{Supplier, SupplierReport};
+use models::payloads::SupplierPayload;
+use models::shared::notifications::{Notification, NotificationLevel};
+use models::system_state::{AuthState, SystemState};
+use std::sync::Arc;
+
+fn percent(rate: Option<f64>) -> String {
+    rate.map(|r| format!("{:.1}%", r * 100.0))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+#[component]
+pub fn SuppliersPage() -> impl IntoView {
+    let state =
+        use_context::<RwSignal<SystemState>>().expect("SystemState context should be provided");
+    let can_view_costs = move || {
+        state.with(|s| match &s.auth_state {
+            AuthState::Authenticated(user) => {
+                Permission::ViewCosts.is_granted_to(user.handler_role)
+            }
+            _ => false,
+        })
+    };
+    let editing = RwSignal::new(None::<Supplier>);
+    let add_action = ServerAction::<AddSupplier>::new();
+    let update_action = ServerAction::<UpdateSupplier>::new();
+    let delete_action = ServerAction::<DeleteSupplier>::new();
+
+    let suppliers = Resource::new(
+        move || {
+            (
+                add_action.version().get(),
+                update_action.version().get(),
+                delete_action.version().get(),
+            )
+        },
+        |_| async move { get_suppliers().await },
+    );
+    let reports = Resource::new(
+        move || (add_action.version().get(), delete_action.version().get()),
+        |_| async move { get_supplier_reports().await },
+    );
+
+    let notify = move |title: &str, message: String| {
+        state.update(|s| {
+            s.add_toast(Arc::new(Notification::new(
+                title,
+                message,
+                NotificationLevel::Success,
+            )))
+        });
+    };
+
+    Effect::new(move |_| match add_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(supplier)) => notify("Supplier Added", format!("'{}' is on file.", supplier.name)),
+        None => {}
+    });
+
+    Effect::new(move |_| match update_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(supplier)) => {
+            editing.set(None);
+            notify("Supplier Updated", format!("Saved '{}'.", supplier.name));
+        }
+        None => {}
+    });
+
+    Effect::new(move |_| match delete_action.value().get() {
+        Some(Err(e)) => state.update(|s| s.add_toast(Arc::new(e))),
+        Some(Ok(())) => notify("Supplier Deleted", "The supplier was removed.".to_string()),
+        None => {}
+    });
+
+    let row = move |supplier: Supplier| {
+        let id = supplier.id;
+        let edit = supplier.clone();
+        view! {
+            <tr>
+                <td>{supplier.name}</td>
+                <td>{supplier.contact_name.unwrap_or_default()}</td>
+                <td class="font-mono">{supplier.phone.unwrap_or_default()}</td>
+                <td>{supplier.email.unwrap_or_default()}</td>
+                <td>
+                    <div class="flex gap-2">
+                        <button
+                            class="btn btn-ghost"
+                            on:click=move |_| editing.set(Some(edit.clone()))
+                        >
+                            "Edit"
+                        </button>
+                        <button
+                            class="btn btn-ghost"
+                            disabled=move || delete_action.pending().get()
+                            on:click=move |_| {
+                                delete_action.dispatch(DeleteSupplier { supplier_id: id });
+                            }
+                        >
+                            "Delete"
+                        </button>
+                    </div>
+                </td>
+            </tr>
+        }
+    };
+
+    let report_row = |report: SupplierReport| {
+        view! {
+            <tr>
+                <td>{report.supplier_name.clone()}</td>
+                <td>{report.tracked_units}</td>
+                <td>{report.bulk_units}</td>
+                <td>{report.total_cost.to_string()}</td>
+                <td>{report.units_sold}</td>
+                <td>{percent(report.return_rate())}</td>
+                <td>{percent(report.rma_rate())}</td>
+            </tr>
+        }
+    };
+
+    view! {
+        <div class="system-page-container">
+            <header class="system-page-header">
+                <div>
+                    <h1>"Suppliers"</h1>
+                    <p>
+                        "Who you buy stock from. Pick a supplier when receiving a delivery to build up their purchasing record."
+                    </p>
+                </div>
+            </header>
+
+            <details class="system-content-area mb-4">
+                <summary class="cursor-pointer font-bold">"Add Supplier"</summary>
+                <SupplierForm
+                    initial=SupplierPayload::default()
+                    submit_label="Save Supplier"
+                    pending=add_action.pending()
+                    on_submit=Callback::new(move |payload| {
+                        add_action.dispatch(AddSupplier { payload });
+                    })
+                />
+            </details>
+
+            {move || {
+                editing
+                    .get()
+                    .map(|supplier| {
+                        let supplier_id = supplier.id;
+                        let initial = SupplierPayload {
+                            name: supplier.name.clone(),
+                            contact_name: supplier.contact_name,
+                            phone: supplier.phone,
+                            email: supplier.email,
+                            notes: supplier.notes,
+                        };
+                        view! {
+                            <section class="system-content-area mb-4">
+                                <div class="flex justify-between items-center">
+                                    <h2 class="text-lg font-bold text-primary">
+                                        {format!("Edit {}", supplier.name)}
+                                    </h2>
+                                    <button class="btn btn-ghost" on:click=move |_| editing.set(None)>
+                                        "Cancel"
+                                    </button>
+                                </div>
+                                <SupplierForm
+                                    initial
+                                    submit_label="Save Changes"
+                                    pending=update_action.pending()
+                                    on_submit=Callback::new(move |payload| {
+                                        update_action.dispatch(UpdateSupplier { supplier_id, payload });
+                                    })
+                                />
+                            </section>
+                        }
+                    })
+            }}
+
+            <div class="system-content-area">
+                <Suspense fallback=|| {
+                    view! { <p class="text-secondary">"Loading suppliers..."</p> }
+                }>
+                    {move || Suspend::new(async move {
+                        match suppliers.await {
+                            Err(e) => view! { <p class="text-secondary">{e.to_string()}</p> }.into_any(),
+                            Ok(list) if list.is_empty() => {
+                                view! { <p class="text-secondary">"No suppliers yet."</p> }
+                                    .into_any()
+                            }
+                            Ok(list) => {
+                                view! {
+                                    <table class="data-table">
+                                        <thead>
+                                            <tr>
+                                                <th>"Name"</th>
+                                                <th>"Contact"</th>
+                                                <th>"Phone"</th>
+                                                <th>"Email"</th>
+                                                <th></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>{list.into_iter().map(row).collect_view()}</tbody>
+                                    </table>
+                                }
+                                    .into_any()
+                            }
+                        }
+                    })}
+                </Suspense>
+            </div>
+
+            <Show when=can_view_costs>
+                <section class="system-content-area mt-4">
+                    <h2 class="text-lg font-bold text-primary">"Purchasing Report"</h2>
+                    <p class="text-secondary text-sm">
+                        "Returns and RMA are tracked per serialized unit; bulk stock is counted when received only."
+                    </p>
+                    <Suspense fallback=|| {
+                        view! { <p class="text-secondary">"Loading report..."</p> }
+                    }>
+                        {move || Suspend::new(async move {
+                            match reports.await {
+                                Err(e) => {
+                                    view! { <p class="text-secondary">{e.to_string()}</p> }.into_any()
+                                }
+                                Ok(list) => {
+                                    view! {
+                                        <table class="data-table">
+                                            <thead>
+                                                <tr>
+                                                    <th>"Supplier"</th>
+                                                    <th>"Tracked Units"</th>
+                                                    <th>"Bulk Units"</th>
+                                                    <th>"Total Cost"</th>
+                                                    <th>"Units Sold"</th>
+                                                    <th>"Return Rate"</th>
+                                                    <th>"RMA Rate"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {list.into_iter().map(report_row).collect_view()}
+                                            </tbody>
+                                        </table>
+                                    }
+                                        .into_any()
+                                }
+                            }
+                        })}
+                    </Suspense>
+                </section>
+            </Show>
+        </div>
+    }
+}